@@ -147,7 +147,9 @@ export const axisFactory = (settings: Settings): AxisFactory => {
             },
             size: component.size,
             decorate: component.decorate,
-            label: settings[settingName].map((v) => v.name).join(", "),
+            label: settings[settingName]
+                .map((v) => settings.column_aliases?.[v.name] ?? v.name)
+                .join(", "),
             tickFormatFunction,
         };
     };
@@ -51,7 +51,7 @@ export const splitterLabels = (settings: Settings): SplitterLabels => {
         const coloured = color && settings.splitValues.length === 0;
         labelDataJoin(selection, labels)
             .classed("disabled", disabled)
-            .text((d) => d.name)
+            .text((d) => settings.column_aliases?.[d.name] ?? d.name)
             .style("color", (d) =>
                 coloured ? withoutOpacity(color(d.name)) : undefined
             )
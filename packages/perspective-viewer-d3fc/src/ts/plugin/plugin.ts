@@ -31,6 +31,7 @@ const DEFAULT_PLUGIN_SETTINGS = {
 
 const styleWithD3FC = `${style}${getD3FCStyles()}`;
 const EXCLUDED_SETTINGS = [
+    "column_aliases",
     "crossValues",
     "mainValues",
     "realValues",
@@ -104,6 +104,12 @@ export type Settings = {
     sunburstLevel?: any;
     columns?: ColumnSettings;
     treemaps?: Record<string, TreemapValue>;
+
+    /**
+     * Display names for columns, keyed by column name, from the viewer's
+     * `column_aliases`.
+     */
+    column_aliases?: Record<string, string>;
 };
 
 export type Orientation = "vertical" | "horizontal" | "both";
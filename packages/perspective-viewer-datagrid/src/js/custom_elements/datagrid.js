@@ -87,6 +87,11 @@ export class HTMLPerspectiveViewerDatagridPluginElement extends HTMLElement {
         return 1;
     }
 
+    /** opt-in to row expansion state in `save()` and `restore()` */
+    get persist_expansion() {
+        return true;
+    }
+
     /** opt-in to column styling */
     get plugin_attributes() {
         return {
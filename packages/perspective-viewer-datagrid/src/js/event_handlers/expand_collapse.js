@@ -10,22 +10,24 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-export async function expandCollapseHandler(regularTable, event) {
+import { record_depth, record_expansion } from "../model/expansion.js";
+
+export async function expandCollapseHandler(regularTable, plugin, event) {
     const meta = regularTable.getMeta(event.target);
     const is_collapse = event.target.classList.contains(
         "psp-tree-label-collapse"
     );
-    if (event.shiftKey && is_collapse) {
-        this._view.set_depth(
-            meta.row_header.filter((x) => x !== undefined).length - 2
-        );
-    } else if (event.shiftKey) {
-        this._view.set_depth(
-            meta.row_header.filter((x) => x !== undefined).length - 1
-        );
+    if (event.shiftKey) {
+        const depth =
+            meta.row_header.filter((x) => x !== undefined).length -
+            (is_collapse ? 2 : 1);
+        record_depth.call(plugin, depth);
+        this._view.set_depth(depth);
     } else if (is_collapse) {
+        await record_expansion.call(plugin, this._view, meta.y, false);
         this._view.collapse(meta.y);
     } else {
+        await record_expansion.call(plugin, this._view, meta.y, true);
         this._view.expand(meta.y);
     }
     this._num_rows = await this._view.num_rows();
@@ -13,7 +13,12 @@
 import { sortHandler } from "./sort.js";
 import { expandCollapseHandler } from "./expand_collapse.js";
 
-export async function mousedown_listener(regularTable, viewer, event) {
+export async function mousedown_listener(
+    regularTable,
+    viewer,
+    plugin,
+    event
+) {
     if (event.which !== 1) {
         return;
     }
@@ -31,7 +36,7 @@ export async function mousedown_listener(regularTable, viewer, event) {
     }
 
     if (target.classList.contains("psp-tree-label")) {
-        expandCollapseHandler.call(this, regularTable, event);
+        expandCollapseHandler.call(this, regularTable, plugin, event);
         event.stopImmediatePropagation();
         return;
     }
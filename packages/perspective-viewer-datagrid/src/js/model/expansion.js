@@ -0,0 +1,111 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+function same_row_path(x, y) {
+    return JSON.stringify(x) === JSON.stringify(y);
+}
+
+async function get_row_paths(view, start_row, end_row) {
+    const columns = await view.to_columns({
+        start_row,
+        end_row,
+        start_col: 0,
+        end_col: 1,
+    });
+
+    return columns.__ROW_PATH__ || [];
+}
+
+/**
+ * Apply this plugin's `expansion` state, as restored from the viewer's
+ * config, to a `View`.  A new `View` is fully expanded, so this must be
+ * re-applied every time the `View` changes.
+ *
+ * @param {perspective.View} view
+ */
+export async function apply_expansion(view) {
+    const { depth, overrides = [] } = this._expansion || {};
+    const config = await view.get_config();
+    if (config.group_by.length === 0) {
+        return;
+    }
+
+    if (depth !== undefined) {
+        await view.set_depth(depth);
+    }
+
+    // Row paths are fetched once per pass, and each pass applies the overrides
+    // for visible rows from the bottom up, so that expanding or collapsing a
+    // row does not shift the index of the rows above it.  Overrides for rows
+    // hidden under a collapsed parent wait for a later pass, in case a parent
+    // override in this pass reveals them.
+    let pending = overrides;
+    while (pending.length > 0) {
+        const row_paths = await get_row_paths(view);
+        const visible = [];
+        const hidden = [];
+        for (const override of pending) {
+            const idx = row_paths.findIndex((x) =>
+                same_row_path(x, override.row_path)
+            );
+
+            if (idx === -1) {
+                hidden.push(override);
+            } else {
+                visible.push({ idx, expanded: override.expanded });
+            }
+        }
+
+        if (visible.length === 0) {
+            break;
+        }
+
+        visible.sort((x, y) => y.idx - x.idx);
+        for (const { idx, expanded } of visible) {
+            if (expanded) {
+                await view.expand(idx);
+            } else {
+                await view.collapse(idx);
+            }
+        }
+
+        pending = hidden;
+    }
+}
+
+/**
+ * Record a row expanded or collapsed by the user in this plugin's `expansion`
+ * state, replacing any previous override for the same row.
+ *
+ * @param {perspective.View} view
+ * @param {number} y The row index.
+ * @param {boolean} expanded
+ */
+export async function record_expansion(view, y, expanded) {
+    const [row_path] = await get_row_paths(view, y, y + 1);
+    const { depth, overrides = [] } = this._expansion || {};
+    this._expansion = {
+        depth,
+        overrides: overrides
+            .filter((x) => !same_row_path(x.row_path, row_path))
+            .concat([{ row_path, expanded }]),
+    };
+}
+
+/**
+ * Record a `set_depth()` by the user, which discards all overrides.
+ *
+ * @param {number} depth
+ */
+export function record_depth(depth) {
+    this._expansion = { depth, overrides: [] };
+}
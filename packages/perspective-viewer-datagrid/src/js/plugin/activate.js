@@ -72,7 +72,8 @@ export async function activate(view) {
             column_header_style_listener.bind(
                 this.model,
                 this.regular_table,
-                viewer
+                viewer,
+                this
             )
         );
 
@@ -83,7 +84,12 @@ export async function activate(view) {
 
         this.regular_table.addEventListener(
             "mousedown",
-            mousedown_listener.bind(this.model, this.regular_table, viewer)
+            mousedown_listener.bind(
+                this.model,
+                this.regular_table,
+                viewer,
+                this
+            )
         );
 
         // Row selection
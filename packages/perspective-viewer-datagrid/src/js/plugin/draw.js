@@ -12,6 +12,7 @@
 
 import { restore_column_size_overrides } from "../model/column_overrides.js";
 import { save_column_size_overrides } from "../model/column_overrides.js";
+import { apply_expansion } from "../model/expansion.js";
 
 /**
  * Draw this datagrid instance.
@@ -22,6 +23,8 @@ import { save_column_size_overrides } from "../model/column_overrides.js";
 export async function draw(view) {
     if (this.parentElement) {
         await this.activate(view);
+        await apply_expansion.call(this, view);
+        this.model._num_rows = await view.num_rows();
     }
 
     if (!this.isConnected || this.offsetParent == null) {
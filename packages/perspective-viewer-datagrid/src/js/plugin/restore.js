@@ -67,6 +67,14 @@ export function restore(token) {
         toggle_scroll_lock.call(this, token.scroll_lock);
     }
 
+    if ("expansion" in token) {
+        this._expansion = token.expansion;
+    }
+
+    if ("column_aliases" in token) {
+        this._column_aliases = token.column_aliases;
+    }
+
     const datagrid = this.regular_table;
     restore_column_size_overrides.call(this, overrides, true);
     datagrid[PRIVATE_PLUGIN_SYMBOL] = token.columns;
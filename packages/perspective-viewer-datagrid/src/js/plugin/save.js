@@ -27,6 +27,10 @@ export function save() {
             editable: !!this._is_edit_mode,
         };
 
+        if (this._expansion) {
+            token.expansion = this._expansion;
+        }
+
         for (const col of Object.keys(datagrid[PRIVATE_PLUGIN_SYMBOL] || {})) {
             const config = Object.assign(
                 {},
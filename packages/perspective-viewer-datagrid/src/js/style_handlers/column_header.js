@@ -58,7 +58,12 @@ export function style_selected_column(regularTable, viewer, selectedColumn) {
                 const title = titles[i];
                 const editBtn = editBtns[i];
 
-                let open = title.innerText === selectedColumn;
+                const title_name =
+                    regularTable.getMeta(title)?.column_header?.[
+                        this._config.split_by.length
+                    ];
+
+                let open = title_name === selectedColumn;
                 title.classList.toggle("psp-menu-open", open);
                 editBtn.classList.toggle("psp-menu-open", open);
                 if (this._config.columns.length > 1) {
@@ -76,7 +81,7 @@ export function style_selected_column(regularTable, viewer, selectedColumn) {
     }
 }
 
-export function column_header_style_listener(regularTable, viewer) {
+export function column_header_style_listener(regularTable, viewer, plugin) {
     let group_header_trs = Array.from(
         regularTable.children[0].children[0].children
     );
@@ -94,7 +99,14 @@ export function column_header_style_listener(regularTable, viewer) {
             1
         );
 
-        style_column_header_row.call(this, regularTable, col_headers, false);
+        const aliases = plugin._column_aliases || {};
+        style_column_header_row.call(
+            this,
+            regularTable,
+            col_headers,
+            false,
+            aliases
+        );
 
         let [style_menu_headers] = group_header_trs.splice(
             this._config.split_by.length,
@@ -112,7 +124,34 @@ export function column_header_style_listener(regularTable, viewer) {
     }
 }
 
-function style_column_header_row(regularTable, col_headers, is_menu_row) {
+/**
+ * Replace the text of a column header `th` with the column's display name.
+ * Only the text is changed, as the header's metadata (and hence every other
+ * style and event handler) identifies the column by name.
+ */
+function set_header_label(th, column_name, label) {
+    const walker = document.createTreeWalker(th, NodeFilter.SHOW_TEXT);
+    while (walker.nextNode()) {
+        const node = walker.currentNode;
+        if (node.data === column_name || node.data === th.dataset.alias) {
+            node.data = label;
+            break;
+        }
+    }
+
+    if (label === column_name) {
+        delete th.dataset.alias;
+    } else {
+        th.dataset.alias = label;
+    }
+}
+
+function style_column_header_row(
+    regularTable,
+    col_headers,
+    is_menu_row,
+    aliases = {}
+) {
     // regular header styling
     const header_depth = regularTable._view_cache.config.row_pivots.length - 1;
     for (const td of col_headers?.children) {
@@ -147,6 +186,19 @@ function style_column_header_row(regularTable, col_headers, is_menu_row) {
             !is_menu_row && !!sort && sort[1] === "col desc"
         );
 
+        if (
+            !is_menu_row &&
+            !is_corner &&
+            column_name !== undefined &&
+            (aliases[column_name] !== undefined || td.dataset.alias)
+        ) {
+            set_header_label(
+                td,
+                column_name,
+                aliases[column_name] ?? column_name
+            );
+        }
+
         let type = get_psp_type.call(this, metadata);
         const is_numeric = type === "integer" || type === "float";
         const is_string = type === "string";
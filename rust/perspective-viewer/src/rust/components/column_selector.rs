@@ -14,14 +14,18 @@ mod active_column;
 mod add_expression_button;
 mod aggregate_selector;
 mod config_selector;
+mod date_range_input;
 mod empty_column;
 mod expression_toolbar;
 mod filter_column;
 mod inactive_column;
 mod invalid_column;
+mod linked_filter_selector;
 mod pivot_column;
 mod sort_column;
+mod top_n_selector;
 
+use std::collections::HashMap;
 use std::iter::*;
 use std::rc::Rc;
 
@@ -39,6 +43,7 @@ use super::containers::split_panel::{Orientation, SplitPanel};
 use super::style::LocalStyle;
 use super::viewer::ColumnLocator;
 use crate::components::containers::scroll_panel_item::ScrollPanelItem;
+use crate::config::FilterTree;
 use crate::custom_elements::ColumnDropDownElement;
 use crate::dragdrop::*;
 use crate::model::*;
@@ -82,7 +87,7 @@ pub enum ColumnSelectorMsg {
     HoverActiveIndex(Option<usize>),
     Drag(DragEffect),
     DragEnd,
-    Drop((String, DragTarget, DragEffect, usize)),
+    Drop((String, DragTarget, DragEffect, usize, Option<usize>)),
 }
 
 use ColumnSelectorMsg::*;
@@ -90,7 +95,7 @@ use ColumnSelectorMsg::*;
 /// A `ColumnSelector` controls the `columns` field of the `ViewConfig`,
 /// deriving its options from the table columns and `ViewConfig` expressions.
 pub struct ColumnSelector {
-    _subscriptions: [Subscription; 5],
+    _subscriptions: [Subscription; 6],
     named_row_count: usize,
     drag_container: DragDropContainer,
     column_dropdown: ColumnDropDownElement,
@@ -127,6 +132,14 @@ impl Component for ColumnSelector {
             ctx.props().dragdrop.dragend_received.add_listener(cb)
         };
 
+        let aliases_sub = {
+            let cb = ctx.link().callback(|_| ColumnSelectorMsg::ViewCreated);
+            ctx.props()
+                .presentation
+                .column_aliases_changed
+                .add_listener(cb)
+        };
+
         let named = maybe! {
             let plugin =
                 ctx.props().renderer.get_active_plugin().ok()?;
@@ -142,7 +155,14 @@ impl Component for ColumnSelector {
 
         let column_dropdown = ColumnDropDownElement::new(ctx.props().session.clone());
         Self {
-            _subscriptions: [table_sub, view_sub, drop_sub, drag_sub, dragend_sub],
+            _subscriptions: [
+                table_sub,
+                view_sub,
+                drop_sub,
+                drag_sub,
+                dragend_sub,
+                aliases_sub,
+            ],
             named_row_count,
             drag_container,
             column_dropdown,
@@ -173,10 +193,17 @@ impl Component for ColumnSelector {
                 ctx.props().dragdrop.notify_drag_leave(DragTarget::Active);
                 true
             },
-            Drop((column, DragTarget::Active, DragEffect::Move(DragTarget::Active), index)) => {
+            Drop((
+                column,
+                DragTarget::Active,
+                DragEffect::Move(DragTarget::Active),
+                index,
+                from_index,
+            )) => {
                 if !ctx.props().is_invalid_columns_column(&column, index) {
                     let update = ctx.props().session.create_drag_drop_update(
                         column,
+                        from_index,
                         index,
                         DragTarget::Active,
                         DragEffect::Move(DragTarget::Active),
@@ -187,9 +214,10 @@ impl Component for ColumnSelector {
                 }
                 true
             },
-            Drop((column, DragTarget::Active, effect, index)) => {
+            Drop((column, DragTarget::Active, effect, index, from_index)) => {
                 let update = ctx.props().session.create_drag_drop_update(
                     column,
+                    from_index,
                     index,
                     DragTarget::Active,
                     effect,
@@ -199,7 +227,7 @@ impl Component for ColumnSelector {
                 ApiFuture::spawn(ctx.props().update_and_render(update));
                 true
             },
-            Drop((_, _, DragEffect::Move(DragTarget::Active), _)) => true,
+            Drop((_, _, DragEffect::Move(DragTarget::Active), ..)) => true,
             Drop((..)) => true,
         }
     }
@@ -233,7 +261,8 @@ impl Component for ColumnSelector {
         let size_hint = 28.0f64.mul_add(
             (config.group_by.len()
                 + config.split_by.len()
-                + config.filter.len()
+                + config.filter.iter_filters().count()
+                + config.filter.iter_groups().count()
                 + config.sort.len()) as f64,
             220.0,
         );
@@ -247,6 +276,7 @@ impl Component for ColumnSelector {
                     dragdrop={&ctx.props().dragdrop}
                     session={&ctx.props().session}
                     renderer={&ctx.props().renderer}
+                    presentation={&ctx.props().presentation}
                     onselect={onselect.clone()}
                     ondragenter={ctx.link().callback(|()| ViewCreated)}
                 />
@@ -254,6 +284,7 @@ impl Component for ColumnSelector {
         };
 
         let mut named_count = self.named_row_count;
+        let mut occurrences = HashMap::<String, usize>::new();
         let mut active_columns: Vec<_> = columns_iter
             .active()
             .enumerate()
@@ -261,16 +292,23 @@ impl Component for ColumnSelector {
                 let ondragenter = ondragenter.reform(move |_| Some(idx));
                 let size_hint = if named_count > 0 { 50.0 } else { 28.0 };
                 named_count = named_count.saturating_sub(1);
-                let key = name
-                    .get_name()
-                    .map(|x| x.to_owned())
-                    .unwrap_or_else(|| format!("__auto_{}__", idx));
+                let occurrence = name.get_name().map_or(0, |x| {
+                    let count = occurrences.entry(x.to_owned()).or_default();
+                    *count += 1;
+                    *count - 1
+                });
+
+                let key = match name.get_name() {
+                    Some(x) if occurrence > 0 => format!("{}__{}", x, occurrence),
+                    Some(x) => x.to_owned(),
+                    None => format!("__auto_{}__", idx),
+                };
 
                 let column_dropdown = self.column_dropdown.clone();
                 let is_editing = matches!(
                     &ctx.props().selected_column,
-                    Some(ColumnLocator::Plain(x)) | Some(ColumnLocator::Expr(Some(x))) if x == &key
-                );
+                    Some(ColumnLocator::Plain(x)) | Some(ColumnLocator::Expr(Some(x))) if Some(x.as_str()) == name.get_name()
+                ) && occurrence == 0;
 
                 let on_open_expr_panel = &ctx.props().on_open_expr_panel;
                 html_nested! {
@@ -281,6 +319,7 @@ impl Component for ColumnSelector {
                         <ActiveColumn
                             {column_dropdown}
                             {idx}
+                            {occurrence}
                             {is_aggregated}
                             {is_editing}
                             {name}
@@ -10,7 +10,7 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use web_sys::*;
 use yew::prelude::*;
@@ -41,6 +41,12 @@ enum ColumnState {
 #[derive(Properties, Clone)]
 pub struct ActiveColumnProps {
     pub idx: usize,
+
+    /// The number of earlier entries of this column in `columns`, which
+    /// selects this entry's aggregate.
+    #[prop_or_default]
+    pub occurrence: usize,
+
     pub name: ActiveColumnState,
     pub dragdrop: DragDrop,
     pub session: Session,
@@ -94,11 +100,14 @@ impl ActiveColumnProps {
     /// `shift` flag).
     ///
     /// # Arguments
-    /// - `name` The name of the column to de-activate, which is a unique ID
-    ///   with respect to `columns`.
+    /// - `name` The name of the column to de-activate, which may appear in
+    ///   `columns` more than once, in which case only the entry at this
+    ///   column's `idx` is removed.
     /// - `shift` whether to toggle or select this column.
     pub fn deactivate_column(&self, name: String, shift: bool) {
-        let mut columns = self.session.get_view_config().columns.clone();
+        let config = self.session.get_view_config().clone();
+        let mut columns = config.columns.clone();
+        let mut aggregates = config.aggregates.clone();
         let max_cols = self
             .renderer
             .metadata()
@@ -106,27 +115,92 @@ impl ActiveColumnProps {
             .as_ref()
             .map_or(0, |x| x.len());
 
+        let index = self.get_index(&config, &name);
+        let occurrence = config.column_occurrence(index);
+        let mut is_removed = true;
         match self.renderer.metadata().mode {
             ColumnSelectMode::Toggle => {
-                let index = columns
-                    .iter()
-                    .position(|x| x.as_ref() == Some(&name))
-                    .unwrap();
-
                 if max_cols > 0 && index < max_cols - 1 {
                     columns[index] = None;
                 } else if !shift && columns.len() > 1 {
-                    columns.retain(|x| x.as_ref() != Some(&name));
+                    columns.remove(index);
                 } else if shift {
+                    is_removed = false;
+                    let agg = config.get_aggregate(&name, occurrence).cloned();
+                    aggregates.insert(name.clone(), ColumnAggregates::default());
+                    aggregates.get_mut(&name).unwrap().set(0, agg);
                     columns.clear();
-                    columns.push(Some(name));
+                    columns.push(Some(name.clone()));
+                } else {
+                    is_removed = false;
                 }
             },
             ColumnSelectMode::Select => {
-                columns.retain(|x| x.as_ref() != Some(&name));
+                columns.remove(index);
             },
         }
-        self.apply_columns(columns);
+
+        if let Some(aggs) = aggregates.get_mut(&name).filter(|_| is_removed) {
+            aggs.remove(occurrence);
+        }
+
+        aggregates.retain(|_, aggs| !aggs.is_empty());
+        self.apply_columns(columns, aggregates);
+    }
+
+    /// Add another entry of this column to `columns` after this one, with the
+    /// first `Aggregate` not already used by an entry of the same column, so
+    /// the column can be shown with several aggregates.
+    pub fn duplicate_column(&self, name: String) {
+        let config = self.session.get_view_config().clone();
+        let mut columns = config.columns.clone();
+        let mut aggregates = config.aggregates.clone();
+        let index = self.get_index(&config, &name);
+        let occurrence = config.column_occurrence(index);
+        let default = self
+            .session
+            .metadata()
+            .get_column_table_type(&name)
+            .map(|x| x.default_aggregate());
+
+        let used = columns
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| x.as_ref() == Some(&name))
+            .map(|(idx, _)| {
+                config
+                    .get_aggregate(&name, config.column_occurrence(idx))
+                    .cloned()
+                    .or_else(|| default.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let agg = self
+            .session
+            .metadata()
+            .get_column_aggregates(&name)
+            .and_then(|mut x| x.find(|x| !used.contains(&Some(x.clone()))));
+
+        columns.insert(index + 1, Some(name.clone()));
+        aggregates
+            .entry(name)
+            .or_default()
+            .insert(occurrence + 1, agg);
+
+        self.apply_columns(columns, aggregates);
+    }
+
+    /// The index of this column in `columns`, which is `idx` unless the
+    /// columns list is out of sync with the `ViewConfig`.
+    fn get_index(&self, config: &ViewConfig, name: &str) -> usize {
+        match config.columns.get(self.idx) {
+            Some(Some(x)) if x == name => self.idx,
+            _ => config
+                .columns
+                .iter()
+                .position(|x| x.as_deref() == Some(name))
+                .unwrap(),
+        }
     }
 
     fn get_is_required(&self) -> bool {
@@ -134,13 +208,21 @@ impl ActiveColumnProps {
         self.idx < min_cols
     }
 
-    fn get_aggregate(&self, name: &str) -> Option<Aggregate> {
-        self.session.get_view_config().aggregates.get(name).cloned()
+    fn get_aggregate(&self, name: &str, occurrence: usize) -> Option<Aggregate> {
+        self.session
+            .get_view_config()
+            .get_aggregate(name, occurrence)
+            .cloned()
     }
 
-    fn apply_columns(&self, columns: Vec<Option<String>>) {
+    fn apply_columns(
+        &self,
+        columns: Vec<Option<String>>,
+        aggregates: HashMap<String, ColumnAggregates>,
+    ) {
         let config = ViewConfigUpdate {
             columns: Some(columns),
+            aggregates: Some(aggregates),
             ..ViewConfigUpdate::default()
         };
 
@@ -150,6 +232,7 @@ impl ActiveColumnProps {
 
 pub enum ActiveColumnMsg {
     DeactivateColumn(String, bool),
+    DuplicateColumn(String),
     MouseEnter(bool),
     MouseLeave(bool),
     New(InPlaceColumn),
@@ -192,6 +275,11 @@ impl Component for ActiveColumn {
                 ctx.props().onselect.emit(());
                 false
             },
+            DuplicateColumn(column) => {
+                ctx.props().duplicate_column(column);
+                ctx.props().onselect.emit(());
+                false
+            },
             MouseEnter(is_render) => {
                 self.mouseover = is_render;
                 is_render
@@ -332,24 +420,39 @@ impl Component for ActiveColumn {
                 } else {
                     Some(ctx.link().callback({
                         let event_name = name.to_owned();
+                        let is_aggregated = ctx.props().is_aggregated;
                         move |event: MouseEvent| {
-                            ActiveColumnMsg::DeactivateColumn(
-                                event_name.to_owned(),
-                                event.shift_key(),
-                            )
+                            if is_aggregated && event.alt_key() {
+                                ActiveColumnMsg::DuplicateColumn(event_name.to_owned())
+                            } else {
+                                ActiveColumnMsg::DeactivateColumn(
+                                    event_name.to_owned(),
+                                    event.shift_key(),
+                                )
+                            }
                         }
                     }))
                 };
 
+                let duplicate_column = ctx.link().callback({
+                    let event_name = name.to_owned();
+                    move |event: MouseEvent| {
+                        event.stop_propagation();
+                        ActiveColumnMsg::DuplicateColumn(event_name.to_owned())
+                    }
+                });
+
                 let ondragend = &ctx.props().ondragend.reform(|_| {});
                 let ondragstart = ctx.link().callback({
                     let event_name = name.to_owned();
                     let dragdrop = ctx.props().dragdrop.clone();
+                    let idx = ctx.props().idx;
                     move |event: DragEvent| {
                         dragdrop.set_drag_image(&event).unwrap();
-                        dragdrop.notify_drag_start(
+                        dragdrop.notify_drag_start_at(
                             event_name.to_string(),
                             DragEffect::Move(DragTarget::Active),
+                            Some(idx),
                         );
 
                         MouseLeave(false)
@@ -362,6 +465,7 @@ impl Component for ActiveColumn {
                     .callback(|event: MouseEvent| MouseEnter(event.which() == 0));
 
                 let is_expression = ctx.props().session.metadata().is_column_expression(&name);
+                let label_text = ctx.props().presentation.get_column_label(&name);
                 let mut class = ctx.props().renderer.metadata().mode.css();
                 if self.is_required {
                     class.push("required");
@@ -411,14 +515,21 @@ impl Component for ActiveColumn {
                                 if ctx.props().is_aggregated {
                                     <AggregateSelector
                                         column={name.clone()}
-                                        aggregate={ctx.props().get_aggregate(&name)}
+                                        aggregate={ctx.props().get_aggregate(&name, ctx.props().occurrence)}
+                                        occurrence={ctx.props().occurrence}
                                         renderer={&ctx.props().renderer}
                                         session={&ctx.props().session}
                                     />
                                 }
-                                <span class="column_name">{ name.clone() }</span>
+                                <span class="column_name">{ label_text }</span>
                                 if !ctx.props().is_aggregated {
                                     <span class="column-selector--spacer" />
+                                } else {
+                                    <span
+                                        class="column-duplicate-button"
+                                        title="Add this column again with another aggregate"
+                                        onmousedown={duplicate_column}
+                                    />
                                 }
                                 if show_edit_btn {
                                     <ExprEditButton
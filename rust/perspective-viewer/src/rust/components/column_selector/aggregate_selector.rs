@@ -25,6 +25,12 @@ use crate::*;
 pub struct AggregateSelectorProps {
     pub column: String,
     pub aggregate: Option<Aggregate>,
+
+    /// Which entry of `column` in `columns` this selector is for, see
+    /// `ViewConfig::column_occurrence`.
+    #[prop_or_default]
+    pub occurrence: usize,
+
     pub renderer: Renderer,
     pub session: Session,
 }
@@ -33,7 +39,9 @@ derive_model!(Renderer, Session for AggregateSelectorProps);
 
 impl PartialEq for AggregateSelectorProps {
     fn eq(&self, rhs: &Self) -> bool {
-        self.column == rhs.column && self.aggregate == rhs.aggregate
+        self.column == rhs.column
+            && self.aggregate == rhs.aggregate
+            && self.occurrence == rhs.occurrence
     }
 }
 
@@ -89,6 +97,11 @@ impl Component for AggregateSelector {
             })
             .unwrap();
 
+        let label = match &selected_agg {
+            Aggregate::MultiAggregate(x, _) => Some(x.as_str()),
+            Aggregate::SingleAggregate(_) => None,
+        };
+
         let values = self.aggregates.clone();
 
         html! {
@@ -102,7 +115,7 @@ impl Component for AggregateSelector {
                     <Select<Aggregate>
                         wrapper_class="aggregate-selector"
                         {values}
-                        label="weighted mean"
+                        {label}
                         selected={selected_agg}
                         on_select={callback}
                     />
@@ -116,7 +129,11 @@ impl AggregateSelector {
     pub fn set_aggregate(&mut self, ctx: &Context<Self>, aggregate: Aggregate) {
         self.aggregate = Some(aggregate.clone());
         let mut aggregates = ctx.props().session.get_view_config().aggregates.clone();
-        aggregates.insert(ctx.props().column.clone(), aggregate);
+        aggregates
+            .entry(ctx.props().column.clone())
+            .or_default()
+            .set(ctx.props().occurrence, Some(aggregate));
+
         let config = ViewConfigUpdate {
             aggregates: Some(aggregates),
             ..ViewConfigUpdate::default()
@@ -134,27 +151,22 @@ impl AggregateSelector {
             .expect("Bad Aggs")
             .collect::<Vec<_>>();
 
-        let multi_aggregates = aggregates
-            .iter()
-            .filter(|x| matches!(x, Aggregate::MultiAggregate(_, _)))
-            .cloned()
-            .collect::<Vec<_>>();
+        let multi_aggregates = MultiAggregate::ALL.iter().filter_map(|multi| {
+            let group = aggregates
+                .iter()
+                .filter(|x| matches!(x, Aggregate::MultiAggregate(y, _) if y == multi))
+                .cloned()
+                .collect::<Vec<_>>();
 
-        let multi_aggregates2 = if !multi_aggregates.is_empty() {
-            vec![SelectItem::OptGroup(
-                "weighted mean".into(),
-                multi_aggregates,
-            )]
-        } else {
-            vec![]
-        };
+            (!group.is_empty()).then(|| SelectItem::OptGroup(multi.as_str().into(), group))
+        });
 
         let s = aggregates
             .iter()
             .filter(|x| matches!(x, Aggregate::SingleAggregate(_)))
             .cloned()
             .map(SelectItem::Option)
-            .chain(multi_aggregates2);
+            .chain(multi_aggregates);
 
         s.collect::<Vec<_>>()
     }
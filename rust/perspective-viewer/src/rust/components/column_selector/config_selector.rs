@@ -16,8 +16,10 @@ use std::rc::Rc;
 use yew::prelude::*;
 
 use super::filter_column::*;
+use super::linked_filter_selector::*;
 use super::pivot_column::*;
 use super::sort_column::*;
+use super::top_n_selector::*;
 use super::InPlaceColumn;
 use crate::components::containers::dragdrop_list::*;
 use crate::components::style::LocalStyle;
@@ -25,6 +27,7 @@ use crate::config::*;
 use crate::custom_elements::{ColumnDropDownElement, FilterDropDownElement};
 use crate::dragdrop::*;
 use crate::model::*;
+use crate::presentation::Presentation;
 use crate::renderer::*;
 use crate::session::*;
 use crate::utils::*;
@@ -33,6 +36,7 @@ use crate::*;
 #[derive(Properties)]
 pub struct ConfigSelectorProps {
     pub session: Session,
+    pub presentation: Presentation,
     pub renderer: Renderer,
     pub dragdrop: DragDrop,
     pub onselect: Callback<()>,
@@ -55,9 +59,9 @@ pub enum ConfigSelectorMsg {
     DragEnd,
     DragOver(usize, DragTarget),
     DragLeave(DragTarget),
-    Drop(String, DragTarget, DragEffect, usize),
+    Drop(String, DragTarget, DragEffect, usize, Option<usize>),
     Close(usize, DragTarget),
-    SetFilterValue(usize, String),
+    SetFilterValue(Vec<usize>, String),
     TransposePivots,
     ViewCreated,
     New(DragTarget, InPlaceColumn),
@@ -179,11 +183,11 @@ impl Component for ConfigSelector {
         let cb = ctx.link().callback(|_| ConfigSelectorMsg::DragEnd);
         let dragend_sub = Rc::new(ctx.props().dragdrop.dragend_received.add_listener(cb));
 
-        let cb = ctx
-            .link()
-            .callback(|x: (String, DragTarget, DragEffect, usize)| {
-                ConfigSelectorMsg::Drop(x.0, x.1, x.2, x.3)
-            });
+        let cb = ctx.link().callback(
+            |x: (String, DragTarget, DragEffect, usize, Option<usize>)| {
+                ConfigSelectorMsg::Drop(x.0, x.1, x.2, x.3, x.4)
+            },
+        );
         let drop_sub = Rc::new(ctx.props().dragdrop.drop_received.add_listener(cb));
 
         let cb = ctx.link().callback(|_| ConfigSelectorMsg::ViewCreated);
@@ -265,11 +269,12 @@ impl Component for ConfigSelector {
                 false
             },
             ConfigSelectorMsg::Close(..) => false,
-            ConfigSelectorMsg::Drop(column, action, effect, index)
+            ConfigSelectorMsg::Drop(column, action, effect, index, from_index)
                 if action != DragTarget::Active =>
             {
                 let update = ctx.props().session.create_drag_drop_update(
                     column,
+                    from_index,
                     index,
                     action,
                     effect,
@@ -279,7 +284,7 @@ impl Component for ConfigSelector {
                 ctx.props().onselect.emit(());
                 false
             },
-            ConfigSelectorMsg::Drop(_, _, DragEffect::Move(action), _)
+            ConfigSelectorMsg::Drop(_, _, DragEffect::Move(action), ..)
                 if action != DragTarget::Active =>
             {
                 true
@@ -299,14 +304,21 @@ impl Component for ConfigSelector {
                 ctx.props().onselect.emit(());
                 false
             },
-            ConfigSelectorMsg::SetFilterValue(index, input) => {
+            ConfigSelectorMsg::SetFilterValue(path, input) => {
                 let mut filter = ctx.props().session.get_view_config().filter.clone();
-                let update = if matches!(filter[index].1, FilterOp::In | FilterOp::NotIn) {
-                    let current = filter[index].2.to_string();
+                let Some(filter_column) = filter
+                    .get_path_mut(&path)
+                    .and_then(FilterItem::as_filter_mut)
+                else {
+                    return false;
+                };
+
+                let update = if matches!(filter_column.1, FilterOp::In | FilterOp::NotIn) {
+                    let current = filter_column.2.to_string();
                     let mut tokens = current.split(',').collect::<Vec<_>>();
                     tokens.pop();
                     tokens.push(&input);
-                    filter[index].2 = FilterTerm::Array(
+                    filter_column.2 = FilterTerm::Array(
                         tokens
                             .iter()
                             .map(|x| Scalar::String(x.trim().to_owned()))
@@ -319,7 +331,7 @@ impl Component for ConfigSelector {
                         ..ViewConfigUpdate::default()
                     }
                 } else {
-                    filter[index].2 = FilterTerm::Scalar(Scalar::String(input));
+                    filter_column.2 = FilterTerm::Scalar(Scalar::String(input));
                     let filter = Some(filter);
                     ViewConfigUpdate {
                         filter,
@@ -356,11 +368,9 @@ impl Component for ConfigSelector {
             },
             ConfigSelectorMsg::New(DragTarget::Filter, InPlaceColumn::Column(col)) => {
                 let mut view_config = ctx.props().session.get_view_config().clone();
-                view_config.filter.push(Filter(
-                    col,
-                    FilterOp::EQ,
-                    FilterTerm::Scalar(Scalar::Null),
-                ));
+                view_config
+                    .filter
+                    .push(Filter(col, FilterOp::EQ, FilterTerm::Scalar(Scalar::Null)).into());
 
                 let update = ViewConfigUpdate {
                     filter: Some(view_config.filter),
@@ -413,11 +423,14 @@ impl Component for ConfigSelector {
             },
             ConfigSelectorMsg::New(DragTarget::Filter, InPlaceColumn::Expression(col)) => {
                 let mut view_config = ctx.props().session.get_view_config().clone();
-                view_config.filter.push(Filter(
-                    col.name.as_ref().to_owned(),
-                    FilterOp::EQ,
-                    FilterTerm::Scalar(Scalar::Null),
-                ));
+                view_config.filter.push(
+                    Filter(
+                        col.name.as_ref().to_owned(),
+                        FilterOp::EQ,
+                        FilterTerm::Scalar(Scalar::Null),
+                    )
+                    .into(),
+                );
                 view_config.expressions.insert(&col);
                 let update = ViewConfigUpdate {
                     filter: Some(view_config.filter),
@@ -457,6 +470,9 @@ impl Component for ConfigSelector {
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let config = ctx.props().session.get_view_config();
+        let column_aliases = ctx.props().presentation.get_column_aliases();
+        let linked_filter = ctx.props().session.get_linked_filter();
+        let label = |name: &String| column_aliases.get(name).unwrap_or(name).to_owned();
         let transpose = ctx.link().callback(|_| ConfigSelectorMsg::TransposePivots);
         let column_dropdown = self.column_dropdown.clone();
         let class = if ctx.props().dragdrop.get_drag_column().is_some() {
@@ -501,7 +517,8 @@ impl Component for ConfigSelector {
                                 <PivotColumn
                                     dragdrop={ &ctx.props().dragdrop }
                                     action={ DragTarget::GroupBy }
-                                    column={ group_by.clone() }>
+                                    column={ group_by.clone() }
+                                    label={ label(group_by) }>
                                 </PivotColumn>
                             }
                         }) }
@@ -527,7 +544,8 @@ impl Component for ConfigSelector {
                                 <PivotColumn
                                     dragdrop={ &ctx.props().dragdrop }
                                     action={ DragTarget::SplitBy }
-                                    column={ split_by.clone() }>
+                                    column={ split_by.clone() }
+                                    label={ label(split_by) }>
                                 </PivotColumn>
                             }
                         }) }
@@ -550,39 +568,53 @@ impl Component for ConfigSelector {
                                     session={ &ctx.props().session }
                                     renderer={ &ctx.props().renderer }
                                     dragdrop={ &ctx.props().dragdrop }
-                                    sort={ sort.clone() }>
+                                    sort={ sort.clone() }
+                                    label={ label(&sort.0) }>
                                 </SortColumn>
                             }
                         }) }
                 </SortSelector>
+                <TopNSelector
+                    session={&ctx.props().session}
+                    renderer={&ctx.props().renderer}
+                    column_aliases={&column_aliases}
+                />
                 <FilterSelector
                     name="filter"
                     allow_duplicates=true
                     parent={ctx.link().clone()}
-                    {column_dropdown}
-                    exclude={config.filter.iter().map(|x| x.0.clone()).collect::<HashSet<_>>()}
+                    column_dropdown={column_dropdown.clone()}
+                    exclude={config.filter.iter_filters().map(|x| x.0.clone()).collect::<HashSet<_>>()}
                     dragdrop={&ctx.props().dragdrop}
                     is_dragover={ctx.props().dragdrop.is_dragover(DragTarget::Filter).map(|(index, name)| {
-                        (index, Filter(name, FilterOp::EQ, FilterTerm::Scalar(Scalar::Null)))
+                        (index, Filter(name, FilterOp::EQ, FilterTerm::Scalar(Scalar::Null)).into())
                     })}
                 >
                     { for config.filter.iter().enumerate().map(|(idx, filter)| {
                             let filter_keydown = ctx.link()
-                                .callback(move |txt| ConfigSelectorMsg::SetFilterValue(idx, txt));
+                                .callback(|(path, txt)| ConfigSelectorMsg::SetFilterValue(path, txt));
 
                             html_nested! {
                                 <FilterColumn
-                                    idx={ idx }
+                                    path={ vec![idx] }
                                     filter_dropdown={ &self.filter_dropdown }
+                                    column_dropdown={ &column_dropdown }
                                     session={ &ctx.props().session }
                                     renderer={ &ctx.props().renderer }
                                     dragdrop={ &ctx.props().dragdrop }
                                     filter={ filter.clone() }
-                                    on_keydown={ filter_keydown }>
+                                    on_keydown={ filter_keydown }
+                                    column_aliases={ &column_aliases }>
                                 </FilterColumn>
                             }
                         }) }
                 </FilterSelector>
+                if !linked_filter.is_empty() {
+                    <LinkedFilterSelector
+                        filter={linked_filter}
+                        column_aliases={&column_aliases}
+                    />
+                }
             </div>
         }
     }
@@ -0,0 +1,283 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::fmt::Display;
+
+use wasm_bindgen::JsCast;
+use web_sys::*;
+use yew::prelude::*;
+
+use crate::components::containers::select::*;
+use crate::config::*;
+use crate::utils::*;
+
+/// The kinds of range a `DateRangeInput` can express.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RangeMode {
+    Custom,
+    Today,
+    This,
+    Last,
+}
+
+impl Display for RangeMode {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = match self {
+            Self::Custom => "custom",
+            Self::Today => "today",
+            Self::This => "this",
+            Self::Last => "last",
+        };
+
+        write!(fmt, "{}", mode)
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct DateRangeInputProps {
+    /// The current term of the `between` filter.
+    pub term: FilterTerm,
+
+    /// The column type, either `Type::Date` or `Type::Datetime`.
+    pub ty: Type,
+    pub on_change: Callback<FilterTerm>,
+}
+
+impl DateRangeInputProps {
+    fn mode(&self) -> RangeMode {
+        match &self.term {
+            FilterTerm::Relative(RelativeDate::Today) => RangeMode::Today,
+            FilterTerm::Relative(RelativeDate::This { .. }) => RangeMode::This,
+            FilterTerm::Relative(RelativeDate::Last { .. }) => RangeMode::Last,
+            _ => RangeMode::Custom,
+        }
+    }
+
+    fn unit(&self) -> DateUnit {
+        match &self.term {
+            FilterTerm::Relative(RelativeDate::This { unit } | RelativeDate::Last { unit, .. }) => {
+                *unit
+            },
+            _ => DateUnit::Day,
+        }
+    }
+
+    fn count(&self) -> u32 {
+        match &self.term {
+            FilterTerm::Relative(RelativeDate::Last { count, .. }) => *count,
+            _ => 7,
+        }
+    }
+
+    /// The concrete `(start, end)` of this range, resolving relative terms
+    /// against the clock so switching to `Custom` starts from the same range.
+    fn bounds(&self) -> (Scalar, Scalar) {
+        match &self.term {
+            FilterTerm::Array(xs) if xs.len() == 2 => (xs[0].clone(), xs[1].clone()),
+            FilterTerm::Relative(relative) => get_local_now()
+                .ok()
+                .and_then(|now| relative.resolve(now, self.ty == Type::Date))
+                .map(|(start, end)| (Scalar::DateTime(start), Scalar::DateTime(end)))
+                .unwrap_or((Scalar::Null, Scalar::Null)),
+            _ => (Scalar::Null, Scalar::Null),
+        }
+    }
+
+    fn format(&self, x: &Scalar) -> String {
+        match x {
+            Scalar::DateTime(x) | Scalar::Float(x) if self.ty == Type::Date => {
+                posix_to_utc_date_str(*x).unwrap_or_default()
+            },
+            Scalar::Date(x) => posix_to_utc_date_str(*x as f64).unwrap_or_default(),
+            Scalar::DateTime(x) | Scalar::Float(x) => posix_to_utc_str(*x).unwrap_or_default(),
+            _ => "".to_owned(),
+        }
+    }
+
+    fn parse(&self, val: &str) -> Scalar {
+        if self.ty == Type::Date {
+            str_to_utc_date_posix(val)
+                .map(|x| Scalar::Date(x as i64))
+                .unwrap_or(Scalar::Null)
+        } else {
+            str_to_utc_posix(val)
+                .map(Scalar::DateTime)
+                .unwrap_or(Scalar::Null)
+        }
+    }
+
+    fn emit_mode(&self, mode: RangeMode) {
+        let unit = self.unit();
+        self.on_change.emit(match mode {
+            RangeMode::Custom => {
+                let (start, end) = self.bounds();
+                FilterTerm::Array(vec![start, end])
+            },
+            RangeMode::Today => FilterTerm::Relative(RelativeDate::Today),
+            RangeMode::This => FilterTerm::Relative(RelativeDate::This { unit }),
+            RangeMode::Last => FilterTerm::Relative(RelativeDate::Last {
+                count: self.count(),
+                unit,
+            }),
+        })
+    }
+
+    fn emit_unit(&self, unit: DateUnit) {
+        match self.mode() {
+            RangeMode::This => self
+                .on_change
+                .emit(FilterTerm::Relative(RelativeDate::This { unit })),
+            RangeMode::Last => self
+                .on_change
+                .emit(FilterTerm::Relative(RelativeDate::Last {
+                    count: self.count(),
+                    unit,
+                })),
+            _ => (),
+        }
+    }
+
+    fn emit_count(&self, val: &str) {
+        if let Ok(count) = val.parse::<u32>() {
+            self.on_change
+                .emit(FilterTerm::Relative(RelativeDate::Last {
+                    count,
+                    unit: self.unit(),
+                }))
+        }
+    }
+
+    fn emit_bound(&self, is_end: bool, val: &str) {
+        let (mut start, mut end) = self.bounds();
+        if is_end {
+            end = self.parse(val);
+        } else {
+            start = self.parse(val);
+        }
+
+        self.on_change.emit(FilterTerm::Array(vec![start, end]))
+    }
+}
+
+fn input_value(event: InputEvent) -> String {
+    event
+        .target()
+        .unwrap()
+        .unchecked_into::<HtmlInputElement>()
+        .value()
+}
+
+/// A range-picker for `between` filters on `Type::Date` and `Type::Datetime`
+/// columns, which builds either a fixed `[start, end]` range or a
+/// `RelativeDate` such as "last 7 days".
+#[function_component]
+pub fn DateRangeInput(props: &DateRangeInputProps) -> Html {
+    let mode = props.mode();
+    let on_mode = Callback::from({
+        let props = props.clone();
+        move |mode| props.emit_mode(mode)
+    });
+
+    let modes = [
+        RangeMode::Custom,
+        RangeMode::Today,
+        RangeMode::This,
+        RangeMode::Last,
+    ]
+    .into_iter()
+    .map(SelectItem::Option)
+    .collect::<Vec<_>>();
+
+    let units = [
+        DateUnit::Hour,
+        DateUnit::Day,
+        DateUnit::Week,
+        DateUnit::Month,
+        DateUnit::Year,
+    ]
+    .into_iter()
+    .map(SelectItem::Option)
+    .collect::<Vec<_>>();
+
+    let on_unit = Callback::from({
+        let props = props.clone();
+        move |unit| props.emit_unit(unit)
+    });
+
+    let on_count = Callback::from({
+        let props = props.clone();
+        move |event| props.emit_count(&input_value(event))
+    });
+
+    let on_start = Callback::from({
+        let props = props.clone();
+        move |event| props.emit_bound(false, &input_value(event))
+    });
+
+    let on_end = Callback::from({
+        let props = props.clone();
+        move |event| props.emit_bound(true, &input_value(event))
+    });
+
+    let (input_type, input_class) = if props.ty == Type::Date {
+        ("date", "date-filter")
+    } else {
+        ("datetime-local", "datetime-filter")
+    };
+
+    let (start, end) = props.bounds();
+    html! {
+        <div class="date-range-filter">
+            <Select<RangeMode>
+                class="filterop-selector"
+                values={modes}
+                selected={mode}
+                on_select={on_mode}
+            />
+            if mode == RangeMode::Last {
+                <input
+                    type="number"
+                    class="num-filter"
+                    min="1"
+                    step="1"
+                    value={props.count().to_string()}
+                    oninput={on_count}
+                />
+            }
+            if matches!(mode, RangeMode::This | RangeMode::Last) {
+                <Select<DateUnit>
+                    class="filterop-selector"
+                    values={units}
+                    selected={props.unit()}
+                    on_select={on_unit}
+                />
+            }
+            if mode == RangeMode::Custom {
+                <input
+                    type={input_type}
+                    class={input_class}
+                    step="0.001"
+                    value={props.format(&start)}
+                    oninput={on_start}
+                />
+                <span class="date-range-separator">{ "–" }</span>
+                <input
+                    type={input_type}
+                    class={input_class}
+                    step="0.001"
+                    value={props.format(&end)}
+                    oninput={on_end}
+                />
+            }
+        </div>
+    }
+}
@@ -10,13 +10,15 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
-use chrono::{NaiveDate, TimeZone, Utc};
 use wasm_bindgen::JsCast;
 use web_sys::*;
 use yew::prelude::*;
 
+use super::date_range_input::DateRangeInput;
+use super::{EmptyColumn, InPlaceColumn};
 use crate::components::containers::dragdrop_list::*;
 use crate::components::containers::select::*;
 use crate::components::style::LocalStyle;
@@ -24,16 +26,21 @@ use crate::components::type_icon::TypeIcon;
 use crate::config::*;
 use crate::custom_elements::*;
 use crate::dragdrop::*;
+use crate::js::PerspectiveValidationError;
 use crate::model::*;
 use crate::renderer::*;
 use crate::session::*;
-use crate::utils::{posix_to_utc_str, str_to_utc_posix, ApiFuture};
+use crate::utils::{
+    posix_to_utc_date_str, posix_to_utc_str, str_to_utc_date_posix, str_to_utc_posix, ApiFuture,
+};
 use crate::*;
 
-/// A control for a single filter condition.
+/// A control for a single filter condition, or a nested group of filter
+/// conditions (which renders its members as `FilterColumn`s recursively).
 pub struct FilterColumn {
     input: String,
     input_ref: NodeRef,
+    error: Option<PerspectiveValidationError>,
 }
 
 #[derive(Debug)]
@@ -42,14 +49,25 @@ pub enum FilterColumnMsg {
     Close,
     FilterOpSelect(FilterOp),
     FilterKeyDown(u32),
+    FilterTermChange(FilterTerm),
+    GroupOpSelect(FilterReducer),
+    Nest,
+    Remove(usize),
+    New(InPlaceColumn),
 }
 
 #[derive(Properties, Clone)]
 pub struct FilterColumnProps {
-    pub filter: Filter,
-    pub idx: usize,
+    pub filter: FilterItem,
+
+    /// The location of `filter` in the `ViewConfig::filter` tree.
+    pub path: Vec<usize>,
     pub filter_dropdown: FilterDropDownElement,
-    pub on_keydown: Callback<String>,
+    pub column_dropdown: ColumnDropDownElement,
+    pub on_keydown: Callback<(Vec<usize>, String)>,
+
+    /// Display names for columns, by column name.
+    pub column_aliases: Rc<HashMap<String, String>>,
     pub session: Session,
     pub renderer: Renderer,
     pub dragdrop: DragDrop,
@@ -57,26 +75,34 @@ pub struct FilterColumnProps {
 
 impl PartialEq for FilterColumnProps {
     fn eq(&self, rhs: &Self) -> bool {
-        self.idx == rhs.idx && self.filter == rhs.filter && self.on_keydown == rhs.on_keydown
+        self.path == rhs.path
+            && self.filter == rhs.filter
+            && self.on_keydown == rhs.on_keydown
+            && self.column_aliases == rhs.column_aliases
     }
 }
 
 derive_model!(Renderer, Session for FilterColumnProps);
 
 impl DragDropListItemProps for FilterColumnProps {
-    type Item = Filter;
+    type Item = FilterItem;
 
-    fn get_item(&self) -> Filter {
+    fn get_item(&self) -> FilterItem {
         self.filter.clone()
     }
 }
 
 impl FilterColumnProps {
+    /// Is this a top-level item, e.g. not nested in a `FilterGroup`?
+    fn is_top_level(&self) -> bool {
+        self.path.len() == 1
+    }
+
     /// Does this filter item get a "suggestions" auto-complete modal?
     fn is_suggestable(&self) -> bool {
-        (self.filter.1 == FilterOp::EQ
-            || self.filter.1 == FilterOp::NE
-            || self.filter.1 == FilterOp::In)
+        self.filter
+            .as_filter()
+            .is_some_and(|filter| matches!(filter.1, FilterOp::EQ | FilterOp::NE | FilterOp::In))
             && self.get_filter_type() == Some(Type::String)
     }
 
@@ -84,26 +110,20 @@ impl FilterColumnProps {
     fn get_filter_type(&self) -> Option<Type> {
         self.session
             .metadata()
-            .get_column_table_type(&self.filter.0)
+            .get_column_table_type(&self.filter.as_filter()?.0)
     }
 
     // Get the string value, suitable for the `value` field of a `FilterColumns`'s
     // `<input>`.
     fn get_filter_input(&self) -> Option<String> {
         let filter_type = self.get_filter_type()?;
-        match (&filter_type, &self.filter.2) {
+        match (&filter_type, &self.filter.as_filter()?.2) {
             (Type::Date, FilterTerm::Scalar(Scalar::Float(x)))
             | (Type::Date, FilterTerm::Scalar(Scalar::DateTime(x))) => {
-                if *x > 0_f64 {
-                    Some(
-                        Utc.timestamp_opt(*x as i64 / 1000, (*x as u32 % 1000) * 1000)
-                            .earliest()?
-                            .format("%Y-%m-%d")
-                            .to_string(),
-                    )
-                } else {
-                    None
-                }
+                posix_to_utc_date_str(*x).ok()
+            },
+            (Type::Date, FilterTerm::Scalar(Scalar::Date(x))) => {
+                posix_to_utc_date_str(*x as f64).ok()
             },
             (Type::Datetime, FilterTerm::Scalar(Scalar::Float(x) | Scalar::DateTime(x))) => {
                 posix_to_utc_str(*x).ok()
@@ -129,6 +149,11 @@ impl FilterColumnProps {
                 FilterOp::BeginsWith,
                 FilterOp::Contains,
                 FilterOp::EndsWith,
+                FilterOp::EQIgnoreCase,
+                FilterOp::ContainsIgnoreCase,
+                FilterOp::BeginsWithIgnoreCase,
+                FilterOp::EndsWithIgnoreCase,
+                FilterOp::Matches,
                 FilterOp::In,
                 FilterOp::NotIn,
                 FilterOp::IsNotNull,
@@ -137,6 +162,17 @@ impl FilterColumnProps {
             Some(Type::Bool) => {
                 vec![FilterOp::EQ, FilterOp::IsNull, FilterOp::IsNotNull]
             },
+            Some(Type::Date | Type::Datetime) => vec![
+                FilterOp::EQ,
+                FilterOp::NE,
+                FilterOp::GT,
+                FilterOp::GTE,
+                FilterOp::LT,
+                FilterOp::LTE,
+                FilterOp::Between,
+                FilterOp::IsNotNull,
+                FilterOp::IsNull,
+            ],
             Some(_) => vec![
                 FilterOp::EQ,
                 FilterOp::NE,
@@ -157,8 +193,24 @@ impl FilterColumnProps {
     /// - `op` The new `FilterOp`.
     fn update_filter_op(&self, op: FilterOp) {
         let mut filter = self.session.get_view_config().filter.clone();
-        let filter_column = &mut filter.get_mut(self.idx).expect("Filter on no column");
+        let filter_column = filter
+            .get_path_mut(&self.path)
+            .and_then(FilterItem::as_filter_mut)
+            .expect("Filter on no column");
+
+        // `between` has its own term shape, so reset the term when switching
+        // to or from it.
+        if op == FilterOp::Between && filter_column.1 != FilterOp::Between {
+            filter_column.2 = FilterTerm::Relative(RelativeDate::Today);
+        } else if op != FilterOp::Between && filter_column.1 == FilterOp::Between {
+            filter_column.2 = FilterTerm::Scalar(Scalar::Null);
+        }
+
         filter_column.1 = op;
+        if filter_column.validate_regex().is_err() {
+            filter_column.2 = FilterTerm::Scalar(Scalar::Null);
+        }
+
         let update = ViewConfigUpdate {
             filter: Some(filter),
             ..ViewConfigUpdate::default()
@@ -167,13 +219,33 @@ impl FilterColumnProps {
         ApiFuture::spawn(self.update_and_render(update));
     }
 
+    /// Check a new string input read from the DOM before it is applied, e.g.
+    /// the pattern of a `matches` filter.
+    ///
+    /// # Arguments
+    /// - `val` The new filter value.
+    fn validate_filter_input(&self, val: &str) -> Result<(), PerspectiveValidationError> {
+        match self.filter.as_filter() {
+            Some(Filter(column, op, _)) => Filter(
+                column.to_owned(),
+                *op,
+                FilterTerm::Scalar(Scalar::String(val.to_owned())),
+            )
+            .validate_regex(),
+            None => Ok(()),
+        }
+    }
+
     /// Update the filter value from the string input read from the DOM.
     ///
     /// # Arguments
     /// - `val` The new filter value.
     fn update_filter_input(&self, val: String) {
         let mut filter = self.session.get_view_config().filter.clone();
-        let filter_column = &mut filter.get_mut(self.idx).expect("Filter on no column");
+        let filter_column = filter
+            .get_path_mut(&self.path)
+            .and_then(FilterItem::as_filter_mut)
+            .expect("Filter on no column");
         let filter_input = match filter_column.1 {
             FilterOp::NotIn | FilterOp::In => Some(FilterTerm::Array(
                 val.split(',')
@@ -185,25 +257,19 @@ impl FilterColumnProps {
                 Some(Type::Integer) => {
                     if val.is_empty() {
                         None
-                    } else if let Ok(num) = val.parse::<f64>() {
-                        Some(FilterTerm::Scalar(Scalar::Float(num.floor())))
+                    } else if let Ok(num) = val.parse::<i64>() {
+                        Some(FilterTerm::Scalar(Scalar::Int(num)))
                     } else {
-                        None
+                        parse_finite(&val).map(|num| {
+                            FilterTerm::Scalar(Scalar::Float(num.floor()).coerce(Type::Integer))
+                        })
                     }
                 },
                 Some(Type::Float) => {
-                    if val.is_empty() {
-                        None
-                    } else if let Ok(num) = val.parse::<f64>() {
-                        Some(FilterTerm::Scalar(Scalar::Float(num)))
-                    } else {
-                        None
-                    }
+                    parse_finite(&val).map(|num| FilterTerm::Scalar(Scalar::Float(num)))
                 },
-                Some(Type::Date) => match NaiveDate::parse_from_str(&val, "%Y-%m-%d") {
-                    Ok(ref posix) => posix
-                        .and_hms_opt(0, 0, 0)
-                        .map(|x| FilterTerm::Scalar(Scalar::DateTime(x.timestamp_millis() as f64))),
+                Some(Type::Date) => match str_to_utc_date_posix(&val) {
+                    Ok(x) => Some(FilterTerm::Scalar(Scalar::Date(x as i64))),
                     _ => None,
                 },
                 Some(Type::Datetime) => match str_to_utc_posix(&val) {
@@ -232,9 +298,121 @@ impl FilterColumnProps {
             }
         }
     }
+
+    /// Update the filter term, e.g. from a `DateRangeInput`.
+    ///
+    /// # Arguments
+    /// - `term` The new `FilterTerm`.
+    fn update_filter_term(&self, term: FilterTerm) {
+        let mut filter = self.session.get_view_config().filter.clone();
+        let filter_column = filter
+            .get_path_mut(&self.path)
+            .and_then(FilterItem::as_filter_mut)
+            .expect("Filter on no column");
+
+        if filter_column.2 != term {
+            filter_column.2 = term;
+            let update = ViewConfigUpdate {
+                filter: Some(filter),
+                ..ViewConfigUpdate::default()
+            };
+
+            ApiFuture::spawn(self.update_and_render(update));
+        }
+    }
+
+    /// Update the combinator of this `FilterGroup`.
+    ///
+    /// # Arguments
+    /// - `op` The new `FilterReducer`.
+    fn update_group_op(&self, op: FilterReducer) {
+        let mut filter = self.session.get_view_config().filter.clone();
+        if let Some(FilterItem::Group(group)) = filter.get_path_mut(&self.path) {
+            group.op = op;
+            let update = ViewConfigUpdate {
+                filter: Some(filter),
+                ..ViewConfigUpdate::default()
+            };
+
+            ApiFuture::spawn(self.update_and_render(update));
+        }
+    }
+
+    /// Wrap this filter in a new `FilterGroup`, using the opposite combinator
+    /// of the group it is currently in.
+    fn nest_filter(&self) {
+        let mut filter = self.session.get_view_config().filter.clone();
+        let op = filter.get_parent_op(&self.path).flip();
+        if let Some(item) = filter.get_path_mut(&self.path) {
+            *item = FilterItem::Group(FilterGroup {
+                op,
+                filters: vec![item.clone()],
+            });
+
+            let update = ViewConfigUpdate {
+                filter: Some(filter),
+                ..ViewConfigUpdate::default()
+            };
+
+            ApiFuture::spawn(self.update_and_render(update));
+        }
+    }
+
+    /// Remove a member of this `FilterGroup`.
+    ///
+    /// # Arguments
+    /// - `idx` The index of the member to remove.
+    fn remove_group_filter(&self, idx: usize) {
+        let mut filter = self.session.get_view_config().filter.clone();
+        let mut path = self.path.clone();
+        path.push(idx);
+        if filter.remove_path(&path).is_some() {
+            let update = ViewConfigUpdate {
+                filter: Some(filter),
+                ..ViewConfigUpdate::default()
+            };
+
+            ApiFuture::spawn(self.update_and_render(update));
+        }
+    }
+
+    /// Append a new, empty condition on `col` to this `FilterGroup`.
+    ///
+    /// # Arguments
+    /// - `col` The column (or new expression) to filter on.
+    fn add_group_filter(&self, col: InPlaceColumn) {
+        let mut view_config = self.session.get_view_config().clone();
+        let name = match &col {
+            InPlaceColumn::Column(name) => name.to_owned(),
+            InPlaceColumn::Expression(expr) => expr.name.as_ref().to_owned(),
+        };
+
+        if let Some(FilterItem::Group(group)) = view_config.filter.get_path_mut(&self.path) {
+            group
+                .filters
+                .push(Filter(name, FilterOp::EQ, FilterTerm::Scalar(Scalar::Null)).into());
+
+            let expressions = match &col {
+                InPlaceColumn::Column(_) => None,
+                InPlaceColumn::Expression(expr) => {
+                    view_config.expressions.insert(expr);
+                    Some(view_config.expressions)
+                },
+            };
+
+            let update = ViewConfigUpdate {
+                filter: Some(view_config.filter),
+                expressions,
+                ..ViewConfigUpdate::default()
+            };
+
+            ApiFuture::spawn(self.update_and_render(update));
+        }
+    }
 }
 
 type FilterOpSelector = Select<FilterOp>;
+type FilterReducerSelector = Select<FilterReducer>;
 
 impl Component for FilterColumn {
     type Message = FilterColumnMsg;
@@ -251,7 +429,11 @@ impl Component for FilterColumn {
             ctx.props().update_filter_input(input.clone());
         }
 
-        Self { input, input_ref }
+        Self {
+            input,
+            input_ref,
+            error: None,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: FilterColumnMsg) -> bool {
@@ -269,21 +451,30 @@ impl Component for FilterColumn {
                 };
 
                 if ctx.props().is_suggestable() {
+                    let path = ctx.props().path.clone();
                     ctx.props().filter_dropdown.autocomplete(
                         column,
-                        if ctx.props().filter.1 == FilterOp::In {
+                        if ctx.props().filter.as_filter().map(|x| x.1) == Some(FilterOp::In) {
                             input.split(',').last().unwrap().to_owned()
                         } else {
                             input.clone()
                         },
                         HashSet::new(),
                         target.unchecked_into(),
-                        ctx.props().on_keydown.clone(),
+                        ctx.props()
+                            .on_keydown
+                            .reform(move |txt| (path.clone(), txt)),
                     );
                 }
 
-                ctx.props().update_filter_input(input);
-                false
+                let error = ctx.props().validate_filter_input(&input).err();
+                let should_render = error != self.error;
+                if error.is_none() {
+                    ctx.props().update_filter_input(input);
+                }
+
+                self.error = error;
+                should_render
             },
             FilterColumnMsg::FilterKeyDown(40) => {
                 if ctx.props().is_suggestable() {
@@ -317,9 +508,31 @@ impl Component for FilterColumn {
                 false
             },
             FilterColumnMsg::FilterOpSelect(op) => {
+                self.error = None;
                 ctx.props().update_filter_op(op);
                 true
             },
+            FilterColumnMsg::FilterTermChange(term) => {
+                ctx.props().update_filter_term(term);
+                false
+            },
+            FilterColumnMsg::GroupOpSelect(op) => {
+                ctx.props().update_group_op(op);
+                true
+            },
+            FilterColumnMsg::Nest => {
+                ctx.props().nest_filter();
+                false
+            },
+            FilterColumnMsg::Remove(idx) => {
+                ctx.props().filter_dropdown.hide().unwrap();
+                ctx.props().remove_group_filter(idx);
+                false
+            },
+            FilterColumnMsg::New(col) => {
+                ctx.props().add_group_filter(col);
+                false
+            },
         }
     }
 
@@ -333,8 +546,12 @@ impl Component for FilterColumn {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let idx = ctx.props().idx;
-        let filter = ctx.props().filter.clone();
+        let filter = match &ctx.props().filter {
+            FilterItem::Filter(filter) => filter.clone(),
+            FilterItem::Group(group) => return self.view_group(ctx, group),
+        };
+
+        let idx = ctx.props().path[0];
         let column = filter.0.to_owned();
         let col_type = ctx
             .props()
@@ -370,7 +587,7 @@ impl Component for FilterColumn {
             .callback(move |event: KeyboardEvent| FilterColumnMsg::FilterKeyDown(event.key_code()));
 
         let dragstart = Callback::from({
-            let event_name = ctx.props().filter.0.to_owned();
+            let event_name = filter.0.to_owned();
             let dragdrop = ctx.props().dragdrop.clone();
             move |event: DragEvent| {
                 dragdrop.set_drag_image(&event).unwrap();
@@ -419,7 +636,7 @@ impl Component for FilterColumn {
                     type="text"
                     size="4"
                     placeholder="Value"
-                    class="string-filter"
+                    class={classes!("string-filter", self.error.is_some().then_some("invalid"))}
                     spellcheck="false"
                     // TODO This is dirty and it may not work in the future.
                      onInput="this.parentNode.dataset.value=this.value"
@@ -477,10 +694,18 @@ impl Component for FilterColumn {
             .map(SelectItem::Option)
             .collect::<Vec<_>>();
 
+        let label = ctx
+            .props()
+            .column_aliases
+            .get(&filter.0)
+            .unwrap_or(&filter.0)
+            .to_owned();
+
+        let nest = ctx.link().callback(|_| FilterColumnMsg::Nest);
         html! {
             <div
                 class="pivot-column-draggable"
-                draggable="true"
+                draggable={ctx.props().is_top_level().to_string()}
                 ondragstart={dragstart}
                 ondragend={dragend}
             >
@@ -491,14 +716,20 @@ impl Component for FilterColumn {
                     class="pivot-column-border"
                 >
                     <TypeIcon ty={Type::String} />
-                    <span class="column_name">{ filter.0.to_owned() }</span>
+                    <span class="column_name">{ label }</span>
                     <FilterOpSelector
                         class="filterop-selector"
                         values={filter_ops}
                         selected={filter.1}
                         on_select={select}
                     />
-                    if !matches!(&filter.1, FilterOp::IsNotNull | FilterOp::IsNull) {
+                    if let (FilterOp::Between, Some(ty @ (Type::Date | Type::Datetime))) = (&filter.1, col_type) {
+                        <DateRangeInput
+                            term={filter.2.clone()}
+                            {ty}
+                            on_change={ctx.link().callback(FilterColumnMsg::FilterTermChange)}
+                        />
+                    } else if !matches!(&filter.1, FilterOp::IsNotNull | FilterOp::IsNull) {
                         if col_type == Some(Type::Bool) {
                             { input_elem }
                         } else {
@@ -510,8 +741,80 @@ impl Component for FilterColumn {
                             </label>
                         }
                     }
+                    if let Some(error) = &self.error {
+                        <span class="filter-error" title={error.error_message.clone()}>
+                            { &error.error_message }
+                        </span>
+                    }
+                    <span
+                        class="filter-nest-button"
+                        title="Nest in group"
+                        onmousedown={nest}
+                    />
                 </div>
             </div>
         }
     }
 }
+
+impl FilterColumn {
+    /// Render a `FilterGroup`, its members and an input to add a new member.
+    fn view_group(&self, ctx: &Context<Self>, group: &FilterGroup) -> Html {
+        let select = ctx.link().callback(FilterColumnMsg::GroupOpSelect);
+        let on_select = ctx.link().callback(FilterColumnMsg::New);
+        let reducers = vec![
+            SelectItem::Option(FilterReducer::And),
+            SelectItem::Option(FilterReducer::Or),
+        ];
+
+        let exclude = group
+            .filters
+            .iter_filters()
+            .map(|x| x.0.clone())
+            .collect::<HashSet<_>>();
+
+        html! {
+            <div class="filter-group">
+                <LocalStyle
+                    href={css!("filter-item")}
+                />
+                <div class="filter-group-header">
+                    <FilterReducerSelector
+                        class="filterop-selector"
+                        values={reducers}
+                        selected={group.op}
+                        on_select={select}
+                    />
+                </div>
+                <ul class="filter-group-items">
+                    { for group.filters.iter().enumerate().map(|(idx, filter)| {
+                            let mut path = ctx.props().path.clone();
+                            path.push(idx);
+                            let close = ctx.link().callback(move |_| FilterColumnMsg::Remove(idx));
+                            html! {
+                                <li class="pivot-column">
+                                    <FilterColumn
+                                        { path }
+                                        filter={ filter.clone() }
+                                        filter_dropdown={ &ctx.props().filter_dropdown }
+                                        column_dropdown={ &ctx.props().column_dropdown }
+                                        session={ &ctx.props().session }
+                                        renderer={ &ctx.props().renderer }
+                                        dragdrop={ &ctx.props().dragdrop }
+                                        on_keydown={ &ctx.props().on_keydown }
+                                        column_aliases={ &ctx.props().column_aliases }>
+                                    </FilterColumn>
+                                    <span class="row_close" onmousedown={close} />
+                                </li>
+                            }
+                        }) }
+                    <EmptyColumn
+                        column_dropdown={ctx.props().column_dropdown.clone()}
+                        {exclude}
+                        {on_select}
+                    />
+                </ul>
+            </div>
+        }
+    }
+}
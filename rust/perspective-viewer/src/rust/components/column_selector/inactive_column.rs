@@ -105,6 +105,7 @@ pub enum InactiveColumnMsg {
     ActivateColumn(bool),
     MouseEnter(bool),
     MouseLeave(bool),
+    SetStats(ColumnStats),
 }
 
 use InactiveColumnMsg::*;
@@ -113,6 +114,9 @@ use InactiveColumnMsg::*;
 pub struct InactiveColumn {
     add_expression_ref: NodeRef,
     mouseover: bool,
+
+    /// The `ColumnStats::summary()` tooltip, fetched on first hover.
+    stats: Option<String>,
 }
 
 impl Component for InactiveColumn {
@@ -132,6 +136,16 @@ impl Component for InactiveColumn {
                 false
             },
             MouseEnter(is_render) => {
+                if is_render && !self.mouseover {
+                    let session = ctx.props().session.clone();
+                    let name = ctx.props().name.clone();
+                    let on_stats = ctx.link().callback(SetStats);
+                    ApiFuture::spawn(async move {
+                        on_stats.emit(session.get_column_stats(name).await?);
+                        Ok(())
+                    });
+                }
+
                 self.mouseover = is_render;
                 is_render
             },
@@ -139,6 +153,10 @@ impl Component for InactiveColumn {
                 self.mouseover = false;
                 is_render
             },
+            SetStats(stats) => {
+                self.stats = Some(stats.summary());
+                true
+            },
         }
     }
 
@@ -176,6 +194,7 @@ impl Component for InactiveColumn {
             .metadata()
             .is_column_expression(&ctx.props().name);
 
+        let label = ctx.props().presentation.get_column_label(&ctx.props().name);
         let is_active_class = ctx.props().renderer.metadata().mode.css();
         let mut class = classes!("column-selector-column");
         if !ctx.props().visible {
@@ -191,6 +210,7 @@ impl Component for InactiveColumn {
                 {class}
                 {onmouseover}
                 {onmouseout}
+                title={self.stats.clone()}
                 data-index={ctx.props().idx.to_string()}
             >
                 <span
@@ -208,7 +228,7 @@ impl Component for InactiveColumn {
                         class="column-selector-column-border"
                     >
                         <TypeIcon ty={col_type} />
-                        <span class="column_name">{ ctx.props().name.clone() }</span>
+                        <span class="column_name">{ label }</span>
                         <span
                             class="column-selector--spacer"
                         />
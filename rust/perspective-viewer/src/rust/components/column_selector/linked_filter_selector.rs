@@ -0,0 +1,54 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::config::*;
+
+#[derive(PartialEq, Properties)]
+pub struct LinkedFilterSelectorProps {
+    /// The filter implied by a selection in a linked viewer, see
+    /// `Session::get_linked_filter()`.
+    pub filter: Vec<Filter>,
+
+    /// Display names for columns, see `Presentation::get_column_aliases()`.
+    pub column_aliases: Rc<HashMap<String, String>>,
+}
+
+/// The filters applied by a `LinkGroup`, shown after the `filter` in the
+/// `ConfigSelector`.  These are not part of the `ViewConfig` and are read-only,
+/// as they are cleared by deselecting in the linked viewer.
+#[function_component]
+pub fn LinkedFilterSelector(props: &LinkedFilterSelectorProps) -> Html {
+    let label = |name: &String| props.column_aliases.get(name).unwrap_or(name).to_owned();
+    html! {
+        <div class="rrow">
+            <div id="linked_filter">
+                <div class="psp-text-field">
+                    <ul class="psp-text-field__input">
+                        { for props.filter.iter().map(|Filter(column, op, term)| html! {
+                            <li class="pivot-column linked-filter" title="Linked filter">
+                                <span class="column_name">{ label(column) }</span>
+                                <span class="filter-op">{ op.to_string() }</span>
+                                <span class="filter-term">{ term.to_string() }</span>
+                            </li>
+                        }) }
+                    </ul>
+                    <label class="pivot-selector-label" for="linked_filter" />
+                </div>
+            </div>
+        </div>
+    }
+}
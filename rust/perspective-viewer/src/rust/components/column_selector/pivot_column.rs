@@ -23,13 +23,16 @@ pub struct PivotColumn {}
 #[derive(Properties)]
 pub struct PivotColumnProps {
     pub column: String,
+
+    /// The display name of `column`.
+    pub label: String,
     pub dragdrop: DragDrop,
     pub action: DragTarget,
 }
 
 impl PartialEq for PivotColumnProps {
     fn eq(&self, other: &Self) -> bool {
-        self.column == other.column && self.action == other.action
+        self.column == other.column && self.label == other.label && self.action == other.action
     }
 }
 
@@ -72,7 +75,7 @@ impl Component for PivotColumn {
                     class="pivot-column-border"
                 >
                     <TypeIcon ty={Type::String} />
-                    <span class="column_name">{ ctx.props().column.clone() }</span>
+                    <span class="column_name">{ ctx.props().label.clone() }</span>
                 </div>
             </div>
         }
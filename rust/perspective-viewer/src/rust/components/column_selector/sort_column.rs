@@ -31,6 +31,9 @@ pub struct SortColumn {}
 pub struct SortColumnProps {
     pub sort: Sort,
     pub idx: usize,
+
+    /// The display name of the sorted column.
+    pub label: String,
     pub session: Session,
     pub renderer: Renderer,
     pub dragdrop: DragDrop,
@@ -38,7 +41,7 @@ pub struct SortColumnProps {
 
 impl PartialEq for SortColumnProps {
     fn eq(&self, other: &Self) -> bool {
-        self.sort == other.sort && self.idx == other.idx
+        self.sort == other.sort && self.idx == other.idx && self.label == other.label
     }
 }
 
@@ -112,7 +115,7 @@ impl Component for SortColumn {
                     class="pivot-column-border"
                 >
                     <TypeIcon ty={Type::String} />
-                    <span class="column_name">{ ctx.props().sort.0.to_owned() }</span>
+                    <span class="column_name">{ ctx.props().label.clone() }</span>
                     <span
                         class={format!("sort-icon {}", ctx.props().sort.1)}
                         onmousedown={onclick}
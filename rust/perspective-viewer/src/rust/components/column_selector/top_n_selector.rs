@@ -0,0 +1,227 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::num::NonZeroUsize;
+use std::rc::Rc;
+
+use web_sys::*;
+use yew::prelude::*;
+
+use crate::components::containers::select::*;
+use crate::config::*;
+use crate::model::*;
+use crate::renderer::*;
+use crate::session::*;
+use crate::utils::ApiFuture;
+use crate::*;
+
+/// A `Select` option for `T`, displayed as its column label.
+#[derive(Clone, PartialEq)]
+struct Labeled<T>(T, String);
+
+impl<T> Display for Labeled<T> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(fmt, "{}", self.1)
+    }
+}
+
+#[derive(Properties)]
+pub struct TopNSelectorProps {
+    /// Display names for columns, see `Presentation::get_column_aliases()`.
+    pub column_aliases: Rc<HashMap<String, String>>,
+    pub session: Session,
+    pub renderer: Renderer,
+}
+
+impl PartialEq for TopNSelectorProps {
+    fn eq(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+derive_model!(Renderer, Session for TopNSelectorProps);
+
+impl TopNSelectorProps {
+    fn label(&self, name: &str) -> String {
+        self.column_aliases
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.to_owned())
+    }
+
+    /// A new `TopN` of `n` rows, ranked by the first sort column (in its
+    /// direction, if supported) or otherwise the first active column.
+    fn default_top_n(&self, n: NonZeroUsize) -> Option<TopN> {
+        let config = self.session.get_view_config();
+        let (column, dir) = match config.sort.first() {
+            Some(Sort(column, dir @ (SortDir::Asc | SortDir::Desc))) => (column.clone(), *dir),
+            Some(Sort(column, _)) => (column.clone(), SortDir::Desc),
+            None => (
+                config.columns.iter().flatten().next()?.clone(),
+                SortDir::Desc,
+            ),
+        };
+
+        Some(TopN {
+            column,
+            dir,
+            n,
+            level: 0,
+        })
+    }
+}
+
+pub enum TopNSelectorMsg {
+    SetN(String),
+    SetColumn(String),
+    SetLevel(usize),
+    DirClick,
+}
+
+/// The `top_n` of the `ViewConfig`, shown next to the `sort` in the
+/// `ConfigSelector`.  Clearing the count removes the `top_n`.
+pub struct TopNSelector {}
+
+impl Component for TopNSelector {
+    type Message = TopNSelectorMsg;
+    type Properties = TopNSelectorProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {}
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: TopNSelectorMsg) -> bool {
+        let current = ctx.props().session.get_view_config().top_n.clone();
+        let top_n = match (msg, current.clone()) {
+            (TopNSelectorMsg::SetN(n), top_n) => match n.trim().parse::<NonZeroUsize>() {
+                Ok(n) if top_n.is_some() => top_n.map(|top_n| TopN { n, ..top_n }),
+                Ok(n) => ctx.props().default_top_n(n),
+                Err(_) => None,
+            },
+            (TopNSelectorMsg::SetColumn(column), Some(top_n)) => Some(TopN { column, ..top_n }),
+            (TopNSelectorMsg::SetLevel(level), Some(top_n)) => Some(TopN { level, ..top_n }),
+            (TopNSelectorMsg::DirClick, Some(top_n)) => Some(TopN {
+                dir: match top_n.dir {
+                    SortDir::Desc => SortDir::Asc,
+                    _ => SortDir::Desc,
+                },
+                ..top_n
+            }),
+            (_, None) => return false,
+        };
+
+        if top_n == current {
+            return false;
+        }
+
+        let update = ViewConfigUpdate {
+            top_n: top_n.into(),
+            ..ViewConfigUpdate::default()
+        };
+
+        ApiFuture::spawn(ctx.props().update_and_render(update));
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let config = ctx.props().session.get_view_config();
+        let onchange = ctx.link().callback(|event: Event| {
+            let value = event
+                .target()
+                .unwrap()
+                .unchecked_into::<HtmlInputElement>()
+                .value();
+
+            TopNSelectorMsg::SetN(value)
+        });
+
+        let count = config
+            .top_n
+            .as_ref()
+            .map(|x| x.n.to_string())
+            .unwrap_or_default();
+
+        let selected = config.top_n.as_ref().map(|top_n| {
+            let metadata = ctx.props().session.metadata();
+            let columns = metadata
+                .get_table_columns()
+                .into_iter()
+                .flatten()
+                .chain(metadata.get_expression_columns())
+                .map(|x| SelectItem::Option(Labeled(x.clone(), ctx.props().label(x))))
+                .collect::<Vec<_>>();
+
+            let column = Labeled(top_n.column.clone(), ctx.props().label(&top_n.column));
+            let levels = config
+                .group_by
+                .iter()
+                .enumerate()
+                .map(|(idx, x)| SelectItem::Option(Labeled(idx, ctx.props().label(x))))
+                .collect::<Vec<_>>();
+
+            let level = top_n.group_level(&config.group_by).and_then(|x| {
+                let name = config.group_by.get(x)?;
+                Some(Labeled(x, ctx.props().label(name)))
+            });
+
+            (columns, column, top_n.dir, levels, level)
+        });
+
+        html! {
+            <div class="rrow">
+                <div id="top_n">
+                    <div class="psp-text-field">
+                        <div class="psp-text-field__input">
+                            <input
+                                type="number"
+                                class="top-n-count"
+                                min="1"
+                                step="1"
+                                placeholder="All"
+                                value={count}
+                                {onchange}
+                            />
+                            if let Some((columns, column, dir, levels, level)) = selected {
+                                <Select<Labeled<String>>
+                                    class="top-n-column"
+                                    values={columns}
+                                    selected={column}
+                                    on_select={ctx.link().callback(|x: Labeled<String>| {
+                                        TopNSelectorMsg::SetColumn(x.0)
+                                    })}
+                                />
+                                <span
+                                    class={format!("sort-icon {}", dir)}
+                                    onmousedown={ctx.link().callback(|_| TopNSelectorMsg::DirClick)}
+                                />
+                                if let Some(level) = level {
+                                    <span class="top-n-of">{ "of" }</span>
+                                    <Select<Labeled<usize>>
+                                        class="top-n-level"
+                                        values={levels}
+                                        selected={level}
+                                        on_select={ctx.link().callback(|x: Labeled<usize>| {
+                                            TopNSelectorMsg::SetLevel(x.0)
+                                        })}
+                                    />
+                                }
+                            }
+                        </div>
+                        <label class="pivot-selector-label" for="top_n" />
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
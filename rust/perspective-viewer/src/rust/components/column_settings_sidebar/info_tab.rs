@@ -0,0 +1,139 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use yew::{html, Component, Context, Html, Properties};
+
+use crate::session::{ColumnStats, Session};
+use crate::utils::{AddListener, ApiFuture, Subscription};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct InfoTabProps {
+    pub session: Session,
+    pub column_name: String,
+}
+
+pub enum InfoTabMsg {
+    Refresh,
+    SetStats(ColumnStats),
+}
+
+/// Summary statistics of a `Table` column, refreshed when the `Table` is
+/// updated.
+pub struct InfoTab {
+    stats: Option<ColumnStats>,
+    _table_sub: Subscription,
+}
+
+impl InfoTab {
+    fn refresh(&self, ctx: &Context<Self>) {
+        let session = ctx.props().session.clone();
+        let column_name = ctx.props().column_name.clone();
+        let on_stats = ctx.link().callback(InfoTabMsg::SetStats);
+        ApiFuture::spawn(async move {
+            on_stats.emit(session.get_column_stats(column_name).await?);
+            Ok(())
+        });
+    }
+}
+
+impl Component for InfoTab {
+    type Message = InfoTabMsg;
+    type Properties = InfoTabProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let _table_sub = ctx
+            .props()
+            .session
+            .table_updated
+            .add_listener(ctx.link().callback(|_| InfoTabMsg::Refresh));
+
+        ctx.link().send_message(InfoTabMsg::Refresh);
+        Self {
+            stats: None,
+            _table_sub,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().column_name != old_props.column_name {
+            self.stats = None;
+            self.refresh(ctx);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            InfoTabMsg::Refresh => {
+                self.refresh(ctx);
+                false
+            },
+            InfoTabMsg::SetStats(stats) => {
+                self.stats = Some(stats);
+                true
+            },
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let Some(stats) = &self.stats else {
+            return html! { <div id="info-tab" /> };
+        };
+
+        let format_value = |x: Option<String>| x.unwrap_or_else(|| "-".to_owned());
+        let rows = [
+            ("Rows", stats.count.to_string()),
+            ("Nulls", stats.null_count.to_string()),
+            ("Distinct", stats.distinct_count.to_string()),
+            (
+                "Min",
+                format_value(stats.min.as_ref().map(|x| x.to_string())),
+            ),
+            (
+                "Max",
+                format_value(stats.max.as_ref().map(|x| x.to_string())),
+            ),
+            ("Mean", format_value(stats.mean.map(|x| x.to_string()))),
+        ];
+
+        let max_count = stats.histogram.iter().map(|x| x.count).max().unwrap_or(1);
+        let bars = stats.histogram.iter().map(|bucket| {
+            let style = format!("height:{}%", 100 * bucket.count / max_count.max(1));
+            let title = format!("{} to {}: {}", bucket.start, bucket.end, bucket.count);
+            html! { <div class="column-stats-bar" {style} {title} /> }
+        });
+
+        html! {
+            <div id="info-tab">
+                <div class="tab-section">
+                    <table class="column-stats">
+                        {
+                            for rows.into_iter().map(|(label, value)| html! {
+                                <tr>
+                                    <td class="item_title">{ label }</td>
+                                    <td>{ value }</td>
+                                </tr>
+                            })
+                        }
+                    </table>
+                </div>
+                if !stats.histogram.is_empty() {
+                    <div class="tab-section">
+                        <div class="column-stats-histogram">{ for bars }</div>
+                    </div>
+                }
+            </div>
+        }
+    }
+}
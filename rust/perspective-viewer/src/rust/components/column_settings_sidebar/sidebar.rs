@@ -18,6 +18,7 @@ use itertools::Itertools;
 use yew::{html, Callback, Component, Html, Properties};
 
 use super::attributes_tab::AttributesTabProps;
+use super::info_tab::{InfoTab, InfoTabProps};
 use super::style_tab::StyleTabProps;
 use crate::components::column_settings_sidebar::attributes_tab::AttributesTab;
 use crate::components::column_settings_sidebar::save_settings::SaveSettingsProps;
@@ -35,7 +36,7 @@ use crate::model::*;
 use crate::presentation::Presentation;
 use crate::renderer::Renderer;
 use crate::session::Session;
-use crate::utils::{AddListener, Subscription};
+use crate::utils::{AddListener, ApiFuture, Subscription};
 use crate::{css, derive_model};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -43,6 +44,7 @@ pub enum ColumnSettingsTab {
     #[default]
     Attributes,
     Style,
+    Info,
 }
 impl Tab for ColumnSettingsTab {}
 impl Display for ColumnSettingsTab {
@@ -83,6 +85,7 @@ pub enum ColumnSettingsMsg {
     SetExprValid(bool),
     SetHeaderValue(Option<String>),
     SetHeaderValid(bool),
+    SetColumnAlias(Option<String>, bool),
     SetSelectedTab((usize, ColumnSettingsTab)),
     OnSaveAttributes(()),
     OnResetAttributes(()),
@@ -132,8 +135,19 @@ impl ColumnSettingsSidebar {
             .get_expression_by_alias(&column_name)
             .unwrap_or_default();
         let initial_expr_value = Rc::new(initial_expr_value);
-        let initial_header_value =
-            (*initial_expr_value != column_name).then_some(column_name.clone());
+        // The header of a table column edits its alias, rather than its name.
+        let initial_header_value = match &ctx.props().selected_column {
+            ColumnLocator::Plain(name) => ctx
+                .props()
+                .presentation
+                .get_column_aliases()
+                .get(name)
+                .cloned(),
+            ColumnLocator::Expr(_) => {
+                (*initial_expr_value != column_name).then_some(column_name.clone())
+            },
+        };
+
         let maybe_ty = ctx.props().selected_column.view_type(ctx.props().session());
 
         // NOTE: This is going to be refactored soon.
@@ -170,6 +184,18 @@ impl ColumnSettingsSidebar {
             if ctx.props().selected_column.is_expr() {
                 tabs.push(ColumnSettingsTab::Attributes);
             }
+
+            if !matches!(ctx.props().selected_column, ColumnLocator::Expr(None))
+                && ctx
+                    .props()
+                    .session
+                    .metadata()
+                    .get_column_table_type(&column_name)
+                    .is_some()
+            {
+                tabs.push(ColumnSettingsTab::Info);
+            }
+
             tabs
         };
 
@@ -257,6 +283,20 @@ impl Component for ColumnSettingsSidebar {
                 self.save_enabled_effect();
                 true
             },
+            ColumnSettingsMsg::SetColumnAlias(alias, valid) => {
+                self.header_valid = valid;
+                if valid {
+                    ctx.props()
+                        .presentation
+                        .set_column_alias(&self.column_name, alias);
+
+                    let session = ctx.props().session.clone();
+                    let renderer = ctx.props().renderer.clone();
+                    ApiFuture::spawn(async move { renderer.update(&session).await });
+                }
+
+                true
+            },
             ColumnSettingsMsg::SetSelectedTab((idx, val)) => {
                 let rerender = self.selected_tab != val || self.selected_tab_idx != idx;
                 self.selected_tab = val;
@@ -308,21 +348,30 @@ impl Component for ColumnSettingsSidebar {
     }
 
     fn view(&self, ctx: &yew::prelude::Context<Self>) -> Html {
+        let is_expr = ctx.props().selected_column.is_expr();
         let header_props = EditableHeaderProps {
             icon_type: self
                 .maybe_ty
                 .map(|ty| ty.into())
                 .or(Some(TypeIconType::Expr)),
-            on_change: ctx.link().batch_callback(|(value, valid)| {
-                vec![
-                    ColumnSettingsMsg::SetHeaderValue(value),
-                    ColumnSettingsMsg::SetHeaderValid(valid),
-                ]
-            }),
-            editable: ctx.props().selected_column.is_expr()
-                && matches!(self.selected_tab, ColumnSettingsTab::Attributes),
+            on_change: if is_expr {
+                ctx.link().batch_callback(|(value, valid)| {
+                    vec![
+                        ColumnSettingsMsg::SetHeaderValue(value),
+                        ColumnSettingsMsg::SetHeaderValid(valid),
+                    ]
+                })
+            } else {
+                ctx.link()
+                    .callback(|(value, valid)| ColumnSettingsMsg::SetColumnAlias(value, valid))
+            },
+            editable: !is_expr || matches!(self.selected_tab, ColumnSettingsTab::Attributes),
             initial_value: self.initial_header_value.clone(),
-            placeholder: self.expr_value.clone(),
+            placeholder: if is_expr {
+                self.expr_value.clone()
+            } else {
+                Rc::new(self.column_name.clone())
+            },
             session: ctx.props().session.clone(),
             reset_count: self.reset_count,
         };
@@ -361,9 +410,15 @@ impl Component for ColumnSettingsSidebar {
             column_name: self.column_name.clone(),
         };
 
+        let info_tab = InfoTabProps {
+            session: ctx.props().session.clone(),
+            column_name: self.column_name.clone(),
+        };
+
         let tab_children = self.tabs.iter().map(|tab| match tab {
             ColumnSettingsTab::Attributes => html! { <AttributesTab ..attrs_tab.clone() /> },
             ColumnSettingsTab::Style => html! { <StyleTab ..style_tab.clone() /> },
+            ColumnSettingsTab::Info => html! { <InfoTab ..info_tab.clone() /> },
         });
 
         html! {
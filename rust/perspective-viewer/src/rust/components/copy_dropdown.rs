@@ -90,6 +90,9 @@ fn get_menu_items(has_render: bool) -> Vec<CopyDropDownMenuItem> {
             ExportMethod::CsvAll,
             ExportMethod::JsonAll,
         ]),
-        CopyDropDownMenuItem::OptGroup("Config".into(), vec![ExportMethod::JsonConfig]),
+        CopyDropDownMenuItem::OptGroup("Config".into(), vec![
+            ExportMethod::JsonConfig,
+            ExportMethod::Sql,
+        ]),
     ]
 }
@@ -78,7 +78,8 @@ fn get_menu_items(name: &str, has_render: bool) -> Vec<ExportDropDownMenuItem> {
             ExportMethod::ArrowAll.new_file(name),
         ]),
         ExportDropDownMenuItem::OptGroup("Config".into(), vec![
-            ExportMethod::JsonConfig.new_file(name)
+            ExportMethod::JsonConfig.new_file(name),
+            ExportMethod::Sql.new_file(name),
         ]),
     ]
 }
@@ -97,6 +97,16 @@ impl Component for RenderWarning {
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         if self.col_warn.is_some() || self.row_warn.is_some() {
+            // `dimensions` are already limited by `top_n`, so note it for context.
+            let points = match &ctx.props().session.get_view_config().top_n {
+                Some(top_n) => format!(
+                    "of points (top {} by {}).",
+                    pretty_print_int(top_n.n.get()),
+                    top_n.column
+                ),
+                None => "of points.".to_owned(),
+            };
+
             let warning = match (self.col_warn, self.row_warn) {
                 (Some((x, y)), Some((a, b))) => html! {
                     <span
@@ -106,7 +116,7 @@ impl Component for RenderWarning {
                         { render_pair(x, y) }
                         { "of columns and" }
                         { render_pair(a, b) }
-                        { "of points." }
+                        { points }
                     </span>
                 },
                 (Some((x, y)), None) => html! {
@@ -124,7 +134,7 @@ impl Component for RenderWarning {
                     >
                         { "Rendering" }
                         { render_pair(x, y) }
-                        { "of points." }
+                        { points }
                     </span>
                 },
                 _ => html! { <div /> },
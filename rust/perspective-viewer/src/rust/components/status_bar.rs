@@ -17,8 +17,10 @@ use yew::prelude::*;
 use super::style::LocalStyle;
 use crate::components::containers::select::*;
 use crate::components::status_bar_counter::StatusBarRowsCounter;
+use crate::config::ViewConfigUpdate;
 use crate::custom_elements::copy_dropdown::*;
 use crate::custom_elements::export_dropdown::*;
+use crate::model::*;
 use crate::presentation::Presentation;
 use crate::renderer::*;
 use crate::session::*;
@@ -50,6 +52,9 @@ impl PartialEq for StatusBarProps {
 
 pub enum StatusBarMsg {
     Reset(bool),
+    Undo,
+    Redo,
+    HistoryChanged,
     Export,
     Copy,
     SetThemeConfig((Vec<String>, Option<usize>)),
@@ -57,6 +62,7 @@ pub enum StatusBarMsg {
     TableStatsChanged,
     SetIsUpdating(bool),
     SetTitle(Option<String>),
+    Resume,
 }
 
 /// A toolbar with buttons, and `Table` & `View` status information.
@@ -68,7 +74,7 @@ pub struct StatusBar {
     copy_ref: NodeRef,
     export_dropdown: Option<ExportDropDownMenuElement>,
     copy_dropdown: Option<CopyDropDownMenuElement>,
-    _sub: [Subscription; 5],
+    _sub: [Subscription; 6],
 }
 
 impl Component for StatusBar {
@@ -98,6 +104,10 @@ impl Component for StatusBar {
                 .presentation
                 .title_changed
                 .add_listener(ctx.link().callback(|_| StatusBarMsg::TableStatsChanged)),
+            ctx.props()
+                .presentation
+                .history_changed
+                .add_listener(ctx.link().callback(|_| StatusBarMsg::HistoryChanged)),
         ];
 
         // Fetch initial theme
@@ -131,6 +141,15 @@ impl Component for StatusBar {
                 ctx.props().on_reset.emit(all);
                 false
             },
+            StatusBarMsg::Undo => {
+                ApiFuture::spawn(ctx.props().undo());
+                false
+            },
+            StatusBarMsg::Redo => {
+                ApiFuture::spawn(ctx.props().redo());
+                false
+            },
+            StatusBarMsg::HistoryChanged => true,
             StatusBarMsg::SetThemeConfig((themes, index)) => {
                 let new_theme = index.and_then(|x| themes.get(x)).cloned();
                 let should_render = new_theme != self.theme || self.themes != themes;
@@ -170,6 +189,13 @@ impl Component for StatusBar {
                 ctx.props().presentation.set_title(title);
                 false
             },
+            StatusBarMsg::Resume => {
+                if ctx.props().session.set_pause(false) {
+                    ApiFuture::spawn(ctx.props().update_and_render(ViewConfigUpdate::default()));
+                }
+
+                false
+            },
         }
     }
 
@@ -189,6 +215,16 @@ impl Component for StatusBar {
             .link()
             .callback(|event: MouseEvent| StatusBarMsg::Reset(event.shift_key()));
 
+        let undo = ctx.link().callback(|_: MouseEvent| StatusBarMsg::Undo);
+        let redo = ctx.link().callback(|_: MouseEvent| StatusBarMsg::Redo);
+        let undo_class = classes!(
+            "button",
+            (!ctx.props().presentation.can_undo()).then_some("disabled")
+        );
+        let redo_class = classes!(
+            "button",
+            (!ctx.props().presentation.can_redo()).then_some("disabled")
+        );
         let export = ctx.link().callback(|_: MouseEvent| StatusBarMsg::Export);
         let copy = ctx.link().callback(|_: MouseEvent| StatusBarMsg::Copy);
 
@@ -239,6 +275,30 @@ impl Component for StatusBar {
             }
         });
 
+        let pending = match &stats {
+            Some(ViewStats {
+                num_pending_updates: Some((updates, row_count_change)),
+                ..
+            }) if *updates > 0 => {
+                let resume = ctx.link().callback(|_: MouseEvent| StatusBarMsg::Resume);
+                let title = format!(
+                    "Net row count change {:+}, click to resume",
+                    row_count_change
+                );
+                html! {
+                    <span
+                        id="pending"
+                        class="button"
+                        {title}
+                        onmousedown={resume}
+                    >
+                        <span>{ format!("{} updates pending", updates) }</span>
+                    </span>
+                }
+            },
+            _ => html! {},
+        };
+
         html! {
             <>
                 <LocalStyle
@@ -261,12 +321,27 @@ impl Component for StatusBar {
                         />
                     </label>
                     <div id="rows" class="section"><StatusBarRowsCounter {stats} /></div>
+                    { pending }
                     <div
                         id="menu-bar"
                         class="section"
                     >
                         { theme_button }
                         <div id="plugin-settings"><slot name="plugin-settings" /></div>
+                        <span
+                            id="undo"
+                            class={undo_class}
+                            onmousedown={undo}
+                        >
+                            <span >{ "Undo" }</span>
+                        </span>
+                        <span
+                            id="redo"
+                            class={redo_class}
+                            onmousedown={redo}
+                        >
+                            <span >{ "Redo" }</span>
+                        </span>
                         <span
                             id="reset"
                             class="button"
@@ -128,6 +128,7 @@ pub async fn test_status_initializing() {
         num_view_cells: None,
         is_split_by: false,
         is_filtered: false,
+        num_pending_updates: None,
     });
 
     let (div, session) = gen(&stats).await;
@@ -144,6 +145,7 @@ pub async fn test_status_table_loaded() {
         is_group_by: false,
         is_split_by: false,
         is_filtered: false,
+        num_pending_updates: None,
     });
 
     let (div, session) = gen(&stats).await;
@@ -162,6 +164,7 @@ pub async fn test_status_table_and_view_loaded() {
         is_group_by: false,
         is_split_by: false,
         is_filtered: false,
+        num_pending_updates: None,
     });
 
     let (div, session) = gen(&stats).await;
@@ -175,3 +178,24 @@ pub async fn test_status_table_and_view_loaded() {
          rows</span>"
     );
 }
+
+#[wasm_bindgen_test]
+pub async fn test_status_pending_updates() {
+    let stats = Some(ViewStats {
+        num_table_cells: Some((12345678, 0)),
+        num_view_cells: None,
+        is_group_by: false,
+        is_split_by: false,
+        is_filtered: false,
+        num_pending_updates: Some((3, 10)),
+    });
+
+    let (div, session) = gen(&stats).await;
+    assert_eq!(session.get_table_stats(), stats);
+    let pending = div.query_selector("#pending").unwrap().unwrap();
+    assert_eq!(pending.text_content().unwrap(), "3 updates pending");
+    assert_eq!(
+        pending.get_attribute("title").unwrap(),
+        "Net row count change +10, click to resume"
+    );
+}
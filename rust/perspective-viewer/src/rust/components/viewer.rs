@@ -77,7 +77,10 @@ impl ColumnLocator {
         let view_config = session.get_view_config();
         view_config.group_by.contains(name)
             || view_config.split_by.contains(name)
-            || view_config.filter.iter().any(|filter| &filter.0 == name)
+            || view_config
+                .filter
+                .iter_filters()
+                .any(|filter| &filter.0 == name)
             || view_config.sort.iter().any(|sort| &sort.0 == name)
     }
 
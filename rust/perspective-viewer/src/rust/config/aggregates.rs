@@ -13,12 +13,17 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::column_type::*;
 use crate::utils::{ApiError, ApiResult};
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, JsonSchema, Ord, PartialEq, PartialOrd, Serialize,
+)]
 #[serde()]
 pub enum SingleAggregate {
     #[serde(rename = "sum")]
@@ -171,14 +176,56 @@ impl FromStr for SingleAggregate {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+/// Aggregates which take another column as a parameter, e.g. the weights of
+/// `weighted mean`.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, JsonSchema, Ord, PartialEq, PartialOrd, Serialize,
+)]
 #[serde()]
 pub enum MultiAggregate {
     #[serde(rename = "weighted mean")]
     WeightedMean,
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+impl Display for MultiAggregate {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(fmt, "{}", self.as_str())
+    }
+}
+
+impl MultiAggregate {
+    pub const ALL: &'static [Self] = &[Self::WeightedMean];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::WeightedMean => "weighted mean",
+        }
+    }
+
+    /// The text which precedes the parameter column in this aggregate's
+    /// `Display`, e.g. `"weighted mean by "`.
+    pub const fn prefix(&self) -> &'static str {
+        match self {
+            Self::WeightedMean => "weighted mean by ",
+        }
+    }
+
+    /// Whether this aggregate can be applied to a column of type `ty`.
+    pub const fn accepts(&self, ty: Type) -> bool {
+        match self {
+            Self::WeightedMean => matches!(ty, Type::Integer | Type::Float),
+        }
+    }
+
+    /// Whether a column of type `ty` is a valid parameter of this aggregate.
+    pub const fn accepts_param(&self, ty: Type) -> bool {
+        match self {
+            Self::WeightedMean => matches!(ty, Type::Integer | Type::Float),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, Ord, PartialEq, PartialOrd, Serialize)]
 #[serde(untagged)]
 pub enum Aggregate {
     SingleAggregate(SingleAggregate),
@@ -189,9 +236,7 @@ impl Display for Aggregate {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
         match self {
             Self::SingleAggregate(x) => write!(fmt, "{}", x)?,
-            Self::MultiAggregate(MultiAggregate::WeightedMean, x) => {
-                write!(fmt, "weighted mean by {}", x)?
-            },
+            Self::MultiAggregate(x, param) => write!(fmt, "{}{}", x.prefix(), param)?,
         };
         Ok(())
     }
@@ -201,13 +246,125 @@ impl FromStr for Aggregate {
     type Err = ApiError;
 
     fn from_str(input: &str) -> ApiResult<Self> {
-        Ok(
-            if let Some(stripped) = input.strip_prefix("weighted mean by ") {
-                Self::MultiAggregate(MultiAggregate::WeightedMean, stripped.to_owned())
-            } else {
-                Self::SingleAggregate(SingleAggregate::from_str(input)?)
+        for x in MultiAggregate::ALL {
+            if let Some(param) = input.strip_prefix(x.prefix()) {
+                return Ok(Self::MultiAggregate(*x, param.to_owned()));
+            }
+        }
+
+        Ok(Self::SingleAggregate(SingleAggregate::from_str(input)?))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ColumnAggregatesDeserde {
+    Aggregate(Aggregate),
+    List(Vec<Option<Aggregate>>),
+}
+
+/// The `Aggregate`s of a column, one for each occurrence of the column in
+/// `columns` in order, where `None` (or a missing entry) is the column type's
+/// default.  A column with a single `Aggregate` serializes as just that
+/// `Aggregate`, as configs did before columns could be repeated.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(from = "ColumnAggregatesDeserde", into = "ColumnAggregatesDeserde")]
+pub struct ColumnAggregates(Vec<Option<Aggregate>>);
+
+impl JsonSchema for ColumnAggregates {
+    fn schema_name() -> String {
+        "ColumnAggregates".to_owned()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        ColumnAggregatesDeserde::json_schema(generator)
+    }
+}
+
+impl From<ColumnAggregatesDeserde> for ColumnAggregates {
+    fn from(value: ColumnAggregatesDeserde) -> Self {
+        match value {
+            ColumnAggregatesDeserde::Aggregate(x) => Self(vec![Some(x)]),
+            ColumnAggregatesDeserde::List(xs) => {
+                let mut aggregates = Self(xs);
+                aggregates.trim();
+                aggregates
             },
-        )
+        }
+    }
+}
+
+impl From<ColumnAggregates> for ColumnAggregatesDeserde {
+    fn from(value: ColumnAggregates) -> Self {
+        match <[_; 1]>::try_from(value.0) {
+            Ok([Some(x)]) => Self::Aggregate(x),
+            Ok(xs) => Self::List(xs.into()),
+            Err(xs) => Self::List(xs),
+        }
+    }
+}
+
+impl From<Aggregate> for ColumnAggregates {
+    fn from(value: Aggregate) -> Self {
+        Self(vec![Some(value)])
+    }
+}
+
+impl ColumnAggregates {
+    /// The `Aggregate` of the `occurrence`-th entry of this column in
+    /// `columns`, or `None` for the default.
+    pub fn get(&self, occurrence: usize) -> Option<&Aggregate> {
+        self.0.get(occurrence)?.as_ref()
+    }
+
+    pub fn set(&mut self, occurrence: usize, aggregate: Option<Aggregate>) {
+        if occurrence >= self.0.len() {
+            self.0.resize(occurrence + 1, None);
+        }
+
+        self.0[occurrence] = aggregate;
+        self.trim();
+    }
+
+    /// Insert the `Aggregate` of a new `occurrence`-th entry, shifting the
+    /// `Aggregate`s of later entries up by one.
+    pub fn insert(&mut self, occurrence: usize, aggregate: Option<Aggregate>) {
+        if occurrence > self.0.len() {
+            self.0.resize(occurrence, None);
+        }
+
+        self.0.insert(occurrence, aggregate);
+        self.trim();
+    }
+
+    /// Remove the `Aggregate` of the `occurrence`-th entry, shifting the
+    /// `Aggregate`s of later entries down by one.
+    pub fn remove(&mut self, occurrence: usize) {
+        if occurrence < self.0.len() {
+            self.0.remove(occurrence);
+            self.trim();
+        }
+    }
+
+    /// Drop the `Aggregate`s of occurrences past the first `len`.
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+        self.trim();
+    }
+
+    /// Whether every occurrence uses the default `Aggregate`.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<&Aggregate>> {
+        self.0.iter().map(|x| x.as_ref())
+    }
+
+    fn trim(&mut self) {
+        while let Some(None) = self.0.last() {
+            self.0.pop();
+        }
     }
 }
 
@@ -19,6 +19,7 @@ mod simple_format;
 pub use color_mode::*;
 pub use custom::*;
 pub use custom_format::*;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 pub use simple::*;
 pub use simple_format::*;
@@ -26,7 +27,7 @@ pub use simple_format::*;
 use crate::*;
 
 /// `Simple` case has all default-able keys and must be last!
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum DatetimeFormatType {
     Custom(CustomDatetimeStyleConfig),
@@ -35,7 +36,7 @@ pub enum DatetimeFormatType {
 
 /// A model for the JSON serialized style configuration for a column of type
 /// `datetime`.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 pub struct DatetimeColumnStyleConfig {
     #[serde(flatten)]
     pub _format: DatetimeFormatType,
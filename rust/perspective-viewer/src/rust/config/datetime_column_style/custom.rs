@@ -10,11 +10,12 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::custom_format::CustomDatetimeFormat;
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 enum FormatUnit {
     #[serde(rename = "custom")]
     #[default]
@@ -61,7 +62,7 @@ const fn numeric_default() -> u32 {
     0
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 pub struct CustomDatetimeStyleConfig {
     format: FormatUnit,
 
@@ -0,0 +1,53 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::filters::*;
+
+/// Which `group_by` rows of a `View` are expanded, as a depth which rows are
+/// expanded to (all rows when `None`), then a list of rows which are expanded
+/// or collapsed individually.  The `View` itself does not persist this, so it
+/// is passed to the plugin as the `"expansion"` field of its `restore()` token
+/// and applied to each new `View` the plugin draws.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExpansionState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<ExpansionOverride>,
+}
+
+/// A single `group_by` row, by its `__ROW_PATH__`, which is expanded or
+/// collapsed regardless of `ExpansionState::depth`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExpansionOverride {
+    pub row_path: Vec<Scalar>,
+    pub expanded: bool,
+}
+
+impl ExpansionState {
+    pub fn to_depth(depth: usize) -> Self {
+        Self {
+            depth: Some(depth),
+            overrides: vec![],
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.depth.is_none() && self.overrides.is_empty()
+    }
+}
@@ -13,9 +13,12 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Clone, PartialEq, Debug)]
+#[derive(Deserialize, Clone, PartialEq, Debug, JsonSchema)]
 #[serde(untagged)]
 pub enum ExpressionsDeserde {
     Array(Vec<String>),
@@ -26,6 +29,16 @@ pub enum ExpressionsDeserde {
 #[serde(from = "ExpressionsDeserde")]
 pub struct Expressions(HashMap<String, String>);
 
+impl JsonSchema for Expressions {
+    fn schema_name() -> String {
+        "Expressions".to_owned()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        ExpressionsDeserde::json_schema(generator)
+    }
+}
+
 impl std::ops::Deref for Expressions {
     type Target = HashMap<String, String>;
 
@@ -40,7 +53,7 @@ impl std::ops::DerefMut for Expressions {
     }
 }
 
-fn upgrade_legacy_format(expressions: &[String]) -> HashMap<String, String> {
+pub(super) fn upgrade_legacy_format(expressions: &[String]) -> HashMap<String, String> {
     tracing::warn!("Legacy `expressions` format: {:?}", expressions);
     expressions.iter().map(|s| {
         if let Some((name, expression)) = s.split_once('\n') && !expression.is_empty() && name.starts_with("//") {
@@ -13,36 +13,175 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Months, NaiveDate, NaiveDateTime, TimeZone,
+    Timelike, Utc,
+};
 use itertools::Itertools;
-use serde::{Deserialize, Serialize};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::JsonSchema;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Clone, Deserialize, Debug, PartialEq, Serialize)]
-#[serde(untagged)]
+use super::column_type::Type;
+use crate::js::PerspectiveValidationError;
+
+/// The largest integer a JavaScript `number` (and hence `Scalar::Float`) can
+/// represent exactly.
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+/// Parse a numeric filter literal, rejecting `NaN` and the infinities (as
+/// well as literals which overflow to them), which no column can contain.
+pub fn parse_finite(x: &str) -> Option<f64> {
+    x.parse::<f64>().ok().filter(|x| x.is_finite())
+}
+
+/// A filter term value.  `Int` and `Date` are not distinguishable from a
+/// number when deserialized, so integers within JavaScript's safe range
+/// deserialize as `Float`;  call `Scalar::coerce()` once the column type is
+/// known to recover the precise variant.
+#[derive(Clone, Debug, PartialEq)]
 pub enum Scalar {
     Float(f64),
+    Int(i64),
     String(String),
     Bool(bool),
     DateTime(f64),
+
+    /// A date, as the POSIX timestamp in milliseconds of midnight UTC.
+    Date(i64),
     Null,
-    // // Can only have one u64 representation ...
-    // Date(u64)
-    // Int(u32)
 }
 
 impl Display for Scalar {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
         match self {
             Self::Float(x) => write!(fmt, "{}", x),
+            Self::Int(x) => write!(fmt, "{}", x),
             Self::String(x) => write!(fmt, "{}", x),
             Self::Bool(x) => write!(fmt, "{}", x),
             Self::DateTime(x) => write!(fmt, "{}", x),
+            Self::Date(x) => match Utc.timestamp_millis_opt(*x).earliest() {
+                Some(date) => write!(fmt, "{}", date.format("%Y-%m-%d")),
+                None => write!(fmt, "{}", x),
+            },
             Self::Null => write!(fmt, ""),
         }
     }
 }
 
+impl Serialize for Scalar {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Float(x) | Self::DateTime(x) => serializer.serialize_f64(*x),
+            Self::Int(x) | Self::Date(x)
+                if serializer.is_human_readable() && x.unsigned_abs() > MAX_SAFE_INTEGER as u64 =>
+            {
+                // JSON consumers (JavaScript in particular) would round this to
+                // the nearest `f64`.
+                serializer.collect_str(x)
+            },
+            Self::Int(x) | Self::Date(x) => serializer.serialize_i64(*x),
+            Self::String(x) => serializer.serialize_str(x),
+            Self::Bool(x) => serializer.serialize_bool(*x),
+            Self::Null => serializer.serialize_unit(),
+        }
+    }
+}
+
+impl JsonSchema for Scalar {
+    fn schema_name() -> String {
+        "Scalar".to_owned()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(
+                vec![
+                    InstanceType::Number,
+                    InstanceType::String,
+                    InstanceType::Boolean,
+                    InstanceType::Null,
+                ]
+                .into(),
+            ),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl<'de> Deserialize<'de> for Scalar {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ScalarVisitor;
+
+        impl<'de> Visitor<'de> for ScalarVisitor {
+            type Value = Scalar;
+
+            fn expecting(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                fmt.write_str("a number, string, boolean or null")
+            }
+
+            fn visit_bool<E: de::Error>(self, x: bool) -> Result<Scalar, E> {
+                Ok(Scalar::Bool(x))
+            }
+
+            fn visit_i64<E: de::Error>(self, x: i64) -> Result<Scalar, E> {
+                if x.unsigned_abs() > MAX_SAFE_INTEGER as u64 {
+                    Ok(Scalar::Int(x))
+                } else {
+                    Ok(Scalar::Float(x as f64))
+                }
+            }
+
+            fn visit_u64<E: de::Error>(self, x: u64) -> Result<Scalar, E> {
+                match i64::try_from(x) {
+                    Ok(x) => self.visit_i64(x),
+                    Err(_) => Ok(Scalar::Float(x as f64)),
+                }
+            }
+
+            fn visit_f64<E: de::Error>(self, x: f64) -> Result<Scalar, E> {
+                if x.is_finite() {
+                    Ok(Scalar::Float(x))
+                } else {
+                    Err(E::invalid_value(
+                        de::Unexpected::Float(x),
+                        &"a finite number",
+                    ))
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, x: &str) -> Result<Scalar, E> {
+                Ok(Scalar::String(x.to_owned()))
+            }
+
+            fn visit_string<E: de::Error>(self, x: String) -> Result<Scalar, E> {
+                Ok(Scalar::String(x))
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Scalar, E> {
+                Ok(Scalar::Null)
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Scalar, E> {
+                Ok(Scalar::Null)
+            }
+        }
+
+        deserializer.deserialize_any(ScalarVisitor)
+    }
+}
+
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Clone, Copy, Deserialize, Debug, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Deserialize, Debug, Eq, PartialEq, Serialize, JsonSchema)]
 #[serde()]
 pub enum FilterOp {
     #[serde(rename = "contains")]
@@ -83,6 +222,24 @@ pub enum FilterOp {
 
     #[serde(rename = "!=")]
     NE,
+
+    #[serde(rename = "between")]
+    Between,
+
+    #[serde(rename = "matches")]
+    Matches,
+
+    #[serde(rename = "== (ignore case)")]
+    EQIgnoreCase,
+
+    #[serde(rename = "contains (ignore case)")]
+    ContainsIgnoreCase,
+
+    #[serde(rename = "begins with (ignore case)")]
+    BeginsWithIgnoreCase,
+
+    #[serde(rename = "ends with (ignore case)")]
+    EndsWithIgnoreCase,
 }
 
 impl Display for FilterOp {
@@ -101,6 +258,12 @@ impl Display for FilterOp {
             Self::GTE => ">=",
             Self::LTE => "<=",
             Self::NE => "!=",
+            Self::Between => "between",
+            Self::Matches => "matches",
+            Self::EQIgnoreCase => "== (ignore case)",
+            Self::ContainsIgnoreCase => "contains (ignore case)",
+            Self::BeginsWithIgnoreCase => "begins with (ignore case)",
+            Self::EndsWithIgnoreCase => "ends with (ignore case)",
         };
 
         write!(fmt, "{}", op)
@@ -125,16 +288,38 @@ impl FromStr for FilterOp {
             ">=" => Ok(Self::GTE),
             "<=" => Ok(Self::LTE),
             "!=" => Ok(Self::NE),
+            "between" => Ok(Self::Between),
+            "matches" => Ok(Self::Matches),
+            "== (ignore case)" => Ok(Self::EQIgnoreCase),
+            "contains (ignore case)" => Ok(Self::ContainsIgnoreCase),
+            "begins with (ignore case)" => Ok(Self::BeginsWithIgnoreCase),
+            "ends with (ignore case)" => Ok(Self::EndsWithIgnoreCase),
             x => Err(format!("Unknown filter operator {}", x)),
         }
     }
 }
 
-#[derive(Clone, Deserialize, Debug, PartialEq, Serialize)]
+impl FilterOp {
+    /// Does this operator have no engine filter equivalent, and must instead
+    /// be sent as an ExprTK expression column?
+    pub fn is_expression(&self) -> bool {
+        matches!(
+            self,
+            Self::Matches
+                | Self::EQIgnoreCase
+                | Self::ContainsIgnoreCase
+                | Self::BeginsWithIgnoreCase
+                | Self::EndsWithIgnoreCase
+        )
+    }
+}
+
+#[derive(Clone, Deserialize, Debug, PartialEq, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub enum FilterTerm {
     Scalar(Scalar),
     Array(Vec<Scalar>),
+    Relative(RelativeDate),
 }
 
 impl Display for FilterTerm {
@@ -149,12 +334,865 @@ impl Display for FilterTerm {
                 Itertools::intersperse(xs.iter().map(|x| format!("{}", x)), ",".to_owned())
                     .collect::<String>()
             )?,
+            Self::Relative(x) => write!(fmt, "{}", x)?,
         }
 
         Ok(())
     }
 }
 
-#[derive(Clone, Deserialize, Debug, PartialEq, Serialize)]
+/// The calendar unit of a `RelativeDate`.
+#[derive(Clone, Copy, Deserialize, Debug, Eq, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DateUnit {
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Display for DateUnit {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        let unit = match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        };
+
+        write!(fmt, "{}", unit)
+    }
+}
+
+impl FromStr for DateUnit {
+    type Err = String;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        match input.trim_end_matches('s') {
+            "hour" => Ok(Self::Hour),
+            "day" => Ok(Self::Day),
+            "week" => Ok(Self::Week),
+            "month" => Ok(Self::Month),
+            "year" => Ok(Self::Year),
+            x => Err(format!("Unknown date unit {}", x)),
+        }
+    }
+}
+
+impl DateUnit {
+    fn add(self, x: NaiveDateTime, count: u32) -> Option<NaiveDateTime> {
+        match self {
+            Self::Hour => x.checked_add_signed(Duration::hours(count.into())),
+            Self::Day => x.checked_add_signed(Duration::days(count.into())),
+            Self::Week => x.checked_add_signed(Duration::weeks(count.into())),
+            Self::Month => x.checked_add_months(Months::new(count)),
+            Self::Year => x.checked_add_months(Months::new(count.checked_mul(12)?)),
+        }
+    }
+
+    fn sub(self, x: NaiveDateTime, count: u32) -> Option<NaiveDateTime> {
+        match self {
+            Self::Hour => x.checked_sub_signed(Duration::hours(count.into())),
+            Self::Day => x.checked_sub_signed(Duration::days(count.into())),
+            Self::Week => x.checked_sub_signed(Duration::weeks(count.into())),
+            Self::Month => x.checked_sub_months(Months::new(count)),
+            Self::Year => x.checked_sub_months(Months::new(count.checked_mul(12)?)),
+        }
+    }
+
+    /// The start of the calendar `unit` containing `x`, e.g. midnight for
+    /// `Day` or Monday for `Week`.
+    fn truncate(self, x: NaiveDateTime) -> Option<NaiveDateTime> {
+        let date = x.date();
+        match self {
+            Self::Hour => date.and_hms_opt(x.time().hour(), 0, 0),
+            Self::Day => date.and_hms_opt(0, 0, 0),
+            Self::Week => (date - Duration::days(date.weekday().num_days_from_monday().into()))
+                .and_hms_opt(0, 0, 0),
+            Self::Month => date.with_day(1)?.and_hms_opt(0, 0, 0),
+            Self::Year => date.with_ordinal(1)?.and_hms_opt(0, 0, 0),
+        }
+    }
+}
+
+/// A date range relative to the current time, e.g. "last 7 days".  These are
+/// stored as-is in the `ViewConfig` and resolved to a concrete range each time
+/// a `View` is created, so a saved layout stays correct as time passes.
+/// Serializes as `{"relative": "last", "count": 7, "unit": "day"}`.
+#[derive(Clone, Copy, Deserialize, Debug, Eq, PartialEq, Serialize, JsonSchema)]
+#[serde(tag = "relative", rename_all = "lowercase", deny_unknown_fields)]
+pub enum RelativeDate {
+    /// The current calendar day.
+    Today,
+
+    /// The current calendar `unit`, e.g. "this month".
+    This { unit: DateUnit },
+
+    /// The `count` `unit`s up to now, e.g. "last 24 hours".
+    Last { count: u32, unit: DateUnit },
+}
+
+impl Display for RelativeDate {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Today => write!(fmt, "today"),
+            Self::This { unit } => write!(fmt, "this {}", unit),
+            Self::Last { count: 1, unit } => write!(fmt, "last {}", unit),
+            Self::Last { count, unit } => write!(fmt, "last {} {}s", count, unit),
+        }
+    }
+}
+
+impl RelativeDate {
+    /// Resolve this range to an inclusive `(start, end)` pair of POSIX
+    /// milliseconds, as of `now`.
+    ///
+    /// # Arguments
+    /// - `now` The current local time.
+    /// - `is_date` Whether the range is for a `Type::Date` column, whose values
+    ///   are UTC midnight of the calendar date, in which case the range is
+    ///   expanded to whole days of the local calendar.
+    pub fn resolve(&self, now: DateTime<FixedOffset>, is_date: bool) -> Option<(f64, f64)> {
+        let local = now.naive_local();
+        let (start, end) = match *self {
+            Self::Today => {
+                let start = DateUnit::Day.truncate(local)?;
+                (start, DateUnit::Day.add(start, 1)?)
+            },
+            Self::This { unit } => {
+                let start = unit.truncate(local)?;
+                (start, unit.add(start, 1)?)
+            },
+            Self::Last { count, unit } => (unit.sub(local, count)?, local),
+        };
+
+        let (start, end) = if is_date {
+            let start = DateUnit::Day.truncate(start)?;
+            let end = if DateUnit::Day.truncate(end)? == end {
+                end
+            } else {
+                DateUnit::Day.add(DateUnit::Day.truncate(end)?, 1)?
+            };
+
+            (
+                Utc.from_utc_datetime(&start).timestamp_millis(),
+                Utc.from_utc_datetime(&end).timestamp_millis(),
+            )
+        } else {
+            (
+                now.timezone()
+                    .from_local_datetime(&start)
+                    .earliest()?
+                    .timestamp_millis(),
+                now.timezone()
+                    .from_local_datetime(&end)
+                    .earliest()?
+                    .timestamp_millis(),
+            )
+        };
+
+        Some((start as f64, (end - 1) as f64))
+    }
+}
+
+#[derive(Clone, Deserialize, Debug, PartialEq, Serialize, JsonSchema)]
 #[serde()]
 pub struct Filter(pub String, pub FilterOp, pub FilterTerm);
+
+impl Filter {
+    /// Retype this filter's term for a column of type `ty`, see
+    /// `Scalar::coerce()`.
+    pub fn coerce_type(&mut self, ty: Type) {
+        match &mut self.2 {
+            FilterTerm::Scalar(x) => *x = std::mem::replace(x, Scalar::Null).coerce(ty),
+            FilterTerm::Array(xs) => {
+                for x in xs.iter_mut() {
+                    *x = std::mem::replace(x, Scalar::Null).coerce(ty);
+                }
+            },
+            FilterTerm::Relative(_) => (),
+        }
+    }
+
+    /// Replace a `FilterTerm::Relative` term with the concrete inclusive range
+    /// it describes as of `now`.
+    pub fn resolve_relative_date(&mut self, now: DateTime<FixedOffset>, is_date: bool) {
+        if let FilterTerm::Relative(relative) = &self.2 {
+            self.2 = match relative.resolve(now, is_date) {
+                Some((start, end)) => {
+                    FilterTerm::Array(vec![Scalar::DateTime(start), Scalar::DateTime(end)])
+                },
+                None => FilterTerm::Scalar(Scalar::Null),
+            };
+        }
+    }
+
+    /// Split a `between` filter with a concrete range into the pair of
+    /// comparisons the engine supports.
+    pub fn split_range(&self) -> Option<(Self, Self)> {
+        match (&self.1, &self.2) {
+            (FilterOp::Between, FilterTerm::Array(xs)) if xs.len() == 2 => Some((
+                Self(
+                    self.0.clone(),
+                    FilterOp::GTE,
+                    FilterTerm::Scalar(xs[0].clone()),
+                ),
+                Self(
+                    self.0.clone(),
+                    FilterOp::LTE,
+                    FilterTerm::Scalar(xs[1].clone()),
+                ),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Check that the pattern of a `matches` filter is a valid regular
+    /// expression, as the engine silently treats an invalid pattern as null.
+    pub fn validate_regex(&self) -> Result<(), PerspectiveValidationError> {
+        match (&self.1, &self.2) {
+            (FilterOp::Matches, FilterTerm::Scalar(Scalar::String(x))) => {
+                let (error_message, span) = match regex_syntax::Parser::new().parse(x) {
+                    Ok(_) => return Ok(()),
+                    Err(regex_syntax::Error::Parse(err)) => (err.kind().to_string(), *err.span()),
+                    Err(regex_syntax::Error::Translate(err)) => {
+                        (err.kind().to_string(), *err.span())
+                    },
+                    Err(err) => (
+                        err.to_string(),
+                        regex_syntax::ast::Span::splat(regex_syntax::ast::Position::new(0, 1, 1)),
+                    ),
+                };
+
+                Err(PerspectiveValidationError {
+                    error_message,
+                    line: span.start.line as i32 - 1,
+                    column: span.start.column as i32 - 1,
+                })
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Render this `Filter` as an ExprTK condition, or `None` if the filter
+    /// has no term yet and would be ignored by the engine.
+    fn to_expression(&self) -> Option<String> {
+        if let Some((start, end)) = self.split_range() {
+            return Some(format!(
+                "({} and {})",
+                start.to_expression()?,
+                end.to_expression()?
+            ));
+        }
+
+        let column = format!("\"{}\"", self.0.replace('\\', "\\\\").replace('"', "\\\""));
+        match (&self.1, &self.2) {
+            (FilterOp::IsNull, _) => Some(format!("is_null({})", column)),
+            (FilterOp::IsNotNull, _) => Some(format!("is_not_null({})", column)),
+            (FilterOp::In | FilterOp::NotIn, FilterTerm::Array(xs)) => {
+                let (cmp, reducer) = if self.1 == FilterOp::In {
+                    ("==", " or ")
+                } else {
+                    ("!=", " and ")
+                };
+
+                let terms = xs
+                    .iter()
+                    .filter_map(|x| Some(format!("{} {} {}", column, cmp, x.to_literal()?)))
+                    .collect::<Vec<_>>();
+
+                (!terms.is_empty()).then(|| format!("({})", terms.join(reducer)))
+            },
+            (FilterOp::Matches, FilterTerm::Scalar(Scalar::String(x))) if !x.is_empty() => {
+                Some(format!(
+                    "match({}, {})",
+                    column,
+                    Scalar::String(x.to_owned()).to_literal()?
+                ))
+            },
+            (
+                FilterOp::Contains
+                | FilterOp::BeginsWith
+                | FilterOp::EndsWith
+                | FilterOp::EQIgnoreCase
+                | FilterOp::ContainsIgnoreCase
+                | FilterOp::BeginsWithIgnoreCase
+                | FilterOp::EndsWithIgnoreCase,
+                FilterTerm::Scalar(Scalar::String(x)),
+            ) if !x.is_empty() => {
+                let pattern = x.chars().fold(String::new(), |mut acc, c| {
+                    if "\\.+*?()|[]{}^$".contains(c) {
+                        acc.push('\\');
+                    }
+
+                    acc.push(c);
+                    acc
+                });
+
+                let (func, pattern) = match self.1 {
+                    FilterOp::BeginsWith => ("match", format!("^{}", pattern)),
+                    FilterOp::EndsWith => ("match", format!("{}$", pattern)),
+                    FilterOp::EQIgnoreCase => ("match_all", format!("(?i){}", pattern)),
+                    FilterOp::ContainsIgnoreCase => ("match", format!("(?i){}", pattern)),
+                    FilterOp::BeginsWithIgnoreCase => ("match", format!("(?i)^{}", pattern)),
+                    FilterOp::EndsWithIgnoreCase => ("match", format!("(?i){}$", pattern)),
+                    _ => ("match", pattern),
+                };
+
+                Some(format!(
+                    "{}({}, {})",
+                    func,
+                    column,
+                    Scalar::String(pattern).to_literal()?
+                ))
+            },
+            (
+                FilterOp::EQ
+                | FilterOp::NE
+                | FilterOp::GT
+                | FilterOp::GTE
+                | FilterOp::LT
+                | FilterOp::LTE,
+                FilterTerm::Scalar(x),
+            ) => Some(format!("{} {} {}", column, self.1, x.to_literal()?)),
+            _ => None,
+        }
+    }
+}
+
+impl Scalar {
+    /// Render this `Scalar` as an ExprTK literal.
+    fn to_literal(&self) -> Option<String> {
+        match self {
+            Self::Float(x) => Some(format!("{}", x)),
+            Self::String(x) => Some(format!(
+                "'{}'",
+                x.replace('\\', "\\\\").replace('\'', "\\'")
+            )),
+            Self::Bool(x) => Some(format!("{}", x)),
+            Self::DateTime(x) => Some(format!("datetime({})", x)),
+            Self::Int(x) => Some(format!("{}", x)),
+            Self::Date(x) => {
+                let date = Utc.timestamp_millis_opt(*x).earliest()?;
+                Some(format!(
+                    "date({}, {}, {})",
+                    date.year(),
+                    date.month(),
+                    date.day()
+                ))
+            },
+            Self::Null => None,
+        }
+    }
+
+    /// Retype this `Scalar` to the variant matching a column of type `ty`, e.g.
+    /// a `Float` deserialized from an untyped number becomes an `Int` when
+    /// filtering an `"integer"` column.  Values which don't fit `ty` are
+    /// returned unchanged and left to the engine to reject.
+    pub fn coerce(self, ty: Type) -> Self {
+        const DAY: i64 = 86_400_000;
+        match (ty, self) {
+            (Type::Integer, Self::Float(x))
+                if x.fract() == 0.0 && x.abs() <= MAX_SAFE_INTEGER as f64 =>
+            {
+                Self::Int(x as i64)
+            },
+            (Type::Integer, Self::String(x)) => match x.trim().parse() {
+                Ok(x) => Self::Int(x),
+                Err(_) => Self::String(x),
+            },
+            (Type::Float, Self::Int(x)) => Self::Float(x as f64),
+            (Type::Date, Self::Float(x) | Self::DateTime(x)) if x.is_finite() => {
+                Self::Date((x as i64).div_euclid(DAY) * DAY)
+            },
+            (Type::Date, Self::Int(x)) => Self::Date(x.div_euclid(DAY) * DAY),
+            (Type::Date, Self::String(x)) => match NaiveDate::parse_from_str(&x, "%Y-%m-%d") {
+                Ok(date) => {
+                    Self::Date(date.signed_duration_since(NaiveDate::default()).num_days() * DAY)
+                },
+                Err(_) => Self::String(x),
+            },
+            (Type::Datetime, Self::Float(x)) => Self::DateTime(x),
+            (Type::Datetime, Self::Int(x) | Self::Date(x)) => Self::DateTime(x as f64),
+            (_, x) => x,
+        }
+    }
+}
+
+/// The boolean combinator of a `FilterGroup`.
+#[derive(Clone, Copy, Default, Deserialize, Debug, Eq, PartialEq, Serialize, JsonSchema)]
+pub enum FilterReducer {
+    #[default]
+    #[serde(rename = "and")]
+    And,
+
+    #[serde(rename = "or")]
+    Or,
+}
+
+impl FilterReducer {
+    /// The opposite combinator, used as the default for a newly nested group.
+    pub fn flip(self) -> Self {
+        match self {
+            Self::And => Self::Or,
+            Self::Or => Self::And,
+        }
+    }
+}
+
+impl Display for FilterReducer {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::And => write!(fmt, "and"),
+            Self::Or => write!(fmt, "or"),
+        }
+    }
+}
+
+impl FromStr for FilterReducer {
+    type Err = String;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        match input {
+            "and" => Ok(Self::And),
+            "or" => Ok(Self::Or),
+            x => Err(format!("Unknown filter group operator {}", x)),
+        }
+    }
+}
+
+/// A nested group of filter conditions, combined by `op`.  Serializes as
+/// `{"op": "or", "filters": [...]}`.
+#[derive(Clone, Deserialize, Debug, PartialEq, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FilterGroup {
+    #[serde(default)]
+    pub op: FilterReducer,
+    pub filters: Vec<FilterItem>,
+}
+
+impl FilterGroup {
+    /// Render this group as a single ExprTK condition, or `None` if none of
+    /// its members would filter anything.
+    pub fn to_expression(&self) -> Option<String> {
+        let terms = self
+            .filters
+            .iter()
+            .filter_map(FilterItem::to_expression)
+            .collect::<Vec<_>>();
+
+        match terms.len() {
+            0 => None,
+            1 => terms.into_iter().next(),
+            _ => Some(format!("({})", terms.join(&format!(" {} ", self.op)))),
+        }
+    }
+}
+
+/// An element of `ViewConfig::filter`, which is either a single `Filter` in
+/// the legacy tuple form, or a nested `FilterGroup`.  Top-level elements are
+/// always AND-ed.
+#[derive(Clone, Deserialize, Debug, PartialEq, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum FilterItem {
+    Filter(Filter),
+    Group(FilterGroup),
+}
+
+impl From<Filter> for FilterItem {
+    fn from(filter: Filter) -> Self {
+        Self::Filter(filter)
+    }
+}
+
+impl PartialEq<Filter> for FilterItem {
+    fn eq(&self, other: &Filter) -> bool {
+        self.as_filter() == Some(other)
+    }
+}
+
+impl FilterItem {
+    pub fn as_filter(&self) -> Option<&Filter> {
+        match self {
+            Self::Filter(x) => Some(x),
+            Self::Group(_) => None,
+        }
+    }
+
+    pub fn as_filter_mut(&mut self) -> Option<&mut Filter> {
+        match self {
+            Self::Filter(x) => Some(x),
+            Self::Group(_) => None,
+        }
+    }
+
+    /// Render this item as a single ExprTK condition, or `None` if it would
+    /// not filter anything.
+    pub fn to_expression(&self) -> Option<String> {
+        match self {
+            Self::Filter(x) => x.to_expression(),
+            Self::Group(x) => x.to_expression(),
+        }
+    }
+}
+
+/// Path-addressed operations on a tree of `FilterItem`s, where a path is the
+/// sequence of indices from the top-level list down through nested
+/// `FilterGroup`s.
+#[extend::ext(name = FilterTree)]
+pub impl Vec<FilterItem> {
+    /// Iterate over every `Filter` in the tree, depth-first.
+    fn iter_filters(&self) -> Box<dyn Iterator<Item = &Filter> + '_> {
+        Box::new(self.iter().flat_map(|x| match x {
+            FilterItem::Filter(x) => {
+                Box::new(std::iter::once(x)) as Box<dyn Iterator<Item = &Filter>>
+            },
+            FilterItem::Group(x) => x.filters.iter_filters(),
+        }))
+    }
+
+    /// Iterate over every `FilterGroup` in the tree, depth-first.
+    fn iter_groups(&self) -> Box<dyn Iterator<Item = &FilterGroup> + '_> {
+        Box::new(self.iter().flat_map(|x| match x {
+            FilterItem::Filter(_) => Box::new(std::iter::empty()),
+            FilterItem::Group(x) => Box::new(std::iter::once(x).chain(x.filters.iter_groups()))
+                as Box<dyn Iterator<Item = &FilterGroup>>,
+        }))
+    }
+
+    /// Apply `f` to every `Filter` in the tree.
+    fn for_each_filter_mut(&mut self, f: &mut dyn FnMut(&mut Filter)) {
+        for item in self.iter_mut() {
+            match item {
+                FilterItem::Filter(x) => f(x),
+                FilterItem::Group(x) => x.filters.for_each_filter_mut(f),
+            }
+        }
+    }
+
+    /// Remove every `Filter` for which `f` is `false`, as well as any
+    /// `FilterGroup` which is (or is left) empty, returning the number of
+    /// groups removed.
+    fn retain_filters(&mut self, f: &mut dyn FnMut(&Filter) -> bool) -> usize {
+        let mut removed = 0;
+        self.retain_mut(|item| match item {
+            FilterItem::Filter(x) => f(x),
+            FilterItem::Group(x) => {
+                removed += x.filters.retain_filters(f);
+                let is_empty = x.filters.is_empty();
+                removed += is_empty as usize;
+                !is_empty
+            },
+        });
+
+        removed
+    }
+
+    fn get_path(&self, path: &[usize]) -> Option<&FilterItem> {
+        match path {
+            [] => None,
+            [idx] => self.get(*idx),
+            [idx, rest @ ..] => match self.get(*idx)? {
+                FilterItem::Group(x) => x.filters.get_path(rest),
+                FilterItem::Filter(_) => None,
+            },
+        }
+    }
+
+    fn get_path_mut(&mut self, path: &[usize]) -> Option<&mut FilterItem> {
+        match path {
+            [] => None,
+            [idx] => self.get_mut(*idx),
+            [idx, rest @ ..] => match self.get_mut(*idx)? {
+                FilterItem::Group(x) => x.filters.get_path_mut(rest),
+                FilterItem::Filter(_) => None,
+            },
+        }
+    }
+
+    /// The combinator of the group containing `path`, which is `And` for the
+    /// top-level list.
+    fn get_parent_op(&self, path: &[usize]) -> FilterReducer {
+        match path.split_last() {
+            Some((_, parent)) if !parent.is_empty() => match self.get_path(parent) {
+                Some(FilterItem::Group(x)) => x.op,
+                _ => FilterReducer::And,
+            },
+            _ => FilterReducer::And,
+        }
+    }
+
+    /// Remove the item at `path`, also removing any group this leaves empty.
+    fn remove_path(&mut self, path: &[usize]) -> Option<FilterItem> {
+        match path {
+            [] => None,
+            [idx] => (*idx < self.len()).then(|| self.remove(*idx)),
+            [idx, rest @ ..] => {
+                let FilterItem::Group(group) = self.get_mut(*idx)? else {
+                    return None;
+                };
+
+                let removed = group.filters.remove_path(rest);
+                if group.filters.is_empty() {
+                    self.remove(*idx);
+                }
+
+                removed
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    fn now() -> DateTime<FixedOffset> {
+        // Wednesday 2024-03-13 15:30 at UTC-05:00
+        DateTime::parse_from_rfc3339("2024-03-13T15:30:00-05:00").unwrap()
+    }
+
+    fn posix(x: &str) -> f64 {
+        DateTime::parse_from_rfc3339(x).unwrap().timestamp_millis() as f64
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_string_filter_ops_to_expression() {
+        let filter = |op, x: &str| {
+            Filter(
+                "name".to_owned(),
+                op,
+                FilterTerm::Scalar(Scalar::String(x.to_owned())),
+            )
+            .to_expression()
+        };
+
+        assert_eq!(
+            filter(FilterOp::ContainsIgnoreCase, "a.b"),
+            Some("match(\"name\", '(?i)a\\\\.b')".to_owned())
+        );
+
+        assert_eq!(
+            filter(FilterOp::EQIgnoreCase, "Abc"),
+            Some("match_all(\"name\", '(?i)Abc')".to_owned())
+        );
+
+        assert_eq!(
+            filter(FilterOp::Matches, "^a+$"),
+            Some("match(\"name\", '^a+$')".to_owned())
+        );
+
+        assert_eq!(filter(FilterOp::Matches, ""), None);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_matches_validate_regex() {
+        let filter = |x: &str| {
+            Filter(
+                "name".to_owned(),
+                FilterOp::Matches,
+                FilterTerm::Scalar(Scalar::String(x.to_owned())),
+            )
+        };
+
+        assert!(filter("^a(b|c)+$").validate_regex().is_ok());
+        let err = filter("ab(c").validate_regex().unwrap_err();
+        assert_eq!(err.line, 0);
+        assert_eq!(err.column, 2);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_relative_date_serde() {
+        let filter: Filter = serde_json::from_value(serde_json::json!([
+            "date",
+            "between",
+            {"relative": "last", "count": 7, "unit": "day"}
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            filter,
+            Filter(
+                "date".to_owned(),
+                FilterOp::Between,
+                FilterTerm::Relative(RelativeDate::Last {
+                    count: 7,
+                    unit: DateUnit::Day
+                })
+            )
+        );
+
+        assert_eq!(format!("{}", filter.2), "last 7 days");
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_relative_date_resolve_datetime() {
+        assert_eq!(
+            RelativeDate::Today.resolve(now(), false),
+            Some((
+                posix("2024-03-13T00:00:00-05:00"),
+                posix("2024-03-14T00:00:00-05:00") - 1.0
+            ))
+        );
+
+        let this_week = RelativeDate::This {
+            unit: DateUnit::Week,
+        };
+
+        assert_eq!(
+            this_week.resolve(now(), false),
+            Some((
+                posix("2024-03-11T00:00:00-05:00"),
+                posix("2024-03-18T00:00:00-05:00") - 1.0
+            ))
+        );
+
+        let last_day = RelativeDate::Last {
+            count: 24,
+            unit: DateUnit::Hour,
+        };
+
+        assert_eq!(
+            last_day.resolve(now(), false),
+            Some((
+                posix("2024-03-12T15:30:00-05:00"),
+                posix("2024-03-13T15:30:00-05:00") - 1.0
+            ))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_relative_date_resolve_date() {
+        let this_month = RelativeDate::This {
+            unit: DateUnit::Month,
+        };
+
+        assert_eq!(
+            this_month.resolve(now(), true),
+            Some((
+                posix("2024-03-01T00:00:00Z"),
+                posix("2024-04-01T00:00:00Z") - 1.0
+            ))
+        );
+
+        let last_week = RelativeDate::Last {
+            count: 1,
+            unit: DateUnit::Week,
+        };
+
+        assert_eq!(
+            last_week.resolve(now(), true),
+            Some((
+                posix("2024-03-06T00:00:00Z"),
+                posix("2024-03-14T00:00:00Z") - 1.0
+            ))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_between_split_range() {
+        let mut filter = Filter(
+            "date".to_owned(),
+            FilterOp::Between,
+            FilterTerm::Relative(RelativeDate::Today),
+        );
+
+        assert_eq!(filter.split_range(), None);
+        filter.resolve_relative_date(now(), true);
+        let (start, end) = filter.split_range().unwrap();
+        assert_eq!(start.1, FilterOp::GTE);
+        assert_eq!(end.1, FilterOp::LTE);
+        assert_eq!(
+            start.2,
+            FilterTerm::Scalar(Scalar::DateTime(posix("2024-03-13T00:00:00Z")))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_scalar_int_round_trip() {
+        let id = 9_007_199_254_740_993_i64;
+        let json = serde_json::to_value(Scalar::Int(id)).unwrap();
+        assert_eq!(json, serde_json::json!("9007199254740993"));
+
+        let scalar: Scalar = serde_json::from_value(json).unwrap();
+        assert_eq!(scalar.coerce(Type::Integer), Scalar::Int(id));
+
+        let scalar: Scalar = serde_json::from_str("9007199254740993").unwrap();
+        assert_eq!(scalar, Scalar::Int(id));
+
+        let bytes = rmp_serde::to_vec(&Scalar::Int(id)).unwrap();
+        let scalar: Scalar = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(scalar, Scalar::Int(id));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_scalar_coerce() {
+        let mut filter: Filter = serde_json::from_value(serde_json::json!(["date", "in", [
+            1710288000000_i64,
+            "2024-03-14",
+            1710345600000.5
+        ]]))
+        .unwrap();
+
+        filter.coerce_type(Type::Date);
+        assert_eq!(
+            filter.2,
+            FilterTerm::Array(vec![
+                Scalar::Date(1710288000000),
+                Scalar::Date(1710374400000),
+                Scalar::Date(1710288000000),
+            ])
+        );
+
+        assert_eq!(format!("{}", filter.2), "2024-03-13,2024-03-14,2024-03-13");
+        assert_eq!(Scalar::Float(4.0).coerce(Type::Integer), Scalar::Int(4));
+        assert_eq!(Scalar::Float(4.5).coerce(Type::Integer), Scalar::Float(4.5));
+        assert_eq!(Scalar::Int(4).coerce(Type::Float), Scalar::Float(4.0));
+        assert_eq!(
+            Scalar::Date(1710288000000).coerce(Type::Datetime),
+            Scalar::DateTime(1710288000000.0)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_retain_filters() {
+        let mut filters: Vec<FilterItem> = serde_json::from_value(serde_json::json!([
+            ["a", "==", 1],
+            {"op": "or", "filters": [["b", "==", 2], {"filters": [["b", "==", 3]]}]},
+            {"filters": [["a", "==", 4]]}
+        ]))
+        .unwrap();
+
+        assert_eq!(filters.retain_filters(&mut |x| x.0 == "a"), 2);
+        assert_eq!(
+            filters
+                .iter_filters()
+                .map(|x| x.0.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "a"]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_non_finite_terms_are_rejected() {
+        use serde::de::value::{Error, F64Deserializer};
+        use serde::de::IntoDeserializer;
+
+        for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let deserializer: F64Deserializer<Error> = x.into_deserializer();
+            assert!(Scalar::deserialize(deserializer).is_err());
+        }
+
+        assert_eq!(parse_finite("2.5"), Some(2.5));
+        for x in ["NaN", "inf", "-infinity", "1e999"] {
+            assert_eq!(parse_finite(x), None);
+        }
+
+        let result: Result<Vec<FilterItem>, _> = serde_json::from_str(r#"[["x", ">", 1e999]]"#);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,79 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::filters::*;
+
+/// Links a viewer to the other viewers with the same `name` on the page, such
+/// that a selection in any of them (a clicked `group_by` row, or a
+/// `split_by` column) filters the others.  Selections are published by column
+/// name, and `columns` maps the column names of the selecting viewer to this
+/// viewer's, for linked `Table`s whose column names differ.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LinkGroup {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub columns: HashMap<String, String>,
+}
+
+impl LinkGroup {
+    /// Map a selection published by another viewer in this group to the
+    /// filter it implies for this viewer, by column name.
+    pub fn map_filter(&self, filter: &[Filter]) -> Vec<Filter> {
+        filter
+            .iter()
+            .map(|Filter(column, op, term)| {
+                let column = self.columns.get(column).unwrap_or(column);
+                Filter(column.clone(), *op, term.clone())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_map_filter() {
+        let group = LinkGroup {
+            name: "sales".to_owned(),
+            columns: HashMap::from([("State".to_owned(), "state".to_owned())]),
+        };
+
+        let filter = vec![
+            Filter(
+                "State".to_owned(),
+                FilterOp::EQ,
+                FilterTerm::Scalar(Scalar::String("Texas".to_owned())),
+            ),
+            Filter(
+                "Region".to_owned(),
+                FilterOp::EQ,
+                FilterTerm::Scalar(Scalar::String("Central".to_owned())),
+            ),
+        ];
+
+        let mapped = group.map_filter(&filter);
+        assert_eq!(mapped[0].0, "state");
+        assert_eq!(mapped[0].2, filter[0].2);
+        assert_eq!(mapped[1].0, "Region");
+    }
+}
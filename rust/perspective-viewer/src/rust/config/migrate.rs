@@ -0,0 +1,696 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Upgrades persisted `ViewerConfig` tokens from the format of the `version`
+//! they were saved with to the current `API_VERSION`.  Migrations operate on
+//! the JSON representation of a config, before it is deserialized into a
+//! `ViewerConfigUpdate`, as many legacy formats (e.g. `row_pivots`) are not
+//! representable in the typed struct at all.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use super::expressions::upgrade_legacy_format;
+use super::viewer_config::API_VERSION;
+use crate::utils::{ApiError, ApiResult};
+
+/// A `major.minor.patch` version, ignoring any pre-release or build suffix.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Semver {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Semver {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl Display for Semver {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(fmt, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl FromStr for Semver {
+    type Err = ApiError;
+
+    fn from_str(input: &str) -> ApiResult<Self> {
+        let version = input
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|x| x.parse::<u32>())
+            .collect::<Result<Vec<_>, _>>();
+
+        match version.as_deref() {
+            Ok([major, minor, patch]) => Ok(Self::new(*major, *minor, *patch)),
+            _ => Err(format!("Unknown version \"{}\"", input).into()),
+        }
+    }
+}
+
+type Migration = fn(&mut Map<String, Value>) -> ApiResult<()>;
+
+/// Every format change to `ViewerConfig`, in order.  Each migration is keyed
+/// by the first version which no longer needs it, and is applied to any config
+/// saved with an older `version`.  Configs without a `version` field are either
+/// partial updates in the current format, or predate versioning entirely, in
+/// which case they are treated as `0.0.0` if they are in a legacy format (see
+/// `is_legacy_format()`).
+const MIGRATIONS: &[(Semver, Migration)] = &[
+    (Semver::new(1, 2, 0), migrate_1_2_0),
+    (Semver::new(1, 6, 0), migrate_1_6_0),
+    (Semver::new(2, 7, 0), migrate_2_7_0),
+];
+
+const GROUP_BY_ALIASES: &[&str] = &["row_pivots", "row-pivot", "row-pivots", "row_pivot"];
+
+const SPLIT_BY_ALIASES: &[&str] = &[
+    "column_pivots",
+    "column-pivot",
+    "column-pivots",
+    "column_pivot",
+    "col_pivots",
+    "col-pivot",
+    "col-pivots",
+    "col_pivot",
+];
+
+/// Fields removed without replacement.
+const DEPRECATED_FIELDS: &[&str] = &["editable", "selectable", "table", "master", "linked"];
+
+/// Fields which were renamed, other than the pivots.
+const RENAMED_FIELDS: &[&str] = &["filters", "computed-columns", "name"];
+
+const PLUGIN_ALIASES: &[(&str, &str)] = &[
+    ("datagrid", "Datagrid"),
+    ("d3_y_area", "Y Area"),
+    ("d3_y_line", "Y Line"),
+    ("d3_xy_line", "X/Y Line"),
+    ("d3_y_scatter", "Y Scatter"),
+    ("d3_xy_scatter", "X/Y Scatter"),
+    ("d3_x_bar", "X Bar"),
+    ("d3_y_bar", "Y Bar"),
+    ("d3_heatmap", "Heatmap"),
+    ("d3_treemap", "Treemap"),
+    ("d3_sunburst", "Sunburst"),
+];
+
+/// Aggregate spellings accepted by the engine for legacy configs, but which
+/// are not valid `Aggregate` names.
+const AGGREGATE_ALIASES: &[(&str, &str)] = &[
+    ("distinctcount", "distinct count"),
+    ("distinct", "distinct count"),
+    ("distinct_count", "distinct count"),
+    ("weighted_mean", "weighted mean"),
+    ("first by index", "first"),
+    ("last_value", "last"),
+    ("high_water_mark", "high"),
+    ("low_water_mark", "low"),
+    ("sum_abs", "sum abs"),
+    ("abs_sum", "abs sum"),
+    ("sum_not_null", "sum not null"),
+    ("pct_sum_parent", "pct sum parent"),
+    ("pct_sum_grand_total", "pct sum grand total"),
+    ("variance", "var"),
+    ("standard deviation", "stddev"),
+];
+
+/// Whether an unversioned config has any field (or `plugin`, `aggregates`,
+/// `expressions` or `plugin_config` value) in a format which predates
+/// versioning.
+fn is_legacy_format(config: &Map<String, Value>) -> bool {
+    let has_legacy_field = GROUP_BY_ALIASES
+        .iter()
+        .chain(SPLIT_BY_ALIASES)
+        .chain(DEPRECATED_FIELDS)
+        .chain(RENAMED_FIELDS)
+        .any(|x| config.contains_key(*x));
+
+    let has_legacy_plugin = config
+        .get("plugin")
+        .and_then(|x| x.as_str())
+        .is_some_and(|x| PLUGIN_ALIASES.iter().any(|(alias, _)| *alias == x));
+
+    let has_legacy_aggregate = config
+        .get("aggregates")
+        .and_then(|x| x.as_object())
+        .into_iter()
+        .flat_map(|x| x.values())
+        .filter_map(|x| match x {
+            Value::Array(multi) => multi.first()?.as_str(),
+            x => x.as_str(),
+        })
+        .any(|x| AGGREGATE_ALIASES.iter().any(|(alias, _)| *alias == x));
+
+    let has_legacy_plugin_config = config
+        .get("plugin_config")
+        .and_then(|x| x.as_object())
+        .is_some_and(is_legacy_plugin_config);
+
+    has_legacy_field
+        || has_legacy_plugin
+        || has_legacy_aggregate
+        || has_legacy_plugin_config
+        || config.get("expressions").is_some_and(|x| x.is_array())
+}
+
+/// Pre-1.6 `Datagrid` configs are a map of column name to style, which we can
+/// distinguish from a modern (partial) `plugin_config` since every value is a
+/// style object.
+fn is_legacy_plugin_config(plugin_config: &Map<String, Value>) -> bool {
+    !plugin_config.contains_key("columns")
+        && !plugin_config.is_empty()
+        && plugin_config.values().all(|x| x.is_object())
+}
+
+/// Migrate a JSON-encoded `ViewerConfig` (or partial `ViewerConfigUpdate`) to
+/// the current `API_VERSION`.  Values which are not objects, and unversioned
+/// configs in the current format, are returned unmodified.
+pub fn migrate_viewer_config(mut config: Value) -> ApiResult<Value> {
+    let Some(map) = config.as_object_mut() else {
+        return Ok(config);
+    };
+
+    let version = match map.get("version") {
+        Some(Value::String(version)) => Semver::from_str(version)?,
+        None | Some(Value::Null) if is_legacy_format(map) => Semver::default(),
+        None | Some(Value::Null) => return Ok(config),
+        Some(x) => return Err(format!("Unknown version {}", x).into()),
+    };
+
+    let api_version = Semver::from_str(&API_VERSION)?;
+    if version > api_version {
+        tracing::warn!(
+            "Config version {} is newer than {}, skipping migration",
+            version,
+            api_version
+        );
+
+        return Ok(config);
+    }
+
+    for (target, migration) in MIGRATIONS.iter() {
+        if version < *target {
+            tracing::debug!("Migrating {} -> {}", version, target);
+            migration(map)?;
+        }
+    }
+
+    map.insert("version".to_owned(), Value::from(*API_VERSION));
+    Ok(config)
+}
+
+/// `1.2.0` renamed `row_pivots` and `column_pivots`, and replaced the legacy
+/// `computed-columns` with ExprTK `expressions`.
+fn migrate_1_2_0(config: &mut Map<String, Value>) -> ApiResult<()> {
+    migrate_field_aliases(config, "group_by", GROUP_BY_ALIASES)?;
+    migrate_field_aliases(config, "split_by", SPLIT_BY_ALIASES)?;
+
+    migrate_field_aliases(config, "filter", &["filters"])?;
+    migrate_computed_columns(config)?;
+    migrate_plugin_aliases(config);
+    migrate_aggregate_aliases(config);
+    migrate_name_title(config);
+    for attr in DEPRECATED_FIELDS {
+        if config.remove(*attr).is_some() {
+            tracing::warn!("Deprecated attribute \"{}\" removed", attr);
+        }
+    }
+
+    Ok(())
+}
+
+/// `1.6.0` moved `Datagrid` column styles under `plugin_config.columns`, and
+/// split number styles into separate foreground and background modes.
+fn migrate_1_6_0(config: &mut Map<String, Value>) -> ApiResult<()> {
+    if config.get("plugin").and_then(|x| x.as_str()) != Some("Datagrid") {
+        return Ok(());
+    }
+
+    let Some(Value::Object(plugin_config)) = config.get_mut("plugin_config") else {
+        return Ok(());
+    };
+
+    if is_legacy_plugin_config(plugin_config) {
+        tracing::warn!("Deprecated `plugin_config` moved to `plugin_config.columns`");
+        let mut columns = std::mem::take(plugin_config);
+        for column in columns.values_mut().filter_map(|x| x.as_object_mut()) {
+            if let Some(mode) = column.remove("color_mode") {
+                rename_number_color_mode(column, "color_mode", &mode);
+            }
+        }
+
+        plugin_config.insert("columns".to_owned(), Value::Object(columns));
+    }
+
+    let Some(Value::Object(columns)) = plugin_config.get_mut("columns") else {
+        return Ok(());
+    };
+
+    for column in columns.values_mut().filter_map(|x| x.as_object_mut()) {
+        if let Some(mode) = column.remove("number_color_mode") {
+            rename_number_color_mode(column, "number_color_mode", &mode);
+        }
+
+        if let Some(gradient) = column.remove("gradient") {
+            tracing::warn!("Deprecated attribute \"gradient\" renamed");
+            if column.get("number_bg_mode").and_then(|x| x.as_str()) == Some("gradient") {
+                column.insert("bg_gradient".to_owned(), gradient);
+            } else if column.get("number_fg_mode").and_then(|x| x.as_str()) == Some("bar") {
+                column.insert("fg_gradient".to_owned(), gradient);
+            }
+        }
+
+        for (old, bg, fg) in [
+            ("pos_color", "pos_bg_color", "pos_fg_color"),
+            ("neg_color", "neg_bg_color", "neg_fg_color"),
+        ] {
+            if let Some(color) = column.remove(old) {
+                tracing::warn!("Deprecated attribute \"{}\" renamed", old);
+                if column.contains_key("number_bg_mode") {
+                    column.insert(bg.to_owned(), color);
+                } else if column.contains_key("number_fg_mode") {
+                    column.insert(fg.to_owned(), color);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `2.7.0` serialized `expressions` as a map of name to expression, and
+/// `X/Y Scatter` symbols as a map of value to symbol.
+fn migrate_2_7_0(config: &mut Map<String, Value>) -> ApiResult<()> {
+    if let Some(Value::Array(expressions)) = config.get("expressions") {
+        let expressions = expressions
+            .iter()
+            .filter_map(|x| x.as_str().map(|x| x.to_owned()))
+            .collect::<Vec<_>>();
+
+        let expressions = upgrade_legacy_format(&expressions)
+            .into_iter()
+            .map(|(k, v)| (k, Value::from(v)))
+            .collect();
+
+        config.insert("expressions".to_owned(), Value::Object(expressions));
+    }
+
+    if config.get("plugin").and_then(|x| x.as_str()) != Some("X/Y Scatter") {
+        return Ok(());
+    }
+
+    let columns = config
+        .get_mut("plugin_config")
+        .and_then(|x| x.get_mut("columns"))
+        .and_then(|x| x.as_object_mut());
+
+    for column in columns.into_iter().flat_map(|x| x.values_mut()) {
+        if let Some(Value::Array(symbols)) = column.get("symbols") {
+            tracing::warn!("Deprecated `X/Y Scatter` symbols array replaced with object");
+            let symbols = symbols
+                .iter()
+                .filter_map(|x| Some((x.get("key")?.as_str()?.to_owned(), x.get("value")?.clone())))
+                .collect();
+
+            column["symbols"] = Value::Object(symbols);
+        }
+    }
+
+    Ok(())
+}
+
+/// Move the first of `aliases` present in `config` to `name`.
+fn migrate_field_aliases(
+    config: &mut Map<String, Value>,
+    name: &str,
+    aliases: &[&str],
+) -> ApiResult<()> {
+    for alias in aliases {
+        if let Some(value) = config.remove(*alias) {
+            if config.contains_key(name) {
+                return Err(format!("Duplicate \"{}\" fields", name).into());
+            }
+
+            tracing::warn!("Deprecated attribute \"{}\" renamed \"{}\"", alias, name);
+            config.insert(name.to_owned(), value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace the `computed-columns` bucket functions with their ExprTK
+/// equivalents, as well as any references to them in the pivots, filters and
+/// sort.
+fn migrate_computed_columns(config: &mut Map<String, Value>) -> ApiResult<()> {
+    let Some(computed) = config.remove("computed-columns") else {
+        return Ok(());
+    };
+
+    if config.contains_key("expressions") {
+        return Err("Duplicate \"expressions\" and \"computed-columns\" fields".into());
+    }
+
+    tracing::warn!("Deprecated attribute \"computed-columns\" renamed \"expressions\"");
+    let mut expressions = vec![];
+    for expr in computed.as_array().into_iter().flatten() {
+        let Some(expr) = expr.as_str() else {
+            continue;
+        };
+
+        let Some(replaced) = migrate_bucket_expression(expr) else {
+            expressions.push(Value::from(expr));
+            continue;
+        };
+
+        tracing::warn!(
+            "Deprecated expression \"{}\" updated to \"{}\"",
+            expr,
+            replaced
+        );
+        let legacy_name = Value::from(expr.replace('"', ""));
+        for field in ["group_by", "split_by"] {
+            for pivot in config
+                .get_mut(field)
+                .and_then(|x| x.as_array_mut())
+                .into_iter()
+                .flatten()
+            {
+                if *pivot == legacy_name {
+                    *pivot = Value::from(replaced.as_str());
+                }
+            }
+        }
+
+        for field in ["filter", "sort"] {
+            for term in config
+                .get_mut(field)
+                .and_then(|x| x.as_array_mut())
+                .into_iter()
+                .flatten()
+            {
+                if let Some(name) = term.get_mut(0)
+                    && *name == legacy_name
+                {
+                    *name = Value::from(replaced.as_str());
+                }
+            }
+        }
+
+        expressions.push(Value::from(replaced));
+    }
+
+    config.insert("expressions".to_owned(), Value::Array(expressions));
+    Ok(())
+}
+
+/// Rewrite a legacy `month_bucket("col")` style expression as
+/// `bucket("col", 'M')`, or `None` if `expr` is not a legacy bucket.
+fn migrate_bucket_expression(expr: &str) -> Option<String> {
+    const BUCKETS: &[(&str, &str)] = &[
+        ("year_bucket(\"", "y"),
+        ("month_bucket(\"", "M"),
+        ("day_bucket(\"", "d"),
+        ("hour_bucket(\"", "h"),
+        ("minute_bucket(\"", "m"),
+        ("second_bucket(\"", "s"),
+    ];
+
+    BUCKETS.iter().find_map(|(prefix, unit)| {
+        let rest = expr.strip_prefix(prefix)?;
+        let first = rest.chars().next()?.len_utf8();
+        let end = rest[first..].find("\")")? + first;
+        Some(format!(
+            "bucket(\"{}\", '{}'){}",
+            &rest[..end],
+            unit,
+            &rest[end + 2..]
+        ))
+    })
+}
+
+fn migrate_plugin_aliases(config: &mut Map<String, Value>) {
+    if let Some(Value::String(plugin)) = config.get_mut("plugin")
+        && let Some((_, name)) = PLUGIN_ALIASES.iter().find(|(alias, _)| alias == plugin)
+    {
+        tracing::warn!("Deprecated plugin \"{}\" renamed \"{}\"", plugin, name);
+        *plugin = (*name).to_owned();
+    }
+}
+
+fn migrate_aggregate_aliases(config: &mut Map<String, Value>) {
+    let aggregates = config.get_mut("aggregates").and_then(|x| x.as_object_mut());
+
+    for agg in aggregates.into_iter().flat_map(|x| x.values_mut()) {
+        let name = match agg {
+            Value::Array(multi) => multi.get_mut(0),
+            x => Some(x),
+        };
+
+        if let Some(Value::String(name)) = name
+            && let Some((_, new_name)) = AGGREGATE_ALIASES.iter().find(|(alias, _)| alias == name)
+        {
+            tracing::warn!("Deprecated aggregate \"{}\" renamed \"{}\"", name, new_name);
+            *name = (*new_name).to_owned();
+        }
+    }
+}
+
+/// The workspace `name` attribute was unified with `title`.
+fn migrate_name_title(config: &mut Map<String, Value>) {
+    if let Some(name) = config.remove("name") {
+        tracing::warn!("Deprecated attribute \"name\" renamed \"title\"");
+        if !config.get("title").is_some_and(|x| !x.is_null()) {
+            config.insert("title".to_owned(), name);
+        }
+    }
+}
+
+fn rename_number_color_mode(column: &mut Map<String, Value>, attr: &str, mode: &Value) {
+    tracing::warn!("Deprecated attribute \"{}\" renamed", attr);
+    let (key, value) = match mode.as_str() {
+        Some("foreground") => ("number_fg_mode", "color"),
+        Some("bar") => ("number_fg_mode", "bar"),
+        Some("background") => ("number_bg_mode", "color"),
+        Some("gradient") => ("number_bg_mode", "gradient"),
+        _ => {
+            tracing::warn!("Unknown {} {}", attr, mode);
+            return;
+        },
+    };
+
+    column.insert(key.to_owned(), Value::from(value));
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_semver_parse() {
+        assert_eq!(Semver::from_str("2.8.1").unwrap(), Semver::new(2, 8, 1));
+        assert_eq!(
+            Semver::from_str("2.10.0-rc.1").unwrap(),
+            Semver::new(2, 10, 0)
+        );
+
+        assert!(Semver::new(2, 10, 0) > Semver::new(2, 9, 3));
+        assert!(Semver::new(1, 2, 0) > Semver::default());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_migrate_0_x_computed_columns() {
+        let config = json!({
+            "columns": ["Sales"],
+            "plugin": "d3_y_area",
+            "computed-columns": ["month_bucket(\"Order Date\")"],
+            "row-pivots": ["month_bucket(Order Date)"],
+            "column-pivots": ["Ship Mode"],
+            "filters": [["Category", "==", "Office Supplies"]],
+            "selectable": null,
+            "editable": null,
+            "aggregates": {"Sales": "distinctcount"},
+            "plugin_config": {}
+        });
+
+        assert_eq!(
+            migrate_viewer_config(config).unwrap(),
+            json!({
+                "version": *API_VERSION,
+                "columns": ["Sales"],
+                "plugin": "Y Area",
+                "expressions": {
+                    "bucket(\"Order Date\", 'M')": "bucket(\"Order Date\", 'M')"
+                },
+                "group_by": ["bucket(\"Order Date\", 'M')"],
+                "split_by": ["Ship Mode"],
+                "filter": [["Category", "==", "Office Supplies"]],
+                "aggregates": {"Sales": "distinct count"},
+                "plugin_config": {}
+            })
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_migrate_bucket_expression() {
+        assert_eq!(
+            migrate_bucket_expression("day_bucket(\"ÿcol\")").as_deref(),
+            Some("bucket(\"ÿcol\", 'd')")
+        );
+
+        assert_eq!(migrate_bucket_expression("day_bucket(\""), None);
+        assert_eq!(migrate_bucket_expression("\"ÿcol\""), None);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_migrate_1_x_pivots() {
+        let config = json!({
+            "plugin": "Datagrid",
+            "row_pivots": ["State"],
+            "column_pivots": ["Region"],
+            "columns": ["Sales", "Profit"],
+            "aggregates": {"Sales": ["weighted_mean", "Profit"]},
+            "name": "My Grid"
+        });
+
+        assert_eq!(
+            migrate_viewer_config(config).unwrap(),
+            json!({
+                "version": *API_VERSION,
+                "plugin": "Datagrid",
+                "group_by": ["State"],
+                "split_by": ["Region"],
+                "columns": ["Sales", "Profit"],
+                "aggregates": {"Sales": ["weighted mean", "Profit"]},
+                "title": "My Grid"
+            })
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_migrate_1_x_datagrid_plugin_config() {
+        let config = json!({
+            "plugin": "datagrid",
+            "plugin_config": {
+                "Sales": {"color_mode": "gradient", "gradient": 10},
+                "Profit": {"number_color_mode": "bar", "pos_color": "#115599"}
+            }
+        });
+
+        assert_eq!(
+            migrate_viewer_config(config).unwrap(),
+            json!({
+                "version": *API_VERSION,
+                "plugin": "Datagrid",
+                "plugin_config": {
+                    "columns": {
+                        "Sales": {"number_bg_mode": "gradient", "bg_gradient": 10},
+                        "Profit": {"number_fg_mode": "bar", "pos_fg_color": "#115599"}
+                    }
+                }
+            })
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_migrate_2_x_expressions_and_symbols() {
+        let config = json!({
+            "version": "2.6.0",
+            "plugin": "X/Y Scatter",
+            "plugin_config": {
+                "columns": {
+                    "Region": {
+                        "symbols": [
+                            {"key": "East", "value": "circle"},
+                            {"key": "West", "value": "square"}
+                        ]
+                    }
+                }
+            },
+            "expressions": ["// Profit Ratio\n\"Profit\" / \"Sales\"", "\"Sales\" + 1"]
+        });
+
+        assert_eq!(
+            migrate_viewer_config(config).unwrap(),
+            json!({
+                "version": *API_VERSION,
+                "plugin": "X/Y Scatter",
+                "plugin_config": {
+                    "columns": {
+                        "Region": {
+                            "symbols": {"East": "circle", "West": "square"}
+                        }
+                    }
+                },
+                "expressions": {
+                    "Profit Ratio": "\"Profit\" / \"Sales\"",
+                    "\"Sales\" + 1": "\"Sales\" + 1"
+                }
+            })
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_migrate_current_version_is_unchanged() {
+        let config = json!({
+            "version": *API_VERSION,
+            "plugin": "Datagrid",
+            "plugin_config": {
+                "columns": {"Sales": {"number_bg_mode": "gradient", "bg_gradient": 10}},
+                "editable": false,
+                "scroll_lock": true
+            },
+            "group_by": ["State"],
+            "expressions": {"Profit Ratio": "\"Profit\" / \"Sales\""},
+            "aggregates": {"Sales": "sum"}
+        });
+
+        assert_eq!(migrate_viewer_config(config.clone()).unwrap(), config);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_migrate_partial_update_is_unchanged() {
+        let config = json!({
+            "plugin": "Datagrid",
+            "plugin_config": {"editable": true}
+        });
+
+        assert_eq!(migrate_viewer_config(config.clone()).unwrap(), config);
+
+        // Nor is an unversioned update in the current format which touches
+        // fields that migrations rewrite.
+        let config = json!({
+            "plugin": "Datagrid",
+            "plugin_config": {"columns": {"Sales": {"number_fg_mode": "bar"}}},
+            "group_by": ["State"],
+            "expressions": {"Profit Ratio": "\"Profit\" / \"Sales\""},
+            "aggregates": {"Sales": ["weighted mean", "Profit"]}
+        });
+
+        assert_eq!(migrate_viewer_config(config.clone()).unwrap(), config);
+    }
+}
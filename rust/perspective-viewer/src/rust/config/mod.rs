@@ -16,22 +16,34 @@
 mod aggregates;
 mod column_type;
 mod datetime_column_style;
+mod expansion;
 mod expressions;
 mod filters;
+mod link_group;
+mod migrate;
 mod number_column_style;
 pub mod plugin;
+mod query;
+mod schema;
 mod sort;
+mod sql;
 mod string_column_style;
+mod top_n;
 mod view_config;
 mod viewer_config;
 
 pub use aggregates::*;
 pub use column_type::*;
 pub use datetime_column_style::*;
+pub use expansion::*;
 pub use expressions::*;
 pub use filters::*;
+pub use link_group::*;
+pub use migrate::*;
 pub use number_column_style::*;
+pub use schema::*;
 pub use sort::*;
 pub use string_column_style::*;
+pub use top_n::*;
 pub use view_config::*;
 pub use viewer_config::*;
@@ -13,11 +13,12 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::*;
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 pub enum NumberForegroundMode {
     #[serde(rename = "disabled")]
     Disabled,
@@ -71,7 +72,7 @@ impl NumberForegroundMode {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 pub enum NumberBackgroundMode {
     #[default]
     #[serde(rename = "disabled")]
@@ -123,7 +124,7 @@ impl NumberBackgroundMode {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, JsonSchema)]
 pub struct NumberColumnStyleConfig {
     #[serde(default = "NumberForegroundMode::default")]
     #[serde(skip_serializing_if = "NumberForegroundMode::is_color")]
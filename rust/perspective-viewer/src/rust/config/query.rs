@@ -0,0 +1,669 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A compact, human-writable text syntax for `ViewConfig`, e.g.
+//!
+//! ```text
+//! group_by region, sector | split_by side | filter price > 10 | sort volume desc | agg price: avg
+//! ```
+//!
+//! A query is a `|`-separated list of clauses:
+//!
+//! - `columns a, b, null`
+//! - `group_by a, b` and `split_by a, b`
+//! - `filter <condition>`, where conditions combine with `and`/`or` and may be
+//!   parenthesized, e.g. `filter (x > 1 or y < 2) and z in ['a', 'b']`.
+//! - `sort a desc, b` (the direction defaults to `asc`)
+//! - `top 10 a desc`, optionally limiting a deeper `group_by` level, e.g. `top
+//!   10 a asc level 1` (the direction defaults to `desc`).
+//! - `agg a: avg, b: weighted mean by c`, where a column repeated in `columns`
+//!   lists one aggregate per occurrence, e.g. `agg a: avg, a: max` (`default`
+//!   for the column type's default).
+//! - `expr name: '<ExprTK expression>'`
+//!
+//! Column names which are not plain identifiers are written in double quotes,
+//! and string literals in single quotes, as in ExprTK.
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use nom::branch::alt;
+use nom::bytes::complete::{escaped_transform, is_not, tag};
+use nom::character::complete::{
+    alpha1, alphanumeric1, char, digit1, multispace0, satisfy, u32 as parse_u32,
+};
+use nom::combinator::{all_consuming, map, map_res, not, opt, recognize, value};
+use nom::error::{Error, ErrorKind};
+use nom::multi::{many0, separated_list0, separated_list1};
+use nom::number::complete::recognize_float;
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
+use nom::IResult;
+
+use super::aggregates::*;
+use super::column_type::Type;
+use super::expressions::*;
+use super::filters::*;
+use super::sort::*;
+use super::top_n::*;
+use super::view_config::*;
+use super::viewer_config::OptionalUpdate;
+use crate::utils::*;
+
+const FILTER_OPS: &[FilterOp] = &[
+    FilterOp::EQIgnoreCase,
+    FilterOp::ContainsIgnoreCase,
+    FilterOp::BeginsWithIgnoreCase,
+    FilterOp::EndsWithIgnoreCase,
+    FilterOp::IsNotNull,
+    FilterOp::IsNull,
+    FilterOp::NotIn,
+    FilterOp::In,
+    FilterOp::Contains,
+    FilterOp::BeginsWith,
+    FilterOp::EndsWith,
+    FilterOp::Between,
+    FilterOp::Matches,
+    FilterOp::GTE,
+    FilterOp::LTE,
+    FilterOp::NE,
+    FilterOp::EQ,
+    FilterOp::GT,
+    FilterOp::LT,
+];
+
+const SORT_DIRS: &[SortDir] = &[
+    SortDir::ColDescAbs,
+    SortDir::ColAscAbs,
+    SortDir::ColDesc,
+    SortDir::ColAsc,
+    SortDir::DescAbs,
+    SortDir::AscAbs,
+    SortDir::Desc,
+    SortDir::Asc,
+    SortDir::None,
+];
+
+impl ViewConfig {
+    /// Print this config in the text query syntax, omitting clauses which are
+    /// set to their defaults.
+    pub fn to_query(&self) -> String {
+        let mut clauses = vec![];
+        if !self.columns.is_empty() {
+            let columns = self.columns.iter().map(|x| match x {
+                Some(x) => print_ident(x),
+                None => "null".to_owned(),
+            });
+
+            clauses.push(format!("columns {}", columns.format(", ")));
+        }
+
+        if !self.group_by.is_empty() {
+            let group_by = self.group_by.iter().map(|x| print_ident(x));
+            clauses.push(format!("group_by {}", group_by.format(", ")));
+        }
+
+        if !self.split_by.is_empty() {
+            let split_by = self.split_by.iter().map(|x| print_ident(x));
+            clauses.push(format!("split_by {}", split_by.format(", ")));
+        }
+
+        if !self.filter.is_empty() {
+            let filter = self.filter.iter().map(print_filter_item);
+            clauses.push(format!("filter {}", filter.format(" and ")));
+        }
+
+        if !self.sort.is_empty() {
+            let sort = self
+                .sort
+                .iter()
+                .map(|Sort(name, dir)| format!("{} {}", print_ident(name), dir));
+
+            clauses.push(format!("sort {}", sort.format(", ")));
+        }
+
+        if let Some(TopN {
+            column,
+            dir,
+            n,
+            level,
+        }) = &self.top_n
+        {
+            let mut clause = format!("top {} {} {}", n, print_ident(column), dir);
+            if *level > 0 {
+                clause.push_str(&format!(" level {}", level));
+            }
+
+            clauses.push(clause);
+        }
+
+        if !self.aggregates.is_empty() {
+            let aggregates = self
+                .aggregates
+                .iter()
+                .sorted_by(|x, y| x.0.cmp(y.0))
+                .flat_map(|(name, aggs)| {
+                    aggs.iter().map(move |agg| {
+                        let agg = agg.map_or_else(|| "default".to_owned(), print_aggregate);
+                        format!("{}: {}", print_ident(name), agg)
+                    })
+                });
+
+            clauses.push(format!("agg {}", aggregates.format(", ")));
+        }
+
+        for (name, expr) in self.expressions.iter().sorted_by(|x, y| x.0.cmp(y.0)) {
+            clauses.push(format!(
+                "expr {}: {}",
+                print_ident(name),
+                print_quoted(expr, '\'')
+            ));
+        }
+
+        clauses.join(" | ")
+    }
+}
+
+impl ViewConfigUpdate {
+    /// Parse a query in the text query syntax.  Clauses omitted from `query`
+    /// are reset to their defaults, except for `columns` which is left
+    /// unchanged.
+    pub fn from_query(query: &str) -> ApiResult<Self> {
+        let clauses = match all_consuming(parse_query)(query) {
+            Ok((_, clauses)) => clauses,
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                let position = query.len() - err.input.len();
+                return Err(
+                    format!("Invalid query at position {}: `{}`", position, err.input).into(),
+                );
+            },
+            Err(nom::Err::Incomplete(_)) => return Err("Incomplete query".into()),
+        };
+
+        let mut update = Self {
+            group_by: Some(vec![]),
+            split_by: Some(vec![]),
+            columns: None,
+            filter: Some(vec![]),
+            sort: Some(vec![]),
+            expressions: Some(Expressions::default()),
+            aggregates: Some(HashMap::default()),
+            top_n: OptionalUpdate::SetDefault,
+        };
+
+        for clause in clauses {
+            match clause {
+                Clause::Columns(x) => update.columns.get_or_insert_with(Vec::new).extend(x),
+                Clause::GroupBy(x) => update.group_by.get_or_insert_with(Vec::new).extend(x),
+                Clause::SplitBy(x) => update.split_by.get_or_insert_with(Vec::new).extend(x),
+                Clause::Filter(x) => update.filter.get_or_insert_with(Vec::new).extend(x),
+                Clause::Sort(x) => update.sort.get_or_insert_with(Vec::new).extend(x),
+                Clause::TopN(x) => update.top_n = OptionalUpdate::Update(x),
+                Clause::Agg(x) => {
+                    let aggregates = update.aggregates.get_or_insert_with(HashMap::new);
+                    let mut counts = HashMap::<String, usize>::new();
+                    for (name, agg) in x {
+                        let occurrence = counts.entry(name.clone()).or_default();
+                        aggregates.entry(name).or_default().set(*occurrence, agg);
+                        *occurrence += 1;
+                    }
+                },
+                Clause::Expr(name, expr) => {
+                    update
+                        .expressions
+                        .get_or_insert_with(Expressions::default)
+                        .insert(&Expression::new(Some(name.into()), expr.into()));
+                },
+            }
+        }
+
+        Ok(update)
+    }
+}
+
+enum Clause {
+    Columns(Vec<Option<String>>),
+    GroupBy(Vec<String>),
+    SplitBy(Vec<String>),
+    Filter(Vec<FilterItem>),
+    Sort(Vec<Sort>),
+    TopN(TopN),
+    Agg(Vec<(String, Option<Aggregate>)>),
+    Expr(String, String),
+}
+
+fn print_ident(name: &str) -> String {
+    let is_plain = name
+        .chars()
+        .next()
+        .map(|x| x.is_ascii_alphabetic() || x == '_')
+        .unwrap_or_default()
+        && name.chars().all(|x| x.is_ascii_alphanumeric() || x == '_')
+        && name != "null";
+
+    if is_plain {
+        name.to_owned()
+    } else {
+        print_quoted(name, '"')
+    }
+}
+
+fn print_quoted(text: &str, quote: char) -> String {
+    let mut output = String::with_capacity(text.len() + 2);
+    output.push(quote);
+    for c in text.chars() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\t' => output.push_str("\\t"),
+            c if c == quote => {
+                output.push('\\');
+                output.push(c);
+            },
+            c => output.push(c),
+        }
+    }
+
+    output.push(quote);
+    output
+}
+
+fn print_scalar(scalar: &Scalar) -> String {
+    match scalar {
+        Scalar::String(x) => print_quoted(x, '\''),
+        Scalar::Null => "null".to_owned(),
+        x => format!("{}", x),
+    }
+}
+
+fn print_filter_item(item: &FilterItem) -> String {
+    match item {
+        FilterItem::Filter(Filter(name, op, term)) => {
+            let name = print_ident(name);
+            match (op, term) {
+                (FilterOp::IsNull | FilterOp::IsNotNull, _) => format!("{} {}", name, op),
+                (_, FilterTerm::Scalar(x)) => format!("{} {} {}", name, op, print_scalar(x)),
+                (_, FilterTerm::Array(xs)) => {
+                    format!(
+                        "{} {} [{}]",
+                        name,
+                        op,
+                        xs.iter().map(print_scalar).format(", ")
+                    )
+                },
+                (_, FilterTerm::Relative(x)) => format!("{} {} {}", name, op, x),
+            }
+        },
+        FilterItem::Group(FilterGroup { op, filters }) => format!(
+            "({})",
+            filters
+                .iter()
+                .map(print_filter_item)
+                .format(&format!(" {} ", op))
+        ),
+    }
+}
+
+fn print_aggregate(agg: &Aggregate) -> String {
+    match agg {
+        Aggregate::SingleAggregate(x) => format!("{}", x),
+        Aggregate::MultiAggregate(x, param) => format!("{}{}", x.prefix(), print_ident(param)),
+    }
+}
+
+fn ws<'a, T>(
+    parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, T> {
+    delimited(multispace0, parser, multispace0)
+}
+
+/// Match `word` only at a word boundary, so e.g. `in` does not match `index`.
+fn keyword<'a>(word: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    terminated(
+        tag(word),
+        not(satisfy(|c: char| c.is_alphanumeric() || c == '_')),
+    )
+}
+
+fn parse_quoted<'a>(quote: char) -> impl FnMut(&'a str) -> IResult<&'a str, String> {
+    let (normal, escaped_quote) = if quote == '"' {
+        ("\"\\", "\"")
+    } else {
+        ("'\\", "'")
+    };
+
+    move |input| {
+        let escapes = alt((
+            value("\\", char('\\')),
+            value("\n", char('n')),
+            value("\t", char('t')),
+            value(escaped_quote, char(quote)),
+        ));
+
+        let contents = escaped_transform(is_not(normal), '\\', escapes);
+        let mut quoted = delimited(
+            char(quote),
+            map(opt(contents), Option::unwrap_or_default),
+            char(quote),
+        );
+
+        quoted(input)
+    }
+}
+
+fn parse_ident(input: &str) -> IResult<&str, String> {
+    let bare = recognize(pair(
+        alt((alpha1, tag("_"))),
+        many0(alt((alphanumeric1, tag("_")))),
+    ));
+
+    alt((parse_quoted('"'), map(bare, str::to_owned)))(input)
+}
+
+fn parse_ident_list(input: &str) -> IResult<&str, Vec<String>> {
+    separated_list0(ws(char(',')), ws(parse_ident))(input)
+}
+
+fn parse_scalar(input: &str) -> IResult<&str, Scalar> {
+    let date = map(
+        recognize(tuple((digit1, char('-'), digit1, char('-'), digit1))),
+        |x: &str| Scalar::String(x.to_owned()).coerce(Type::Date),
+    );
+
+    // Integers beyond the range `f64` can represent exactly stay `Int`, as
+    // when deserializing JSON.
+    let number = map_res(recognize_float, |x: &str| match x.parse::<i64>() {
+        Ok(x) if x.unsigned_abs() > 1 << 53 => Ok(Scalar::Int(x)),
+        _ => parse_finite(x)
+            .map(Scalar::Float)
+            .ok_or("Number out of range"),
+    });
+
+    alt((
+        map(parse_quoted('\''), Scalar::String),
+        value(Scalar::Bool(true), keyword("true")),
+        value(Scalar::Bool(false), keyword("false")),
+        value(Scalar::Null, keyword("null")),
+        date,
+        number,
+    ))(input)
+}
+
+fn parse_relative_date(input: &str) -> IResult<&str, RelativeDate> {
+    let unit = || map_res(alpha1, DateUnit::from_str);
+    alt((
+        value(RelativeDate::Today, keyword("today")),
+        map(preceded(keyword("this"), ws(unit())), |unit| {
+            RelativeDate::This { unit }
+        }),
+        map(
+            preceded(keyword("last"), pair(opt(ws(parse_u32)), ws(unit()))),
+            |(count, unit)| RelativeDate::Last {
+                count: count.unwrap_or(1),
+                unit,
+            },
+        ),
+    ))(input)
+}
+
+fn parse_filter_op(input: &str) -> IResult<&str, FilterOp> {
+    for op in FILTER_OPS {
+        let name = op.to_string();
+        if let Some(rest) = input.strip_prefix(name.as_str()) {
+            let is_word = name.ends_with(|c: char| c.is_alphabetic());
+            if !is_word || !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+                return Ok((rest, *op));
+            }
+        }
+    }
+
+    Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)))
+}
+
+fn parse_filter(input: &str) -> IResult<&str, Filter> {
+    let (input, name) = ws(parse_ident)(input)?;
+    let (input, op) = ws(parse_filter_op)(input)?;
+    let array = || {
+        map(
+            delimited(
+                char('['),
+                separated_list0(ws(char(',')), ws(parse_scalar)),
+                char(']'),
+            ),
+            FilterTerm::Array,
+        )
+    };
+
+    let (input, term) = match op {
+        FilterOp::IsNull | FilterOp::IsNotNull => (input, FilterTerm::Scalar(Scalar::Null)),
+        FilterOp::In | FilterOp::NotIn => ws(array())(input)?,
+        _ => ws(alt((
+            map(parse_relative_date, FilterTerm::Relative),
+            array(),
+            map(parse_scalar, FilterTerm::Scalar),
+        )))(input)?,
+    };
+
+    Ok((input, Filter(name, op, term)))
+}
+
+fn filter_group(op: FilterReducer, mut filters: Vec<FilterItem>) -> FilterItem {
+    if filters.len() == 1 {
+        filters.pop().unwrap()
+    } else {
+        FilterItem::Group(FilterGroup { op, filters })
+    }
+}
+
+fn parse_filter_or(input: &str) -> IResult<&str, FilterItem> {
+    map(
+        separated_list1(ws(keyword("or")), parse_filter_and),
+        |filters| filter_group(FilterReducer::Or, filters),
+    )(input)
+}
+
+fn parse_filter_and(input: &str) -> IResult<&str, FilterItem> {
+    let atom = alt((
+        delimited(ws(char('(')), parse_filter_or, ws(char(')'))),
+        map(parse_filter, FilterItem::Filter),
+    ));
+
+    map(separated_list1(ws(keyword("and")), atom), |filters| {
+        filter_group(FilterReducer::And, filters)
+    })(input)
+}
+
+fn parse_sort(input: &str) -> IResult<&str, Sort> {
+    let (input, name) = ws(parse_ident)(input)?;
+    let dir = SORT_DIRS.iter().find_map(|dir| {
+        let rest = input.strip_prefix(dir.to_string().as_str())?;
+        (!rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')).then_some((rest, *dir))
+    });
+
+    match dir {
+        Some((input, dir)) => Ok((input, Sort(name, dir))),
+        None => Ok((input, Sort(name, SortDir::Asc))),
+    }
+}
+
+fn parse_top_n(input: &str) -> IResult<&str, TopN> {
+    let n = map_res(ws(parse_u32), |x| NonZeroUsize::try_from(x as usize));
+    let dir = alt((
+        value(SortDir::Desc, keyword("desc")),
+        value(SortDir::Asc, keyword("asc")),
+    ));
+
+    let level = preceded(ws(keyword("level")), ws(parse_u32));
+    map(
+        tuple((n, ws(parse_ident), opt(ws(dir)), opt(level))),
+        |(n, column, dir, level)| TopN {
+            column,
+            dir: dir.unwrap_or(SortDir::Desc),
+            n,
+            level: level.unwrap_or_default() as usize,
+        },
+    )(input)
+}
+
+fn parse_multi_aggregate(input: &str) -> IResult<&str, Aggregate> {
+    for x in MultiAggregate::ALL {
+        let Ok((input, _)) = keyword(x.prefix().trim_end())(input) else {
+            continue;
+        };
+
+        let (rest, param) = ws(parse_ident)(input)?;
+        return Ok((rest, Aggregate::MultiAggregate(*x, param)));
+    }
+
+    Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)))
+}
+
+fn parse_aggregate(input: &str) -> IResult<&str, Aggregate> {
+    let single = map_res(recognize(separated_list1(char(' '), alpha1)), |x| {
+        SingleAggregate::from_str(x).map(Aggregate::SingleAggregate)
+    });
+
+    alt((parse_multi_aggregate, single))(input)
+}
+
+fn parse_clause(input: &str) -> IResult<&str, Clause> {
+    let columns = separated_list0(
+        ws(char(',')),
+        ws(alt((value(None, keyword("null")), map(parse_ident, Some)))),
+    );
+
+    let filter = map(parse_filter_or, |item| match item {
+        FilterItem::Group(FilterGroup {
+            op: FilterReducer::And,
+            filters,
+        }) => filters,
+        item => vec![item],
+    });
+
+    let sort = separated_list0(ws(char(',')), parse_sort);
+    let agg = separated_list0(
+        ws(char(',')),
+        separated_pair(
+            ws(parse_ident),
+            char(':'),
+            ws(alt((
+                value(None, keyword("default")),
+                map(parse_aggregate, Some),
+            ))),
+        ),
+    );
+
+    let expr = separated_pair(ws(parse_ident), char(':'), ws(parse_quoted('\'')));
+    ws(alt((
+        map(preceded(keyword("columns"), columns), Clause::Columns),
+        map(
+            preceded(keyword("group_by"), parse_ident_list),
+            Clause::GroupBy,
+        ),
+        map(
+            preceded(keyword("split_by"), parse_ident_list),
+            Clause::SplitBy,
+        ),
+        map(preceded(keyword("filter"), filter), Clause::Filter),
+        map(preceded(keyword("sort"), sort), Clause::Sort),
+        map(preceded(keyword("top"), parse_top_n), Clause::TopN),
+        map(preceded(keyword("agg"), agg), Clause::Agg),
+        map(preceded(keyword("expr"), expr), |(name, expr)| {
+            Clause::Expr(name, expr)
+        }),
+    )))(input)
+}
+
+fn parse_query(input: &str) -> IResult<&str, Vec<Clause>> {
+    alt((
+        separated_list1(char('|'), parse_clause),
+        map(multispace0, |_| vec![]),
+    ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    fn parse(query: &str) -> ViewConfig {
+        let mut config = ViewConfig::default();
+        config.apply_update(ViewConfigUpdate::from_query(query).unwrap());
+        config
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_query_parse() {
+        let config = parse(
+            "group_by region, sector | split_by side | filter price > 10 | sort volume desc | agg \
+             price: avg",
+        );
+
+        assert_eq!(config.group_by, vec!["region", "sector"]);
+        assert_eq!(config.split_by, vec!["side"]);
+        assert_eq!(config.filter, vec![FilterItem::Filter(Filter(
+            "price".to_owned(),
+            FilterOp::GT,
+            FilterTerm::Scalar(Scalar::Float(10.0))
+        ))]);
+
+        assert_eq!(config.sort, vec![Sort("volume".to_owned(), SortDir::Desc)]);
+        assert_eq!(
+            config.aggregates.get("price").and_then(|x| x.get(0)),
+            Some(&Aggregate::SingleAggregate(SingleAggregate::Avg))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_query_rejects_non_finite_numbers() {
+        assert!(ViewConfigUpdate::from_query("filter price > 1e999").is_err());
+        assert!(ViewConfigUpdate::from_query("filter price > 1e99").is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_query_round_trip() {
+        let query = "columns \"Order Date\", null, Sales | group_by \"null\" | filter (x == \
+                     'it\\'s' or y in [1, 2.5]) and z is not null and d between last 7 days and e \
+                     contains (ignore case) 'a' | sort Sales col desc abs, Profit asc | top 5 \
+                     Profit asc level 1 | agg Profit: weighted mean by Sales, Sales: sum abs, \
+                     Sales: last by index | expr a: '\"Sales\" + 1\\n'";
+
+        let config = parse(query);
+        assert_eq!(config.columns, vec![
+            Some("Order Date".to_owned()),
+            None,
+            Some("Sales".to_owned())
+        ]);
+
+        assert_eq!(config.group_by, vec!["null"]);
+        assert_eq!(config.filter.len(), 4);
+        assert_eq!(config.top_n.as_ref().map(|x| x.level), Some(1));
+        assert_eq!(
+            config.expressions.get("a").map(|x| x.as_str()),
+            Some("\"Sales\" + 1\n")
+        );
+
+        assert_eq!(config.to_query(), query);
+        assert_eq!(parse(&config.to_query()), config);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_query_errors() {
+        assert!(ViewConfigUpdate::from_query("").is_ok());
+        assert!(ViewConfigUpdate::from_query("group_by a | frobnicate b").is_err());
+        assert!(ViewConfigUpdate::from_query("filter a >").is_err());
+        assert!(ViewConfigUpdate::from_query("agg a: bogus").is_err());
+        assert!(ViewConfigUpdate::from_query("top 0 a").is_err());
+    }
+}
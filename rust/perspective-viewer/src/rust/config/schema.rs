@@ -0,0 +1,263 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::BTreeMap;
+
+use schemars::schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec};
+use schemars::schema_for;
+use serde_json::Value;
+
+use super::*;
+use crate::utils::*;
+
+/// Generate JSON Schema for the persisted config types, keyed by type name.
+/// These are derived from the same `serde` definitions `save()` and
+/// `restore()` use, so they always describe the current format.
+pub fn get_config_schemas() -> BTreeMap<&'static str, RootSchema> {
+    BTreeMap::from([
+        ("ViewerConfig", schema_for!(ViewerConfig)),
+        ("ViewConfig", schema_for!(ViewConfig)),
+        ("Filter", schema_for!(Filter)),
+        ("Sort", schema_for!(Sort)),
+        ("Aggregate", schema_for!(Aggregate)),
+        (
+            "NumberColumnStyleConfig",
+            schema_for!(NumberColumnStyleConfig),
+        ),
+        (
+            "StringColumnStyleConfig",
+            schema_for!(StringColumnStyleConfig),
+        ),
+        (
+            "DatetimeColumnStyleConfig",
+            schema_for!(DatetimeColumnStyleConfig),
+        ),
+    ])
+}
+
+/// Check a JSON `value` against `schema` for object keys the schema does not
+/// allow, returning an error with the path to the first one found.  Only keys
+/// are checked;  type errors are left to `serde`, which reports them (without
+/// a path) when the value is deserialized.
+pub fn check_unknown_fields(value: &Value, schema: &RootSchema) -> ApiResult<()> {
+    UnknownFieldsChecker(&schema.definitions)
+        .check(value, &schema.schema, "")
+        .map(|_| ())
+        .map_err(ApiError::from)
+}
+
+struct UnknownFieldsChecker<'a>(&'a schemars::Map<String, Schema>);
+
+impl UnknownFieldsChecker<'_> {
+    /// `Ok(false)` if `value` is not of the schema's type at all, which
+    /// decides between the branches of an untagged `enum`.
+    fn check(&self, value: &Value, schema: &SchemaObject, path: &str) -> Result<bool, String> {
+        if let Some(name) = schema.reference.as_ref() {
+            return match self.0.get(name.trim_start_matches("#/definitions/")) {
+                Some(Schema::Object(schema)) => self.check(value, schema, path),
+                _ => Ok(true),
+            };
+        }
+
+        if !Self::is_instance(value, schema.instance_type.as_ref()) {
+            return Ok(false);
+        }
+
+        if let Some(subschemas) = schema.subschemas.as_ref() {
+            for schema in subschemas.all_of.iter().flatten() {
+                if !self.check_schema(value, schema, path)? {
+                    return Ok(false);
+                }
+            }
+
+            let branches = subschemas.any_of.iter().chain(subschemas.one_of.iter());
+            for schemas in branches {
+                let mut error = None;
+                let mut is_matched = schemas.is_empty();
+                for schema in schemas {
+                    match self.check_schema(value, schema, path) {
+                        Ok(true) => {
+                            is_matched = true;
+                            error = None;
+                            break;
+                        },
+                        Ok(false) => (),
+                        Err(err) => {
+                            is_matched = true;
+                            error.get_or_insert(err);
+                        },
+                    }
+                }
+
+                if let Some(err) = error {
+                    return Err(err);
+                } else if !is_matched {
+                    return Ok(false);
+                }
+            }
+        }
+
+        self.check_fields(value, Some(schema), path)
+    }
+
+    fn check_schema(&self, value: &Value, schema: &Schema, path: &str) -> Result<bool, String> {
+        schema_object(schema).map_or(Ok(true), |schema| self.check(value, schema, path))
+    }
+
+    fn check_fields(
+        &self,
+        value: &Value,
+        schema: Option<&SchemaObject>,
+        path: &str,
+    ) -> Result<bool, String> {
+        match (value, schema) {
+            (
+                Value::Object(map),
+                Some(SchemaObject {
+                    object: Some(object),
+                    ..
+                }),
+            ) => {
+                for (key, value) in map {
+                    let path = if path.is_empty() {
+                        key.to_owned()
+                    } else {
+                        format!("{}.{}", path, key)
+                    };
+
+                    match object
+                        .properties
+                        .get(key)
+                        .or(object.additional_properties.as_deref())
+                    {
+                        Some(Schema::Bool(false)) => {
+                            let suggestion = object.properties.keys().find(|x| {
+                                x.replace('_', "")
+                                    == key.replace(['-', '_', ' '], "").to_lowercase()
+                            });
+
+                            return Err(match suggestion {
+                                Some(x) => {
+                                    format!("Unknown field `{}`, did you mean `{}`?", path, x)
+                                },
+                                None => format!("Unknown field `{}`", path),
+                            });
+                        },
+                        Some(schema) => {
+                            self.check_schema(value, schema, &path)?;
+                        },
+                        None => (),
+                    }
+                }
+            },
+            (
+                Value::Array(values),
+                Some(SchemaObject {
+                    array: Some(array), ..
+                }),
+            ) => {
+                for (idx, value) in values.iter().enumerate() {
+                    let schema = match array.items.as_ref() {
+                        Some(SingleOrVec::Single(schema)) => Some(&**schema),
+                        Some(SingleOrVec::Vec(schemas)) => schemas.get(idx),
+                        None => None,
+                    };
+
+                    if let Some(schema) = schema {
+                        self.check_schema(value, schema, &format!("{}[{}]", path, idx))?;
+                    }
+                }
+            },
+            _ => (),
+        }
+
+        Ok(true)
+    }
+
+    fn is_instance(value: &Value, instance_type: Option<&SingleOrVec<InstanceType>>) -> bool {
+        let is_type = |ty: &InstanceType| match (ty, value) {
+            (InstanceType::Null, Value::Null)
+            | (InstanceType::Boolean, Value::Bool(_))
+            | (InstanceType::Number, Value::Number(_))
+            | (InstanceType::String, Value::String(_))
+            | (InstanceType::Array, Value::Array(_))
+            | (InstanceType::Object, Value::Object(_)) => true,
+            (InstanceType::Integer, Value::Number(x)) => x.is_i64() || x.is_u64(),
+            _ => false,
+        };
+
+        match instance_type {
+            None => true,
+            Some(SingleOrVec::Single(ty)) => is_type(ty),
+            Some(SingleOrVec::Vec(tys)) => tys.iter().any(is_type),
+        }
+    }
+}
+
+fn schema_object(schema: &Schema) -> Option<&SchemaObject> {
+    match schema {
+        Schema::Object(schema) => Some(schema),
+        Schema::Bool(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_view_config_schema() {
+        let schemas = serde_json::to_value(get_config_schemas()).unwrap();
+        let view_config = &schemas["ViewConfig"];
+        assert_eq!(view_config["additionalProperties"], json!(false));
+        assert_eq!(view_config["properties"]["filter"]["type"], json!("array"));
+        assert!(view_config["definitions"]["FilterOp"]["enum"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("between")));
+
+        let viewer_config = &schemas["ViewerConfig"];
+        assert!(viewer_config["required"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("plugin")));
+        assert!(viewer_config["properties"]["group_by"].is_object());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_check_unknown_fields() {
+        let schema = schema_for!(ViewerConfig);
+        let check = |value: Value| {
+            UnknownFieldsChecker(&schema.definitions).check(&value, &schema.schema, "")
+        };
+
+        assert_eq!(
+            check(json!({"plugin": "Datagrid", "group_by": ["a"], "plugin_config": {"x": 1}})),
+            Ok(true)
+        );
+
+        assert_eq!(
+            check(json!({"group-by": ["a"]})),
+            Err("Unknown field `group-by`, did you mean `group_by`?".to_owned())
+        );
+
+        assert_eq!(
+            check(json!({"filter": [["a", "==", 1], {"op": "or", "filters": [
+                {"filters": [], "ops": "and"}
+            ]}]})),
+            Err("Unknown field `filter[1].filters[0].ops`".to_owned())
+        );
+    }
+}
@@ -12,13 +12,14 @@
 
 use std::fmt::Display;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Deserialize, Debug, Eq, PartialEq, Serialize)]
+#[derive(Clone, Deserialize, Debug, Eq, PartialEq, Serialize, JsonSchema)]
 #[serde()]
 pub struct Sort(pub String, pub SortDir);
 
-#[derive(Clone, Copy, Deserialize, Debug, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Deserialize, Debug, Eq, PartialEq, Serialize, JsonSchema)]
 #[serde()]
 pub enum SortDir {
     #[serde(rename = "none")]
@@ -0,0 +1,640 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Translation of a `ViewConfig` to an equivalent ANSI SQL query, for
+//! re-implementing a view against a data warehouse.  Parts of the config with
+//! no SQL equivalent (e.g. `matches` filters or `first` aggregates) are
+//! replaced by a neutral placeholder and marked with an `UNTRANSLATED` comment,
+//! both inline and in a summary at the top of the query.
+
+use std::fmt::Write;
+
+use chrono::{TimeZone, Utc};
+use itertools::Itertools;
+
+use super::aggregates::*;
+use super::column_type::Type;
+use super::filters::*;
+use super::sort::*;
+use super::view_config::*;
+use crate::exprtk::{tokenize, Token};
+
+/// The maximum number of conditional columns generated per `columns` entry
+/// for `split_by`, beyond which `split_by` is left untranslated.
+const MAX_SPLIT_COMBINATIONS: usize = 256;
+
+/// ExprTK functions with an identically-named ANSI SQL equivalent.
+const SQL_FUNCTIONS: &[&str] = &["abs", "ceil", "floor", "sqrt", "exp", "upper", "lower"];
+
+impl ViewConfig {
+    /// Translate this config into an ANSI SQL query against `table`.
+    ///
+    /// # Arguments
+    /// - `table` The (optionally `.`-qualified) table name to query.
+    /// - `column_type` The table type of a column or expression, which
+    ///   determines default aggregates.
+    /// - `split_values` The distinct values of each `split_by` column, which
+    ///   `split_by` is expanded to as conditional aggregates.
+    pub fn to_sql(
+        &self,
+        table: &str,
+        column_type: impl Fn(&str) -> Option<Type>,
+        split_values: &[Vec<Scalar>],
+    ) -> String {
+        let mut sql = SqlBuilder::default();
+        let from = if self.expressions.is_empty() {
+            print_table(table)
+        } else {
+            let expressions = self
+                .expressions
+                .iter()
+                .sorted_by(|x, y| x.0.cmp(y.0))
+                .map(|(name, expr)| {
+                    format!("{} AS {}", sql.expression(name, expr), print_ident(name))
+                })
+                .collect::<Vec<_>>();
+
+            sql.ctes.push(format!(
+                "\"expressions\" AS (\n    SELECT *, {}\n    FROM {}\n)",
+                expressions.join(", "),
+                print_table(table)
+            ));
+
+            "\"expressions\"".to_owned()
+        };
+
+        let is_grouped = !self.group_by.is_empty();
+        let splits = if self.split_by.is_empty() {
+            vec![]
+        } else if split_values.len() != self.split_by.len()
+            || split_values.iter().map(Vec::len).product::<usize>() > MAX_SPLIT_COMBINATIONS
+        {
+            sql.untranslated(format!(
+                "split_by {} (too many distinct values)",
+                self.split_by.iter().map(|x| print_ident(x)).join(", ")
+            ));
+
+            vec![]
+        } else {
+            split_values
+                .iter()
+                .multi_cartesian_product()
+                .map(|values| {
+                    let alias = values.iter().map(|x| format!("{}", x)).join("|");
+                    let cond = self
+                        .split_by
+                        .iter()
+                        .zip(values)
+                        .map(|(name, value)| match value {
+                            Scalar::Null => format!("{} IS NULL", print_ident(name)),
+                            value => format!("{} = {}", print_ident(name), print_scalar(value)),
+                        })
+                        .join(" AND ");
+
+                    (alias, cond)
+                })
+                .collect()
+        };
+
+        let mut select = self
+            .group_by
+            .iter()
+            .map(|x| print_ident(x))
+            .collect::<Vec<_>>();
+
+        for (name, occurrence, column) in self.column_aliases() {
+            let agg = self.aggregate(name, occurrence, &column_type);
+            if splits.is_empty() {
+                let value = if is_grouped {
+                    sql.aggregate(name, &agg, &print_ident)
+                } else {
+                    print_ident(name)
+                };
+
+                select.push(format!("{} AS {}", value, print_ident(&column)));
+            }
+
+            for (alias, cond) in splits.iter() {
+                let conditional =
+                    |x: &str| format!("CASE WHEN {} THEN {} END", cond, print_ident(x));
+                let value = if is_grouped {
+                    sql.aggregate(name, &agg, &conditional)
+                } else {
+                    conditional(name)
+                };
+
+                let alias = format!("{}|{}", alias, column);
+                select.push(format!("{} AS {}", value, print_ident(&alias)));
+            }
+        }
+
+        if select.is_empty() {
+            select.push("*".to_owned());
+        }
+
+        let filter = self
+            .filter
+            .iter()
+            .filter_map(|item| sql.filter_item(item, &column_type))
+            .collect::<Vec<_>>();
+
+        // Ungrouped `top_n` is ANSI `FETCH FIRST n ROWS WITH TIES` on a leading
+        // sort, but ranking the groups of a `ROLLUP` has no simple equivalent.
+        let mut sorts = self.sort.clone();
+        let fetch = match &self.top_n {
+            Some(top_n) if is_grouped => {
+                sql.untranslated(format!(
+                    "top {} {} {} level {}",
+                    top_n.n,
+                    print_ident(&top_n.column),
+                    top_n.dir,
+                    top_n.level
+                ));
+
+                None
+            },
+            Some(top_n) => {
+                sorts.retain(|x| x.0 != top_n.column);
+                sorts.insert(0, top_n.sort());
+                Some(top_n.n)
+            },
+            None => None,
+        };
+
+        let sort = sorts
+            .iter()
+            .filter_map(|sort| {
+                sql.sort(sort, is_grouped && !self.group_by.contains(&sort.0), &|x| {
+                    self.aggregate(x, 0, &column_type)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut output = sql
+            .untranslated
+            .iter()
+            .fold(String::new(), |mut output, x| {
+                let _ = writeln!(output, "-- UNTRANSLATED: {}", x);
+                output
+            });
+
+        for x in sql.skipped.iter() {
+            let _ = writeln!(output, "-- SKIPPED: {}", x);
+        }
+
+        if !sql.ctes.is_empty() {
+            output.push_str(&format!("WITH {}\n", sql.ctes.join(",\n")));
+        }
+
+        output.push_str(&format!(
+            "SELECT\n    {}\nFROM {}",
+            select.join(",\n    "),
+            from
+        ));
+        if !filter.is_empty() {
+            output.push_str(&format!("\nWHERE {}", filter.join("\n    AND ")));
+        }
+
+        if is_grouped {
+            let group_by = self.group_by.iter().map(|x| print_ident(x)).join(", ");
+            output.push_str(&format!("\nGROUP BY ROLLUP({})", group_by));
+        }
+
+        if !sort.is_empty() {
+            output.push_str(&format!("\nORDER BY {}", sort.join(", ")));
+        }
+
+        if let Some(n) = fetch {
+            output.push_str(&format!("\nFETCH FIRST {} ROWS WITH TIES", n));
+        }
+
+        output
+    }
+
+    /// The aggregate applied to the `occurrence`-th entry of `name`, which
+    /// defaults to `sum` for numeric columns and `count` otherwise, as in the
+    /// engine.
+    fn aggregate(
+        &self,
+        name: &str,
+        occurrence: usize,
+        column_type: &impl Fn(&str) -> Option<Type>,
+    ) -> Aggregate {
+        self.get_aggregate(name, occurrence)
+            .cloned()
+            .unwrap_or_else(|| {
+                column_type(name)
+                    .unwrap_or(Type::String)
+                    .default_aggregate()
+            })
+    }
+}
+
+#[derive(Default)]
+struct SqlBuilder {
+    ctes: Vec<String>,
+    untranslated: Vec<String>,
+    skipped: Vec<String>,
+}
+
+impl SqlBuilder {
+    /// Record `reason` in the summary, returning an inline marker comment.
+    fn untranslated(&mut self, reason: String) -> String {
+        let marker = format!("/* UNTRANSLATED: {} */", reason.replace("*/", "* /"));
+        self.untranslated.push(reason);
+        marker
+    }
+
+    /// Translate an ExprTK expression made only of columns, literals,
+    /// arithmetic and comparison operators and a few common functions.
+    fn expression(&mut self, name: &str, expr: &str) -> String {
+        let tokens = tokenize(expr);
+        let mut output = String::new();
+        for (idx, token) in tokens.iter().enumerate() {
+            let next = tokens[idx + 1..]
+                .iter()
+                .find(|x| !matches!(x, Token::Whitespace(_) | Token::Break(_)));
+
+            let translated = match token {
+                Token::Comment(_) => Some(""),
+                Token::Whitespace(_) | Token::Break(_) => Some(" "),
+                Token::Literal(x) | Token::Column(x)
+                    if x.starts_with('"') || x.starts_with('\'') || x.parse::<f64>().is_ok() =>
+                {
+                    Some(*x)
+                },
+                Token::Symbol(x) if *x == "true" || *x == "false" => Some(*x),
+                Token::Symbol(x) if matches!(*x, "and" | "or" | "not") => Some(*x),
+                Token::Symbol(x)
+                    if SQL_FUNCTIONS.contains(x)
+                        && matches!(next, Some(Token::Operator(y)) if y.starts_with('(')) =>
+                {
+                    Some(*x)
+                },
+                Token::Operator(x) => match *x {
+                    "==" => Some("="),
+                    "!=" => Some("<>"),
+                    x if x.chars().all(|c| "+-*/%()<>=,".contains(c)) => Some(x),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            match translated {
+                Some(x) => output.push_str(x),
+                None => {
+                    let marker = self.untranslated(format!(
+                        "expression {} `{}`",
+                        print_ident(name),
+                        expr.trim()
+                    ));
+
+                    return format!("NULL {}", marker);
+                },
+            }
+        }
+
+        format!("({})", output.trim())
+    }
+
+    fn aggregate(&mut self, name: &str, agg: &Aggregate, arg: &dyn Fn(&str) -> String) -> String {
+        let x = arg(name);
+        match agg {
+            Aggregate::MultiAggregate(MultiAggregate::WeightedMean, weight) => {
+                let w = arg(weight);
+                format!("SUM({} * {}) / SUM({})", x, w, w)
+            },
+            Aggregate::SingleAggregate(agg) => match agg {
+                SingleAggregate::Sum | SingleAggregate::SumNotNull => format!("SUM({})", x),
+                SingleAggregate::SumAbs => format!("SUM(ABS({}))", x),
+                SingleAggregate::AbsSum => format!("ABS(SUM({}))", x),
+                SingleAggregate::Count => format!("COUNT({})", x),
+                SingleAggregate::DistinctCount => format!("COUNT(DISTINCT {})", x),
+                SingleAggregate::Avg | SingleAggregate::Mean => format!("AVG({})", x),
+                SingleAggregate::Max | SingleAggregate::High => format!("MAX({})", x),
+                SingleAggregate::Min | SingleAggregate::Low | SingleAggregate::Any => {
+                    format!("MIN({})", x)
+                },
+                SingleAggregate::HighMinusLow => format!("MAX({}) - MIN({})", x, x),
+                SingleAggregate::StdDev => format!("STDDEV_SAMP({})", x),
+                SingleAggregate::Var => format!("VAR_SAMP({})", x),
+                SingleAggregate::Median => {
+                    format!("PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY {})", x)
+                },
+                SingleAggregate::Unique => {
+                    format!("CASE WHEN COUNT(DISTINCT {}) = 1 THEN MIN({}) END", x, x)
+                },
+                SingleAggregate::Join => format!("LISTAGG({}, ', ')", x),
+                SingleAggregate::PctSumGrandTotal => {
+                    format!("100.0 * SUM({}) / SUM(SUM({})) OVER ()", x, x)
+                },
+                agg => format!(
+                    "NULL {}",
+                    self.untranslated(format!("aggregate `{}` of {}", agg, print_ident(name)))
+                ),
+            },
+        }
+    }
+
+    /// Translate a filter, or `None` if the engine would ignore it entirely.
+    fn filter_item(
+        &mut self,
+        item: &FilterItem,
+        column_type: &impl Fn(&str) -> Option<Type>,
+    ) -> Option<String> {
+        match item {
+            FilterItem::Filter(filter) => {
+                let mut filter = filter.clone();
+                if let Some(ty) = column_type(&filter.0) {
+                    filter.coerce_type(ty);
+                }
+
+                self.filter(&filter)
+            },
+            FilterItem::Group(FilterGroup { op, filters }) => {
+                let op = match op {
+                    FilterReducer::And => " AND ",
+                    FilterReducer::Or => " OR ",
+                };
+
+                let filters = filters
+                    .iter()
+                    .filter_map(|x| self.filter_item(x, column_type))
+                    .collect::<Vec<_>>();
+
+                (!filters.is_empty()).then(|| format!("({})", filters.join(op)))
+            },
+        }
+    }
+
+    /// Like the engine's `is_valid_filter()`, a `null` term is only meaningful
+    /// to `is null` and `is not null`, and the filter is otherwise skipped.
+    fn filter(&mut self, filter: &Filter) -> Option<String> {
+        let Filter(name, op, term) = filter;
+        let col = print_ident(name);
+        let lower = format!("LOWER({})", col);
+        let translated = match (op, term) {
+            (FilterOp::IsNull, _) => Some(format!("{} IS NULL", col)),
+            (FilterOp::IsNotNull, _) => Some(format!("{} IS NOT NULL", col)),
+            (_, FilterTerm::Scalar(Scalar::Null)) => {
+                self.skipped.push(format!("filter {} {} null", col, op));
+                return None;
+            },
+            (FilterOp::In | FilterOp::NotIn, FilterTerm::Array(xs)) => Some(format!(
+                "{} {} ({})",
+                col,
+                if *op == FilterOp::In { "IN" } else { "NOT IN" },
+                xs.iter().map(print_scalar).join(", ")
+            )),
+            (FilterOp::Between, FilterTerm::Array(xs)) if xs.len() == 2 => Some(format!(
+                "{} BETWEEN {} AND {}",
+                col,
+                print_scalar(&xs[0]),
+                print_scalar(&xs[1])
+            )),
+            (_, FilterTerm::Scalar(x)) => {
+                let pattern = |prefix: &str, suffix: &str| match x {
+                    Scalar::String(x) => Some(format!(
+                        "LIKE {} ESCAPE '\\'",
+                        print_string(&format!("{}{}{}", prefix, escape_like(x), suffix))
+                    )),
+                    _ => None,
+                };
+
+                let lower_x = format!("LOWER({})", print_scalar(x));
+                match op {
+                    FilterOp::GT => Some(format!("{} > {}", col, print_scalar(x))),
+                    FilterOp::LT => Some(format!("{} < {}", col, print_scalar(x))),
+                    FilterOp::GTE => Some(format!("{} >= {}", col, print_scalar(x))),
+                    FilterOp::LTE => Some(format!("{} <= {}", col, print_scalar(x))),
+                    FilterOp::EQ => Some(format!("{} = {}", col, print_scalar(x))),
+                    FilterOp::NE => Some(format!("{} <> {}", col, print_scalar(x))),
+                    FilterOp::EQIgnoreCase => Some(format!("{} = {}", lower, lower_x)),
+                    FilterOp::Contains => pattern("%", "%").map(|x| format!("{} {}", col, x)),
+                    FilterOp::BeginsWith => pattern("", "%").map(|x| format!("{} {}", col, x)),
+                    FilterOp::EndsWith => pattern("%", "").map(|x| format!("{} {}", col, x)),
+                    FilterOp::ContainsIgnoreCase => {
+                        pattern("%", "%").map(|x| format!("{} {}", lower, x.to_lowercase()))
+                    },
+                    FilterOp::BeginsWithIgnoreCase => {
+                        pattern("", "%").map(|x| format!("{} {}", lower, x.to_lowercase()))
+                    },
+                    FilterOp::EndsWithIgnoreCase => {
+                        pattern("%", "").map(|x| format!("{} {}", lower, x.to_lowercase()))
+                    },
+                    _ => None,
+                }
+            },
+            _ => None,
+        };
+
+        Some(translated.unwrap_or_else(|| {
+            let marker = self.untranslated(format!("filter {} {} {}", col, op, term));
+            format!("TRUE {}", marker)
+        }))
+    }
+
+    /// Row sorts on an aggregated column sort by the column's aggregate, while
+    /// column sorts (which reorder `split_by` columns) have no SQL equivalent.
+    fn sort(
+        &mut self,
+        Sort(name, dir): &Sort,
+        is_aggregated: bool,
+        aggregate: &dyn Fn(&str) -> Aggregate,
+    ) -> Option<String> {
+        let (order, abs) = match dir {
+            SortDir::None => return None,
+            SortDir::Asc => ("ASC", false),
+            SortDir::Desc => ("DESC", false),
+            SortDir::AscAbs => ("ASC", true),
+            SortDir::DescAbs => ("DESC", true),
+            _ => {
+                self.untranslated(format!("sort {} {}", print_ident(name), dir));
+                return None;
+            },
+        };
+
+        let value = if is_aggregated {
+            self.aggregate(name, &aggregate(name), &print_ident)
+        } else {
+            print_ident(name)
+        };
+
+        Some(if abs {
+            format!("ABS({}) {}", value, order)
+        } else {
+            format!("{} {}", value, order)
+        })
+    }
+}
+
+fn print_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn print_table(table: &str) -> String {
+    table.split('.').map(print_ident).join(".")
+}
+
+fn print_string(x: &str) -> String {
+    format!("'{}'", x.replace('\'', "''"))
+}
+
+fn escape_like(x: &str) -> String {
+    x.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn print_scalar(scalar: &Scalar) -> String {
+    match scalar {
+        Scalar::Float(x) => format!("{}", x),
+        Scalar::Int(x) => format!("{}", x),
+        Scalar::String(x) => print_string(x),
+        Scalar::Bool(true) => "TRUE".to_owned(),
+        Scalar::Bool(false) => "FALSE".to_owned(),
+        Scalar::Date(_) => format!("DATE '{}'", scalar),
+        Scalar::DateTime(x) => match Utc.timestamp_millis_opt(*x as i64).earliest() {
+            Some(x) => format!("TIMESTAMP '{}'", x.format("%Y-%m-%d %H:%M:%S%.3f")),
+            None => format!("{}", x),
+        },
+        Scalar::Null => "NULL".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use wasm_bindgen_test::*;
+
+    use super::*;
+    use crate::config::{Expression, Expressions, TopN};
+
+    fn column_type(name: &str) -> Option<Type> {
+        match name {
+            "State" | "Segment" | "Ship Mode" => Some(Type::String),
+            _ => Some(Type::Float),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_to_sql_flat() {
+        let config = ViewConfig {
+            columns: vec![Some("Sales".to_owned()), None, Some("Profit".to_owned())],
+            filter: vec![
+                Filter(
+                    "State".to_owned(),
+                    FilterOp::BeginsWith,
+                    FilterTerm::Scalar(Scalar::String("New_".to_owned())),
+                )
+                .into(),
+                Filter(
+                    "Sales".to_owned(),
+                    FilterOp::GT,
+                    FilterTerm::Scalar(Scalar::Float(10.0)),
+                )
+                .into(),
+            ],
+            sort: vec![Sort("Profit".to_owned(), SortDir::DescAbs)],
+            ..ViewConfig::default()
+        };
+
+        assert_eq!(
+            config.to_sql("db.superstore", column_type, &[]),
+            "SELECT\n    \"Sales\" AS \"Sales\",\n    \"Profit\" AS \"Profit\"\nFROM \
+             \"db\".\"superstore\"\nWHERE \"State\" LIKE 'New\\_%' ESCAPE '\\'\n    AND \"Sales\" \
+             > 10\nORDER BY ABS(\"Profit\") DESC"
+        );
+
+        let config = ViewConfig {
+            top_n: Some(TopN {
+                column: "Sales".to_owned(),
+                dir: SortDir::Desc,
+                n: 5.try_into().unwrap(),
+                level: 0,
+            }),
+            ..config
+        };
+
+        assert!(config.to_sql("superstore", column_type, &[]).ends_with(
+            "ORDER BY \"Sales\" DESC, ABS(\"Profit\") DESC\nFETCH FIRST 5 ROWS WITH TIES"
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_to_sql_grouped_split() {
+        let mut expressions = Expressions::default();
+        expressions.insert(&Expression::new(
+            Some("Margin".into()),
+            "\"Profit\" / \"Sales\"".into(),
+        ));
+
+        expressions.insert(&Expression::new(Some("Bad".into()), "var x := 1; x".into()));
+        let config = ViewConfig {
+            group_by: vec!["State".to_owned()],
+            split_by: vec!["Segment".to_owned()],
+            columns: vec![Some("Sales".to_owned()), Some("Margin".to_owned())],
+            aggregates: HashMap::from([(
+                "Margin".to_owned(),
+                Aggregate::SingleAggregate(SingleAggregate::First).into(),
+            )]),
+            expressions,
+            sort: vec![Sort("Sales".to_owned(), SortDir::Desc)],
+            ..ViewConfig::default()
+        };
+
+        let split_values = [vec![
+            Scalar::String("Consumer".to_owned()),
+            Scalar::String("Corporate".to_owned()),
+        ]];
+
+        let sql = config.to_sql("superstore", column_type, &split_values);
+        assert!(sql.starts_with("-- UNTRANSLATED: expression \"Bad\""));
+        assert!(sql.contains("(\"Profit\" / \"Sales\") AS \"Margin\""));
+        assert!(sql.contains(
+            "SUM(CASE WHEN \"Segment\" = 'Consumer' THEN \"Sales\" END) AS \"Consumer|Sales\""
+        ));
+
+        assert!(sql.contains(
+            "NULL /* UNTRANSLATED: aggregate `first` of \"Margin\" */ AS \"Corporate|Margin\""
+        ));
+        assert!(sql.contains(
+            "FROM \"expressions\"\nGROUP BY ROLLUP(\"State\")\nORDER BY SUM(\"Sales\") DESC"
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_to_sql_null_terms() {
+        let null_filter = |op| Filter("State".to_owned(), op, FilterTerm::Scalar(Scalar::Null));
+        let config = ViewConfig {
+            columns: vec![Some("Sales".to_owned())],
+            filter: vec![
+                null_filter(FilterOp::EQ).into(),
+                null_filter(FilterOp::NE).into(),
+                null_filter(FilterOp::GT).into(),
+                null_filter(FilterOp::IsNull).into(),
+            ],
+            ..ViewConfig::default()
+        };
+
+        let sql = config.to_sql("superstore", column_type, &[]);
+        assert!(sql.starts_with(
+            "-- SKIPPED: filter \"State\" == null\n-- SKIPPED: filter \"State\" != null\n-- \
+             SKIPPED: filter \"State\" > null\n"
+        ));
+        assert!(sql.ends_with("WHERE \"State\" IS NULL"));
+        assert!(!sql.contains("UNTRANSLATED"));
+        assert!(!sql.contains("= NULL"));
+        assert!(!sql.contains("> NULL"));
+    }
+}
@@ -13,9 +13,10 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 pub enum StringColorMode {
     #[serde(rename = "foreground")]
     Foreground,
@@ -53,7 +54,7 @@ impl FromStr for StringColorMode {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 pub enum FormatMode {
     #[serde(rename = "link")]
     Link,
@@ -96,7 +97,7 @@ impl FromStr for FormatMode {
     }
 }
 
-#[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq, Serialize, JsonSchema)]
 pub struct StringColumnStyleConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
@@ -0,0 +1,330 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::num::NonZeroUsize;
+
+use schemars::JsonSchema;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::filters::*;
+use super::sort::*;
+use super::view_config::*;
+
+/// The most groups a grouped `TopN` filter may keep, as the filter has an
+/// equality clause per group.  Beyond this, `TopN::to_filter()` does not limit
+/// the `View` at all.
+pub const MAX_TOP_N_GROUPS: usize = 1000;
+
+/// Limits a `View` to the rows with the `n` highest (for `SortDir::Desc`) or
+/// lowest (for `SortDir::Asc`) values of `column`.  When the `View` has a
+/// `group_by`, the groups of `group_by[level]` are limited instead, to the
+/// top `n` within each of their parent groups, ranked by `column`'s aggregate.
+/// Ties with the `n`-th value are kept.
+///
+/// The engine has no equivalent, so the top `n` are computed from an auxiliary
+/// `View` each time the `View` is created (see `TopN::aux_config()`), and
+/// applied as an additional filter (see `TopN::to_filter()`).
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TopN {
+    pub column: String,
+
+    #[serde(deserialize_with = "deserialize_top_n_dir")]
+    pub dir: SortDir,
+
+    pub n: NonZeroUsize,
+
+    #[serde(default)]
+    pub level: usize,
+}
+
+fn deserialize_top_n_dir<'de, D>(deserializer: D) -> Result<SortDir, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match SortDir::deserialize(deserializer)? {
+        dir @ (SortDir::Asc | SortDir::Desc) => Ok(dir),
+        dir => Err(D::Error::custom(format!(
+            "Unsupported top_n direction `{}`, expected `asc` or `desc`",
+            dir
+        ))),
+    }
+}
+
+impl TopN {
+    pub fn sort(&self) -> Sort {
+        Sort(self.column.clone(), self.dir)
+    }
+
+    /// The index into `group_by` of the limited groups, or `None` if the rows
+    /// themselves are limited.  `level`s past the last `group_by` limit the
+    /// deepest groups.
+    pub fn group_level(&self, group_by: &[String]) -> Option<usize> {
+        (!group_by.is_empty()).then(|| self.level.min(group_by.len() - 1))
+    }
+
+    /// The `ViewConfig` of the auxiliary `View` which ranks the rows (or
+    /// groups) of `config` by `column`.  To resolve the top `n`, only the
+    /// first `n` rows of this `View` are needed when `config` has no
+    /// `group_by`; otherwise, all of its rows are needed.
+    pub fn aux_config(&self, config: &ViewConfig) -> ViewConfig {
+        let mut filter = config.filter.clone();
+        let group_by = match self.group_level(&config.group_by) {
+            Some(level) => config.group_by[..=level].to_vec(),
+            None => {
+                let not_null = Filter(
+                    self.column.clone(),
+                    FilterOp::IsNotNull,
+                    FilterTerm::Scalar(Scalar::Null),
+                );
+
+                filter.push(not_null.into());
+                vec![]
+            },
+        };
+
+        ViewConfig {
+            group_by,
+            columns: vec![Some(self.column.clone())],
+            filter,
+            sort: vec![self.sort()],
+            expressions: config.expressions.clone(),
+            aggregates: config
+                .aggregates
+                .get_key_value(&self.column)
+                .map(|(name, aggs)| (name.clone(), aggs.clone()))
+                .into_iter()
+                .collect(),
+            ..ViewConfig::default()
+        }
+    }
+
+    /// The filter which keeps the top `n` rows (or groups), given the
+    /// `row_paths` and `column` `values` of the `View` created from
+    /// `TopN::aux_config()`, or `None` if every row is in the top `n` (or more
+    /// than `MAX_TOP_N_GROUPS` groups are).
+    pub fn to_filter(
+        &self,
+        group_by: &[String],
+        row_paths: &[Vec<Scalar>],
+        values: &[Scalar],
+    ) -> Option<FilterItem> {
+        let Some(level) = self.group_level(group_by) else {
+            let threshold = values.get(self.n.get() - 1)?;
+            let op = match self.dir {
+                SortDir::Asc => FilterOp::LTE,
+                _ => FilterOp::GTE,
+            };
+
+            let term = FilterTerm::Scalar(threshold.clone());
+            return Some(Filter(self.column.clone(), op, term).into());
+        };
+
+        let mut groups = vec![];
+        let mut parent: Option<&[Scalar]> = None;
+        let mut count = 0;
+        let mut last: Option<&Scalar> = None;
+        let mut is_limited = false;
+        for (path, value) in row_paths.iter().zip(values) {
+            if path.len() != level + 1 {
+                continue;
+            }
+
+            if parent != Some(&path[..level]) {
+                parent = Some(&path[..level]);
+                count = 0;
+                last = None;
+            }
+
+            if count < self.n.get() || last == Some(value) {
+                groups.push(path);
+                last = Some(value);
+            } else {
+                is_limited = true;
+            }
+
+            count += 1;
+        }
+
+        if !is_limited {
+            return None;
+        }
+
+        if groups.len() > MAX_TOP_N_GROUPS {
+            tracing::warn!(
+                "`top_n` keeps {} groups, more than the {} supported, so it is not applied",
+                groups.len(),
+                MAX_TOP_N_GROUPS
+            );
+
+            return None;
+        }
+
+        let filters = groups
+            .into_iter()
+            .map(|path| {
+                let filters = group_by
+                    .iter()
+                    .zip(path)
+                    .map(|(name, value)| {
+                        let (op, term) = match value {
+                            Scalar::Null => (FilterOp::IsNull, Scalar::Null),
+                            x => (FilterOp::EQ, x.clone()),
+                        };
+
+                        Filter(name.clone(), op, FilterTerm::Scalar(term)).into()
+                    })
+                    .collect::<Vec<FilterItem>>();
+
+                match <[FilterItem; 1]>::try_from(filters) {
+                    Ok([filter]) => filter,
+                    Err(filters) => FilterItem::Group(FilterGroup {
+                        op: FilterReducer::And,
+                        filters,
+                    }),
+                }
+            })
+            .collect();
+
+        Some(FilterItem::Group(FilterGroup {
+            op: FilterReducer::Or,
+            filters,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    fn top_n(dir: SortDir, n: usize, level: usize) -> TopN {
+        TopN {
+            column: "Sales".to_owned(),
+            dir,
+            n: NonZeroUsize::new(n).unwrap(),
+            level,
+        }
+    }
+
+    fn eq(name: &str, value: &str) -> FilterItem {
+        let term = FilterTerm::Scalar(Scalar::String(value.to_owned()));
+        Filter(name.to_owned(), FilterOp::EQ, term).into()
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_top_n_flat() {
+        let values = [5.0, 4.0, 4.0].map(Scalar::Float);
+        assert_eq!(
+            top_n(SortDir::Desc, 2, 0).to_filter(&[], &[], &values),
+            Some(
+                Filter(
+                    "Sales".to_owned(),
+                    FilterOp::GTE,
+                    FilterTerm::Scalar(Scalar::Float(4.0))
+                )
+                .into()
+            )
+        );
+
+        assert_eq!(top_n(SortDir::Asc, 4, 0).to_filter(&[], &[], &values), None);
+        assert!(serde_json::from_value::<TopN>(serde_json::json!({
+            "column": "Sales",
+            "dir": "desc abs",
+            "n": 10
+        }))
+        .is_err());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_top_n_group_by() {
+        let group_by = ["Region".to_owned(), "State".to_owned()];
+        let path = |xs: &[&str]| {
+            xs.iter()
+                .map(|x| Scalar::String(x.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        let row_paths = [
+            path(&[]),
+            path(&["East"]),
+            path(&["East", "NY"]),
+            path(&["East", "PA"]),
+            path(&["East", "MA"]),
+            path(&["West"]),
+            path(&["West", "CA"]),
+        ];
+
+        let values = [10.0, 6.0, 3.0, 2.0, 1.0, 4.0, 4.0].map(Scalar::Float);
+        let filter = top_n(SortDir::Desc, 1, 0).to_filter(&group_by, &row_paths, &values);
+        assert_eq!(
+            filter,
+            Some(FilterItem::Group(FilterGroup {
+                op: FilterReducer::Or,
+                filters: vec![eq("Region", "East")]
+            }))
+        );
+
+        let filter = top_n(SortDir::Desc, 3, 1).to_filter(&group_by, &row_paths, &values);
+        assert_eq!(filter, None);
+
+        let filter = top_n(SortDir::Desc, 2, 1).to_filter(&group_by, &row_paths, &values);
+        let group = |filters| {
+            FilterItem::Group(FilterGroup {
+                op: FilterReducer::And,
+                filters,
+            })
+        };
+
+        assert_eq!(
+            filter,
+            Some(FilterItem::Group(FilterGroup {
+                op: FilterReducer::Or,
+                filters: vec![
+                    group(vec![eq("Region", "East"), eq("State", "NY")]),
+                    group(vec![eq("Region", "East"), eq("State", "PA")]),
+                    group(vec![eq("Region", "West"), eq("State", "CA")]),
+                ]
+            }))
+        );
+
+        let config = ViewConfig {
+            group_by: group_by.to_vec(),
+            split_by: vec!["Segment".to_owned()],
+            columns: vec![Some("Profit".to_owned())],
+            ..ViewConfig::default()
+        };
+
+        // A limit on more than `MAX_TOP_N_GROUPS` groups is not applied.
+        let row_paths = (0..MAX_TOP_N_GROUPS + 2)
+            .map(|x| path(&[&x.to_string()]))
+            .collect::<Vec<_>>();
+
+        let values = (0..MAX_TOP_N_GROUPS + 2)
+            .map(|x| Scalar::Float(-(x as f64)))
+            .collect::<Vec<_>>();
+
+        let n = MAX_TOP_N_GROUPS;
+        let filter = top_n(SortDir::Desc, n, 0).to_filter(&group_by, &row_paths, &values);
+        assert!(filter.is_some());
+        let filter = top_n(SortDir::Desc, n + 1, 0).to_filter(&group_by, &row_paths, &values);
+        assert_eq!(filter, None);
+
+        let aux = top_n(SortDir::Desc, 2, 5).aux_config(&config);
+        assert_eq!(aux.group_by, group_by);
+        assert!(aux.split_by.is_empty());
+        assert_eq!(aux.columns, vec![Some("Sales".to_owned())]);
+        assert_eq!(aux.sort, vec![Sort("Sales".to_owned(), SortDir::Desc)]);
+    }
+}
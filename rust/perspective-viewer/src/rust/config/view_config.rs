@@ -10,8 +10,9 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
@@ -24,10 +25,12 @@ use super::aggregates::*;
 use super::expressions::*;
 use super::filters::*;
 use super::sort::*;
+use super::top_n::*;
+use super::viewer_config::OptionalUpdate;
 use crate::js::perspective::JsPerspectiveViewConfig;
 use crate::utils::*;
 
-#[derive(Clone, Debug, Deserialize, Default, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Default, PartialEq, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ViewConfig {
     #[serde(default)]
@@ -40,7 +43,7 @@ pub struct ViewConfig {
     pub columns: Vec<Option<String>>,
 
     #[serde(default)]
-    pub filter: Vec<Filter>,
+    pub filter: Vec<FilterItem>,
 
     #[serde(default)]
     pub sort: Vec<Sort>,
@@ -49,7 +52,10 @@ pub struct ViewConfig {
     pub expressions: Expressions,
 
     #[serde(default)]
-    pub aggregates: HashMap<String, Aggregate>,
+    pub aggregates: HashMap<String, ColumnAggregates>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_n: Option<TopN>,
 }
 
 impl ViewConfig {
@@ -63,12 +69,88 @@ impl ViewConfig {
         }
     }
 
+    /// The engine only supports a flat, AND-ed `filter` list of simple
+    /// comparisons, so any nested `FilterGroup` or `FilterOp` without an engine
+    /// equivalent is compiled to a boolean expression column which is then
+    /// filtered on `true`, and `between` filters are split into a pair of
+    /// comparisons.  `between` filters with an unresolved
+    /// `FilterTerm::Relative` term are ignored, see
+    /// `Filter::resolve_relative_date`.  Similarly, the engine only supports
+    /// one instance of a column, so repeated `columns` entries are aliased as
+    /// expression columns (see `ViewConfig::column_aliases`).  `top_n` must
+    /// already be resolved to a filter (see `TopN::to_filter()`), and only
+    /// contributes its sort, ahead of `sort`.
     pub fn as_jsvalue(&self) -> ApiResult<JsPerspectiveViewConfig> {
-        let mut new_config = self.clone();
-        new_config.columns.retain(|x| x.is_some());
+        let new_config = self.to_engine_config();
         Ok(JsValue::from_serde_ext(&new_config).map(|x| x.unchecked_into())?)
     }
 
+    /// The `ViewConfig` sent to the engine by `ViewConfig::as_jsvalue()`.
+    /// Filter terms are sent as typed, so they should already be coerced to
+    /// their column's type (see `Scalar::coerce()`).
+    pub(crate) fn to_engine_config(&self) -> Self {
+        let mut new_config = self.clone();
+        let top_n = new_config.top_n.take();
+        if let Some(top_n) = top_n.filter(|x| !self.sort.iter().any(|y| y.0 == x.column)) {
+            new_config.sort.insert(0, top_n.sort());
+        }
+
+        new_config.aggregates = self
+            .aggregates
+            .iter()
+            .filter_map(|(name, aggs)| Some((name.clone(), aggs.get(0)?.clone().into())))
+            .collect();
+
+        new_config.columns = self
+            .column_aliases()
+            .into_iter()
+            .map(|(name, occurrence, alias)| {
+                if occurrence > 0 {
+                    let expr = format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""));
+                    new_config
+                        .expressions
+                        .insert(&Expression::new(Some(alias.as_str().into()), expr.into()));
+
+                    if let Some(agg) = self.get_aggregate(name, occurrence) {
+                        new_config
+                            .aggregates
+                            .insert(alias.clone(), agg.clone().into());
+                    }
+                }
+
+                Some(alias)
+            })
+            .collect();
+
+        new_config.filter = self
+            .filter
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, item)| match item {
+                FilterItem::Filter(filter) if filter.1 == FilterOp::Between => filter
+                    .split_range()
+                    .map(|(start, end)| vec![start.into(), end.into()])
+                    .unwrap_or_default(),
+                FilterItem::Filter(filter) if !filter.1.is_expression() => vec![item.clone()],
+                _ => {
+                    let Some(expr) = item.to_expression() else {
+                        return vec![];
+                    };
+
+                    let name = format!("__filter_{}__", idx);
+                    let expr = format!("if ({}) {{ true }} else {{ false }}", expr);
+                    new_config
+                        .expressions
+                        .insert(&Expression::new(Some(name.as_str().into()), expr.into()));
+
+                    vec![Filter(name, FilterOp::EQ, FilterTerm::Scalar(Scalar::Bool(true))).into()]
+                },
+            })
+            .collect();
+
+        new_config
+    }
+
     pub fn reset(&mut self, reset_expressions: bool) {
         let mut config = Self::default();
         if !reset_expressions {
@@ -88,9 +170,183 @@ impl ViewConfig {
         changed = Self::_apply(&mut self.sort, update.sort) || changed;
         changed = Self::_apply(&mut self.aggregates, update.aggregates) || changed;
         changed = Self::_apply(&mut self.expressions, update.expressions) || changed;
+        changed = match update.top_n {
+            OptionalUpdate::Update(x) => Self::_apply(&mut self.top_n, Some(Some(x))),
+            OptionalUpdate::SetDefault => Self::_apply(&mut self.top_n, Some(None)),
+            OptionalUpdate::Missing => false,
+        } || changed;
         changed
     }
 
+    /// Compute the minimal `ViewConfigUpdate` which transforms `self` into
+    /// `other`, setting only the fields which differ, as well as the inverse
+    /// update which transforms `other` back into `self`.
+    pub fn diff(&self, other: &Self) -> ViewConfigDiff {
+        let ViewConfigDiff {
+            mut patch,
+            mut inverse,
+        } = ViewConfigDiff::default();
+
+        Self::_diff(
+            &self.group_by,
+            &other.group_by,
+            &mut patch.group_by,
+            &mut inverse.group_by,
+        );
+        Self::_diff(
+            &self.split_by,
+            &other.split_by,
+            &mut patch.split_by,
+            &mut inverse.split_by,
+        );
+        Self::_diff(
+            &self.columns,
+            &other.columns,
+            &mut patch.columns,
+            &mut inverse.columns,
+        );
+        Self::_diff(
+            &self.filter,
+            &other.filter,
+            &mut patch.filter,
+            &mut inverse.filter,
+        );
+        Self::_diff(&self.sort, &other.sort, &mut patch.sort, &mut inverse.sort);
+        Self::_diff(
+            &self.aggregates,
+            &other.aggregates,
+            &mut patch.aggregates,
+            &mut inverse.aggregates,
+        );
+        Self::_diff(
+            &self.expressions,
+            &other.expressions,
+            &mut patch.expressions,
+            &mut inverse.expressions,
+        );
+        if self.top_n != other.top_n {
+            patch.top_n = other.top_n.clone().into();
+            inverse.top_n = self.top_n.clone().into();
+        }
+
+        ViewConfigDiff { patch, inverse }
+    }
+
+    fn _diff<T: Clone + PartialEq>(
+        field: &T,
+        other: &T,
+        patch: &mut Option<T>,
+        inverse: &mut Option<T>,
+    ) {
+        if field != other {
+            *patch = Some(other.clone());
+            *inverse = Some(field.clone());
+        }
+    }
+
+    /// The `Aggregate` of the `occurrence`-th entry of `name` in `columns`, or
+    /// `None` for the column type's default.
+    pub fn get_aggregate(&self, name: &str, occurrence: usize) -> Option<&Aggregate> {
+        self.aggregates.get(name)?.get(occurrence)
+    }
+
+    /// The number of entries in `columns` before `idx` which are the same
+    /// column as `columns[idx]`.
+    pub fn column_occurrence(&self, idx: usize) -> usize {
+        match self.columns.get(idx) {
+            Some(Some(name)) => self.columns[..idx]
+                .iter()
+                .filter(|x| x.as_ref() == Some(name))
+                .count(),
+            _ => 0,
+        }
+    }
+
+    /// The `Aggregate` of each `columns` entry (`None` for the default or an
+    /// empty entry), so entries can be moved with their `Aggregate` and then
+    /// restored with `ViewConfig::set_column_slot_aggregates()`.
+    pub fn column_slot_aggregates(&self) -> Vec<Option<Aggregate>> {
+        (0..self.columns.len())
+            .map(|idx| {
+                let name = self.columns[idx].as_ref()?;
+                self.get_aggregate(name, self.column_occurrence(idx))
+                    .cloned()
+            })
+            .collect()
+    }
+
+    /// Replace the `aggregates` of the columns in `columns` with
+    /// `slot_aggregates`, one for each `columns` entry as returned by
+    /// `ViewConfig::column_slot_aggregates()`.  The `aggregates` of columns
+    /// not in `columns` are unchanged.
+    pub fn set_column_slot_aggregates(&mut self, slot_aggregates: Vec<Option<Aggregate>>) {
+        let mut aggregates = self
+            .aggregates
+            .iter()
+            .filter(|(name, _)| !self.columns.iter().flatten().any(|x| x == *name))
+            .map(|(name, aggs)| (name.clone(), aggs.clone()))
+            .collect::<HashMap<_, _>>();
+
+        let mut counts = HashMap::<&str, usize>::new();
+        for (name, agg) in self.columns.iter().zip(slot_aggregates) {
+            if let Some(name) = name {
+                let occurrence = counts.entry(name).or_default();
+                aggregates
+                    .entry(name.clone())
+                    .or_default()
+                    .set(*occurrence, agg);
+
+                *occurrence += 1;
+            }
+        }
+
+        aggregates.retain(|_, aggs| !aggs.is_empty());
+        self.aggregates = aggregates;
+    }
+
+    /// The non-empty `columns` entries, each paired with its occurrence (see
+    /// `ViewConfig::column_occurrence`) and the name of the `View` column it
+    /// is rendered as.  The first occurrence of a column keeps its name, and
+    /// later occurrences are named for their aggregate, e.g. `"price (max)"`.
+    pub fn column_aliases(&self) -> Vec<(&str, usize, String)> {
+        let mut counts = HashMap::<&str, usize>::new();
+        let mut names = self
+            .columns
+            .iter()
+            .flatten()
+            .map(|x| x.to_owned())
+            .chain(self.expressions.keys().cloned())
+            .collect::<HashSet<_>>();
+
+        self.columns
+            .iter()
+            .flatten()
+            .map(|name| {
+                let count = counts.entry(name).or_default();
+                let occurrence = *count;
+                *count += 1;
+                if occurrence == 0 {
+                    return (name.as_str(), occurrence, name.to_owned());
+                }
+
+                let suffix = match self.get_aggregate(name, occurrence) {
+                    Some(agg) => format!("{}", agg),
+                    None => format!("{}", occurrence + 1),
+                };
+
+                let mut alias = format!("{} ({})", name, suffix);
+                let mut idx = 2;
+                while names.contains(&alias) {
+                    alias = format!("{} ({} {})", name, suffix, idx);
+                    idx += 1;
+                }
+
+                names.insert(alias.clone());
+                (name.as_str(), occurrence, alias)
+            })
+            .collect()
+    }
+
     pub fn is_aggregated(&self) -> bool {
         !self.group_by.is_empty()
     }
@@ -100,34 +356,56 @@ impl ViewConfig {
         self.group_by.contains(&name)
             || self.split_by.contains(&name)
             || self.sort.iter().any(|x| x.0 == name)
-            || self.filter.iter().any(|x| x.0 == name)
+            || self.filter.iter_filters().any(|x| x.0 == name)
+            || self.top_n.iter().any(|x| x.column == name)
             || self.columns.contains(&Some(name))
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Default, Serialize)]
+#[derive(Clone, Debug, Deserialize, Default, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ViewConfigUpdate {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub group_by: Option<Vec<String>>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub split_by: Option<Vec<String>>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub columns: Option<Vec<Option<String>>>,
 
-    #[serde(default)]
-    pub filter: Option<Vec<Filter>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Vec<FilterItem>>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort: Option<Vec<Sort>>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub expressions: Option<Expressions>,
 
-    #[serde(default)]
-    pub aggregates: Option<HashMap<String, Aggregate>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregates: Option<HashMap<String, ColumnAggregates>>,
+
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
+    pub top_n: OptionalUpdate<TopN>,
+}
+
+/// The difference between two `ViewConfig`s, as returned by
+/// `ViewConfig::diff()`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ViewConfigDiff {
+    /// Transforms the original `ViewConfig` into the new one.
+    pub patch: ViewConfigUpdate,
+
+    /// Transforms the new `ViewConfig` back into the original.
+    pub inverse: ViewConfigUpdate,
+}
+
+impl ViewConfigUpdate {
+    /// Whether this update would leave any `ViewConfig` unchanged.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 #[cfg(test)]
@@ -144,11 +422,47 @@ mod tests {
 
         let rec: ViewConfig = x.into_serde_ext().unwrap();
         assert_eq!(
-            *rec.aggregates.get("x").unwrap(),
+            *rec.get_aggregate("x", 0).unwrap(),
             Aggregate::MultiAggregate(MultiAggregate::WeightedMean, "y".to_owned())
         );
     }
 
+    #[wasm_bindgen_test]
+    pub fn test_repeated_column_aggregates() {
+        let x = serde_json::json!({
+            "columns": ["x", "y", "x", "x"],
+            "aggregates": {
+                "x": [null, "max", ["weighted mean", "y"]],
+                "y": "avg"
+            }
+        });
+
+        let rec: ViewConfig = serde_json::from_value(x).unwrap();
+        assert_eq!(rec.get_aggregate("x", 0), None);
+        assert_eq!(
+            rec.get_aggregate("x", 1),
+            Some(&Aggregate::SingleAggregate(SingleAggregate::Max))
+        );
+
+        assert_eq!(
+            rec.get_aggregate("y", 0),
+            Some(&Aggregate::SingleAggregate(SingleAggregate::Avg))
+        );
+
+        assert_eq!(rec.column_occurrence(3), 2);
+        assert_eq!(
+            rec.column_aliases()
+                .into_iter()
+                .map(|(_, _, alias)| alias)
+                .collect::<Vec<_>>(),
+            vec!["x", "y", "x (max)", "x (weighted mean by y)"]
+        );
+
+        let json = serde_json::to_value(&rec).unwrap();
+        assert_eq!(json["aggregates"]["y"], serde_json::json!("avg"));
+        assert_eq!(serde_json::from_value::<ViewConfig>(json).unwrap(), rec);
+    }
+
     #[wasm_bindgen_test]
     pub fn test_group_by() {
         let x = json!({
@@ -199,6 +513,76 @@ mod tests {
         )]);
     }
 
+    #[wasm_bindgen_test]
+    pub fn test_column_filter_groups() {
+        let eu = [
+            JsValue::from("region"),
+            JsValue::from("=="),
+            JsValue::from("EU"),
+        ]
+        .iter()
+        .collect::<Array>();
+
+        let us = [
+            JsValue::from("region"),
+            JsValue::from("=="),
+            JsValue::from("US"),
+        ]
+        .iter()
+        .collect::<Array>();
+
+        let size = [JsValue::from("size"), JsValue::from(">"), JsValue::from(10)]
+            .iter()
+            .collect::<Array>();
+
+        let and = json!({
+            "op": "and",
+            "filters": [us, size]
+        });
+
+        let or = json!({
+            "op": "or",
+            "filters": [eu, and]
+        });
+
+        let x = json!({ "filter": [or] });
+
+        let rec: ViewConfig = x.into_serde_ext().unwrap();
+        let region = |x: &str| {
+            FilterItem::Filter(Filter(
+                "region".to_owned(),
+                FilterOp::EQ,
+                FilterTerm::Scalar(Scalar::String(x.to_owned())),
+            ))
+        };
+
+        let group = FilterGroup {
+            op: FilterReducer::Or,
+            filters: vec![
+                region("EU"),
+                FilterItem::Group(FilterGroup {
+                    op: FilterReducer::And,
+                    filters: vec![
+                        region("US"),
+                        FilterItem::Filter(Filter(
+                            "size".to_owned(),
+                            FilterOp::GT,
+                            FilterTerm::Scalar(Scalar::Float(10_f64)),
+                        )),
+                    ],
+                }),
+            ],
+        };
+
+        assert_eq!(
+            group.to_expression().unwrap(),
+            "(\"region\" == 'EU' or (\"region\" == 'US' and \"size\" > 10))"
+        );
+
+        assert_eq!(rec.filter, vec![FilterItem::Group(group)]);
+        assert_eq!(rec.filter.iter_filters().count(), 3);
+    }
+
     #[wasm_bindgen_test]
     pub fn test_column_sorts() {
         let sort = ["Test", "asc"]
@@ -277,4 +661,40 @@ mod tests {
         assert_eq!(view_config.group_by, vec!["Test".to_owned()]);
         assert_eq!(view_config.split_by, vec!["Test2".to_owned()]);
     }
+
+    #[wasm_bindgen_test]
+    pub fn test_view_config_diff() {
+        let old = ViewConfig {
+            group_by: vec!["Test".to_owned()],
+            columns: vec![Some("Test".to_owned()), Some("Test2".to_owned())],
+            ..ViewConfig::default()
+        };
+
+        let new = ViewConfig {
+            group_by: vec!["Test".to_owned()],
+            split_by: vec!["Test2".to_owned()],
+            columns: vec![Some("Test".to_owned())],
+            top_n: Some(TopN {
+                column: "Test".to_owned(),
+                dir: SortDir::Desc,
+                n: 10.try_into().unwrap(),
+                level: 0,
+            }),
+            ..ViewConfig::default()
+        };
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.patch.group_by, None);
+        assert_eq!(diff.patch.split_by, Some(vec!["Test2".to_owned()]));
+        assert_eq!(diff.patch.columns, Some(vec![Some("Test".to_owned())]));
+        assert_eq!(diff.inverse.split_by, Some(vec![]));
+        assert_eq!(diff.inverse.top_n, OptionalUpdate::SetDefault);
+        assert!(old.diff(&old).patch.is_empty());
+
+        let mut view_config = old.clone();
+        assert!(view_config.apply_update(diff.patch));
+        assert_eq!(view_config, new);
+        assert!(view_config.apply_update(diff.inverse));
+        assert_eq!(view_config, old);
+    }
 }
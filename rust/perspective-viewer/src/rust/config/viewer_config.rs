@@ -9,6 +9,7 @@
 // ┃ This file is part of the Perspective library, distributed under the terms ┃
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::str::FromStr;
 use std::sync::LazyLock;
@@ -16,11 +17,16 @@ use std::sync::LazyLock;
 use flate2::read::ZlibDecoder;
 use flate2::write::ZlibEncoder;
 use flate2::Compression;
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 
+use super::expansion::*;
+use super::link_group::*;
+use super::migrate::*;
+use super::schema::*;
 use super::view_config::*;
 use crate::utils::*;
 
@@ -29,6 +35,7 @@ pub enum ViewerConfigEncoding {
     String,
     ArrayBuffer,
     JSONString,
+    Query,
 }
 
 impl FromStr for ViewerConfigEncoding {
@@ -39,6 +46,7 @@ impl FromStr for ViewerConfigEncoding {
             "json" => Ok(Self::Json),
             "string" => Ok(Self::String),
             "arraybuffer" => Ok(Self::ArrayBuffer),
+            "query" => Ok(Self::Query),
             x => Err(format!("Unknown format \"{}\"", x).into()),
         }
     }
@@ -46,7 +54,7 @@ impl FromStr for ViewerConfigEncoding {
 
 /// The state of an entire `custom_elements::PerspectiveViewerElement` component
 /// and its `Plugin`.
-#[derive(Serialize, PartialEq)]
+#[derive(Clone, Debug, Default, Serialize, PartialEq, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ViewerConfig {
     pub version: String,
@@ -56,6 +64,18 @@ pub struct ViewerConfig {
     pub theme: Option<String>,
     pub title: Option<String>,
 
+    /// Display names for columns, by column name.  These only affect how
+    /// columns are labelled in the UI;  the `ViewConfig` always refers to
+    /// columns by their name in the `Table`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub column_aliases: HashMap<String, String>,
+
+    #[serde(default, skip_serializing_if = "ExpansionState::is_default")]
+    pub expansion: ExpansionState,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_group: Option<LinkGroup>,
+
     #[serde(flatten)]
     pub view_config: ViewConfig,
 }
@@ -70,17 +90,58 @@ type ViewerConfigBinarySerialFormat<'a> = (
     &'a Option<String>,
     &'a Option<String>,
     &'a ViewConfig,
+    &'a HashMap<String, String>,
+    &'a ExpansionState,
+    &'a Option<LinkGroup>,
 );
 
-type ViewerConfigBinaryDeserialFormat = (
-    VersionUpdate,
-    PluginUpdate,
-    Option<Value>,
-    SettingsUpdate,
-    ThemeUpdate,
-    TitleUpdate,
-    ViewConfigUpdate,
-);
+// Filter terms in tokens written before `Scalar::Int` and `Scalar::Date` are
+// plain `f64`s, which decode as `Scalar::Float` and are retyped against the
+// column type when the `Session` validates the config.  Tokens written before
+// `column_aliases`, `expansion` or `link_group` lack the trailing elements,
+// which default to `OptionalUpdate::Missing`.
+#[derive(Deserialize)]
+struct ViewerConfigBinaryDeserialFormat {
+    version: VersionUpdate,
+    plugin: PluginUpdate,
+    plugin_config: Option<Value>,
+    settings: SettingsUpdate,
+    theme: ThemeUpdate,
+    title: TitleUpdate,
+    view_config: ViewConfigUpdate,
+
+    #[serde(default)]
+    column_aliases: ColumnAliasesUpdate,
+
+    #[serde(default)]
+    expansion: ExpansionUpdate,
+
+    #[serde(default)]
+    link_group: LinkGroupUpdate,
+}
+
+// The `ViewConfig` fields in declaration order, which is the order they are
+// written in a binary token.
+const VIEW_CONFIG_FIELDS: [&str; 8] = [
+    "group_by",
+    "split_by",
+    "columns",
+    "filter",
+    "sort",
+    "expressions",
+    "aggregates",
+    "top_n",
+];
+
+/// Options for `restore()`, its optional second argument.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RestoreOptions {
+    /// Reject configs with fields which would otherwise be ignored, such as a
+    /// misspelled `"group-by"`.
+    #[serde(default)]
+    pub strict: bool,
+}
 
 pub static API_VERSION: LazyLock<&'static str> = LazyLock::new(|| {
     #[derive(Deserialize)]
@@ -102,6 +163,9 @@ impl ViewerConfig {
             &self.theme,
             &self.title,
             &self.view_config,
+            &self.column_aliases,
+            &self.expansion,
+            &self.link_group,
         )
     }
 
@@ -128,86 +192,338 @@ impl ViewerConfig {
                     .slice_with_end(start, start + len)
                     .unchecked_into())
             },
+            Some(ViewerConfigEncoding::Query) => Ok(JsValue::from(self.view_config.to_query())),
             Some(ViewerConfigEncoding::JSONString) => {
                 Ok(JsValue::from(serde_json::to_string(self)?))
             },
             None | Some(ViewerConfigEncoding::Json) => Ok(JsValue::from_serde_ext(self)?),
         }
     }
+
+    /// Compute the minimal `ViewerConfigUpdate` which transforms `self` into
+    /// `other`, setting only the fields which differ, as well as the inverse
+    /// update which transforms `other` back into `self`.  Both carry the
+    /// `version` of the config they lead to, so `restore()` does not migrate
+    /// them.
+    pub fn diff(&self, other: &Self) -> ViewerConfigDiff {
+        let view_config = self.view_config.diff(&other.view_config);
+        let mut patch = ViewerConfigUpdate {
+            version: OptionalUpdate::Update(other.version.clone()),
+            view_config: view_config.patch,
+            ..ViewerConfigUpdate::default()
+        };
+
+        let mut inverse = ViewerConfigUpdate {
+            version: OptionalUpdate::Update(self.version.clone()),
+            view_config: view_config.inverse,
+            ..ViewerConfigUpdate::default()
+        };
+
+        Self::_diff(
+            OptionalUpdate::Update(self.plugin.clone()),
+            OptionalUpdate::Update(other.plugin.clone()),
+            &mut patch.plugin,
+            &mut inverse.plugin,
+        );
+        Self::_diff(
+            OptionalUpdate::Update(self.settings),
+            OptionalUpdate::Update(other.settings),
+            &mut patch.settings,
+            &mut inverse.settings,
+        );
+        Self::_diff(
+            self.theme.clone().into(),
+            other.theme.clone().into(),
+            &mut patch.theme,
+            &mut inverse.theme,
+        );
+        Self::_diff(
+            self.title.clone().into(),
+            other.title.clone().into(),
+            &mut patch.title,
+            &mut inverse.title,
+        );
+        Self::_diff(
+            OptionalUpdate::Update(self.column_aliases.clone()),
+            OptionalUpdate::Update(other.column_aliases.clone()),
+            &mut patch.column_aliases,
+            &mut inverse.column_aliases,
+        );
+        Self::_diff(
+            OptionalUpdate::Update(self.expansion.clone()),
+            OptionalUpdate::Update(other.expansion.clone()),
+            &mut patch.expansion,
+            &mut inverse.expansion,
+        );
+        Self::_diff(
+            self.link_group.clone().into(),
+            other.link_group.clone().into(),
+            &mut patch.link_group,
+            &mut inverse.link_group,
+        );
+        if self.plugin_config != other.plugin_config {
+            patch.plugin_config = Some(other.plugin_config.clone());
+            inverse.plugin_config = Some(self.plugin_config.clone());
+        }
+
+        ViewerConfigDiff { patch, inverse }
+    }
+
+    fn _diff<T: Clone + PartialEq>(
+        field: OptionalUpdate<T>,
+        other: OptionalUpdate<T>,
+        patch: &mut OptionalUpdate<T>,
+        inverse: &mut OptionalUpdate<T>,
+    ) {
+        if field != other {
+            *patch = other;
+            *inverse = field;
+        }
+    }
+
+    /// Apply a `ViewerConfigUpdate` as `restore()` would, setting fields which
+    /// are `null` in the update to their default.
+    pub fn apply_update(&mut self, update: ViewerConfigUpdate) {
+        fn apply<T: Clone + Default>(field: &mut T, update: OptionalUpdate<T>) {
+            match update {
+                OptionalUpdate::Update(x) => *field = x,
+                OptionalUpdate::SetDefault => *field = T::default(),
+                OptionalUpdate::Missing => (),
+            }
+        }
+
+        fn apply_option<T: Clone>(field: &mut Option<T>, update: OptionalUpdate<T>) {
+            match update {
+                OptionalUpdate::Update(x) => *field = Some(x),
+                OptionalUpdate::SetDefault => *field = None,
+                OptionalUpdate::Missing => (),
+            }
+        }
+
+        apply(&mut self.version, update.version);
+        apply(&mut self.plugin, update.plugin);
+        apply(&mut self.settings, update.settings);
+        apply(&mut self.column_aliases, update.column_aliases);
+        apply(&mut self.expansion, update.expansion);
+        apply_option(&mut self.theme, update.theme);
+        apply_option(&mut self.title, update.title);
+        apply_option(&mut self.link_group, update.link_group);
+        if let Some(plugin_config) = update.plugin_config {
+            self.plugin_config = plugin_config;
+        }
+
+        self.view_config.apply_update(update.view_config);
+    }
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 // #[serde(deny_unknown_fields)]
 pub struct ViewerConfigUpdate {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
     pub version: VersionUpdate,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
     pub plugin: PluginUpdate,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
     pub title: TitleUpdate,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
+    pub column_aliases: ColumnAliasesUpdate,
+
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
+    pub expansion: ExpansionUpdate,
+
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
+    pub link_group: LinkGroupUpdate,
+
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
     pub theme: ThemeUpdate,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
     pub settings: SettingsUpdate,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub plugin_config: Option<Value>,
 
     #[serde(flatten)]
     pub view_config: ViewConfigUpdate,
 }
 
+/// The difference between two `ViewerConfig`s, as returned by
+/// `ViewerConfig::diff()`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ViewerConfigDiff {
+    /// Transforms the original `ViewerConfig` into the new one.
+    pub patch: ViewerConfigUpdate,
+
+    /// Transforms the new `ViewerConfig` back into the original.
+    pub inverse: ViewerConfigUpdate,
+}
+
 impl ViewerConfigUpdate {
-    fn from_token(
-        (version, plugin, plugin_config, settings, theme, title, view_config): ViewerConfigBinaryDeserialFormat,
-    ) -> ViewerConfigUpdate {
+    /// Whether this update would leave any `ViewerConfig` unchanged, other
+    /// than its `version`.
+    pub fn is_empty(&self) -> bool {
+        *self
+            == Self {
+                version: self.version.clone(),
+                ..Self::default()
+            }
+    }
+
+    fn from_token(token: ViewerConfigBinaryDeserialFormat) -> ViewerConfigUpdate {
         ViewerConfigUpdate {
-            version,
-            plugin,
-            plugin_config,
-            settings,
-            theme,
-            title,
-            view_config,
+            version: token.version,
+            plugin: token.plugin,
+            plugin_config: token.plugin_config,
+            settings: token.settings,
+            theme: token.theme,
+            title: token.title,
+            column_aliases: token.column_aliases,
+            expansion: token.expansion,
+            link_group: token.link_group,
+            view_config: token.view_config,
         }
     }
 
+    /// Decode a zlib-compressed binary token.
+    fn from_token_bytes(bytes: &[u8], strict: bool) -> ApiResult<Self> {
+        let mut decoder = ZlibDecoder::new(bytes);
+        let mut decoded = vec![];
+        decoder.read_to_end(&mut decoded)?;
+        if strict {
+            Self::check_token_fields(&decoded)?;
+        }
+
+        let token = rmp_serde::from_slice(&decoded[..])?;
+        ViewerConfigUpdate::from_token(token).migrate()
+    }
+
     /// Decode a `JsValue` into a `ViewerConfigUpdate` by auto-detecting format
-    /// from JavaScript type.
-    pub fn decode(update: &JsValue) -> ApiResult<Self> {
+    /// from JavaScript type, migrating it from the `version` it was saved with.
+    /// When `strict`, fields which are unknown to `ViewerConfig` are an error
+    /// rather than ignored.
+    pub fn decode(update: &JsValue, strict: bool) -> ApiResult<Self> {
         if update.is_string() {
             let js_str = update.as_string().into_apierror()?;
+
+            // Text queries can be told apart from base64 tokens by their
+            // whitespace and punctuation.
+            let is_base64 = js_str
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '='));
+
+            if !is_base64 {
+                return Ok(ViewerConfigUpdate {
+                    view_config: ViewConfigUpdate::from_query(&js_str)?,
+                    ..ViewerConfigUpdate::default()
+                });
+            }
+
             let bytes = base64::decode(js_str)?;
-            let mut decoder = ZlibDecoder::new(&*bytes);
-            let mut decoded = vec![];
-            decoder.read_to_end(&mut decoded)?;
-            let token = rmp_serde::from_slice(&decoded[..])?;
-            Ok(ViewerConfigUpdate::from_token(token))
+            Self::from_token_bytes(&bytes, strict)
         } else if update.is_instance_of::<js_sys::ArrayBuffer>() {
             let uint8array = js_sys::Uint8Array::new(update);
             let mut slice = vec![0; uint8array.length() as usize];
             uint8array.copy_to(&mut slice[..]);
-            let mut decoder = ZlibDecoder::new(&*slice);
-            let mut decoded = vec![];
-            decoder.read_to_end(&mut decoded)?;
-            let token = rmp_serde::from_slice(&decoded[..])?;
-            Ok(ViewerConfigUpdate::from_token(token))
+            Self::from_token_bytes(&slice, strict)
         } else {
-            Ok(update.into_serde_ext()?)
+            let json: Value = update.into_serde_ext()?;
+            let json = migrate_viewer_config(json)?;
+            if strict {
+                check_unknown_fields(&json, &schemars::schema_for!(ViewerConfig))?;
+            }
+
+            Ok(serde_json::from_value(json)?)
         }
     }
 
+    /// Check a decompressed binary token for unknown fields.  The token is
+    /// positional, so it is named as if it were in the JSON form first, so
+    /// that errors report the same path.
+    fn check_token_fields(token: &[u8]) -> ApiResult<()> {
+        let token: Vec<Value> = rmp_serde::from_slice(token)?;
+        if !(7..=10).contains(&token.len()) {
+            return Err(format!("Expected 10 fields, found {}", token.len()).into());
+        }
+
+        let mut token = token.into_iter();
+        let mut next = || token.next().unwrap_or_default();
+        let (version, plugin, plugin_config, settings, theme, title, view_config) =
+            (next(), next(), next(), next(), next(), next(), next());
+
+        let (column_aliases, expansion, link_group) = (token.next(), token.next(), token.next());
+
+        let mut json = match view_config {
+            Value::Object(map) => map,
+            Value::Array(values) if values.len() > VIEW_CONFIG_FIELDS.len() => {
+                return Err(
+                    format!("Unknown field `view_config[{}]`", VIEW_CONFIG_FIELDS.len()).into(),
+                )
+            },
+            Value::Array(values) => VIEW_CONFIG_FIELDS
+                .iter()
+                .map(|x| x.to_string())
+                .zip(values)
+                .collect(),
+            _ => serde_json::Map::new(),
+        };
+
+        json.insert("version".to_owned(), version);
+        json.insert("plugin".to_owned(), plugin);
+        json.insert("plugin_config".to_owned(), plugin_config);
+        json.insert("settings".to_owned(), settings);
+        json.insert("theme".to_owned(), theme);
+        json.insert("title".to_owned(), title);
+        if let Some(column_aliases) = column_aliases {
+            json.insert("column_aliases".to_owned(), column_aliases);
+        }
+
+        if let Some(expansion) = expansion {
+            json.insert("expansion".to_owned(), expansion);
+        }
+
+        if let Some(link_group) = link_group {
+            json.insert("link_group".to_owned(), link_group);
+        }
+
+        check_unknown_fields(&Value::Object(json), &schemars::schema_for!(ViewerConfig))
+    }
+
+    /// Migrate a `ViewerConfigUpdate` decoded from a binary token to the
+    /// current `API_VERSION`.  Binary tokens are strongly typed except for
+    /// `plugin` and `plugin_config`, so only these fields are migrated.
     pub fn migrate(&self) -> ApiResult<Self> {
-        // TODO: Call the migrate script from js
-        Ok(self.clone())
+        let mut json = serde_json::Map::new();
+        if let OptionalUpdate::Update(version) = &self.version {
+            json.insert("version".to_owned(), Value::from(version.as_str()));
+        }
+
+        if let OptionalUpdate::Update(plugin) = &self.plugin {
+            json.insert("plugin".to_owned(), Value::from(plugin.as_str()));
+        }
+
+        if let Some(plugin_config) = &self.plugin_config {
+            json.insert("plugin_config".to_owned(), plugin_config.clone());
+        }
+
+        let mut json = migrate_viewer_config(Value::Object(json))?;
+        let mut update = self.clone();
+        update.version = VersionUpdate::Update(API_VERSION.to_string());
+        if let Some(Value::String(plugin)) = json.get_mut("plugin").map(Value::take) {
+            update.plugin = PluginUpdate::Update(plugin);
+        }
+
+        if update.plugin_config.is_some() {
+            update.plugin_config = json.get_mut("plugin_config").map(Value::take);
+        }
+
+        Ok(update)
     }
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum OptionalUpdate<T: Clone> {
     SetDefault,
@@ -215,10 +531,19 @@ pub enum OptionalUpdate<T: Clone> {
     Update(T),
 }
 
+impl<T: Clone> OptionalUpdate<T> {
+    pub fn is_missing(&self) -> bool {
+        matches!(self, Self::Missing)
+    }
+}
+
 pub type PluginUpdate = OptionalUpdate<String>;
 pub type SettingsUpdate = OptionalUpdate<bool>;
 pub type ThemeUpdate = OptionalUpdate<String>;
 pub type TitleUpdate = OptionalUpdate<String>;
+pub type ColumnAliasesUpdate = OptionalUpdate<HashMap<String, String>>;
+pub type ExpansionUpdate = OptionalUpdate<ExpansionState>;
+pub type LinkGroupUpdate = OptionalUpdate<LinkGroup>;
 pub type VersionUpdate = OptionalUpdate<String>;
 
 /// Handles `{}` when included as a field with `#[serde(default)]`.
@@ -252,3 +577,197 @@ where
         Option::deserialize(deserializer).map(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+    use crate::config::Scalar;
+
+    fn compress<T: Serialize>(token: &T) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&rmp_serde::to_vec(token).unwrap())
+            .unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_column_aliases_token() {
+        let config = ViewerConfig {
+            version: API_VERSION.to_string(),
+            plugin: "Datagrid".to_owned(),
+            plugin_config: Value::Null,
+            settings: false,
+            theme: None,
+            title: None,
+            column_aliases: HashMap::from([("px".to_owned(), "Price".to_owned())]),
+            expansion: ExpansionState::default(),
+            link_group: None,
+            view_config: ViewConfig::default(),
+        };
+
+        let update =
+            ViewerConfigUpdate::from_token_bytes(&compress(&config.token()), true).unwrap();
+        assert!(matches!(
+            update.column_aliases,
+            OptionalUpdate::Update(x) if x == config.column_aliases
+        ));
+
+        let (version, plugin, plugin_config, settings, theme, title, view_config, ..) =
+            config.token();
+
+        let legacy = (
+            version,
+            plugin,
+            plugin_config,
+            settings,
+            theme,
+            title,
+            view_config,
+        );
+        let update = ViewerConfigUpdate::from_token_bytes(&compress(&legacy), true).unwrap();
+        assert!(matches!(update.column_aliases, OptionalUpdate::Missing));
+        assert!(matches!(update.plugin, OptionalUpdate::Update(x) if x == "Datagrid"));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_expansion_token() {
+        let config = ViewerConfig {
+            version: API_VERSION.to_string(),
+            plugin: "Datagrid".to_owned(),
+            plugin_config: Value::Null,
+            settings: false,
+            theme: None,
+            title: None,
+            column_aliases: HashMap::default(),
+            expansion: ExpansionState {
+                depth: Some(1),
+                overrides: vec![ExpansionOverride {
+                    row_path: vec![Scalar::String("East".to_owned()), Scalar::Float(2.5)],
+                    expanded: false,
+                }],
+            },
+            link_group: None,
+            view_config: ViewConfig::default(),
+        };
+
+        let update =
+            ViewerConfigUpdate::from_token_bytes(&compress(&config.token()), true).unwrap();
+        assert!(matches!(
+            update.expansion,
+            OptionalUpdate::Update(x) if x == config.expansion
+        ));
+
+        let (version, plugin, plugin_config, settings, theme, title, view_config, aliases, ..) =
+            config.token();
+
+        let legacy = (
+            version,
+            plugin,
+            plugin_config,
+            settings,
+            theme,
+            title,
+            view_config,
+            aliases,
+        );
+        let update = ViewerConfigUpdate::from_token_bytes(&compress(&legacy), true).unwrap();
+        assert!(matches!(update.expansion, OptionalUpdate::Missing));
+        assert!(matches!(update.column_aliases, OptionalUpdate::Update(x) if x.is_empty()));
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            json["expansion"],
+            serde_json::json!({"depth": 1, "overrides": [{"row_path": ["East", 2.5], "expanded": false}]})
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_viewer_config_diff() {
+        let old = ViewerConfig {
+            version: API_VERSION.to_string(),
+            plugin: "Datagrid".to_owned(),
+            title: Some("Sales".to_owned()),
+            view_config: ViewConfig {
+                group_by: vec!["State".to_owned()],
+                ..ViewConfig::default()
+            },
+            ..ViewerConfig::default()
+        };
+
+        let new = ViewerConfig {
+            plugin: "Y Bar".to_owned(),
+            plugin_config: serde_json::json!({"legend": true}),
+            settings: true,
+            theme: Some("Pro Dark".to_owned()),
+            title: None,
+            column_aliases: HashMap::from([("px".to_owned(), "Price".to_owned())]),
+            expansion: ExpansionState::to_depth(1),
+            link_group: Some(LinkGroup {
+                name: "sales".to_owned(),
+                columns: HashMap::default(),
+            }),
+            ..old.clone()
+        };
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.patch.plugin,
+            OptionalUpdate::Update("Y Bar".to_owned())
+        );
+        assert_eq!(diff.patch.title, OptionalUpdate::SetDefault);
+        assert_eq!(
+            diff.inverse.title,
+            OptionalUpdate::Update("Sales".to_owned())
+        );
+        assert_eq!(diff.inverse.theme, OptionalUpdate::SetDefault);
+        assert_eq!(diff.inverse.link_group, OptionalUpdate::SetDefault);
+        assert!(diff.patch.view_config.is_empty());
+        assert!(!diff.patch.is_empty());
+        assert!(old.diff(&old).patch.is_empty());
+
+        let json = serde_json::to_value(&diff.patch).unwrap();
+        assert_eq!(json["title"], Value::Null);
+        assert!(json.get("group_by").is_none());
+
+        let mut config = old.clone();
+        config.apply_update(diff.patch);
+        assert_eq!(config, new);
+        config.apply_update(diff.inverse);
+        assert_eq!(config, old);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_link_group_token() {
+        let config = ViewerConfig {
+            version: API_VERSION.to_string(),
+            plugin: "Datagrid".to_owned(),
+            plugin_config: Value::Null,
+            settings: false,
+            theme: None,
+            title: None,
+            column_aliases: HashMap::default(),
+            expansion: ExpansionState::default(),
+            link_group: Some(LinkGroup {
+                name: "sales".to_owned(),
+                columns: HashMap::from([("State".to_owned(), "state".to_owned())]),
+            }),
+            view_config: ViewConfig::default(),
+        };
+
+        let update =
+            ViewerConfigUpdate::from_token_bytes(&compress(&config.token()), true).unwrap();
+        assert_eq!(
+            update.link_group,
+            OptionalUpdate::Update(config.link_group.clone().unwrap())
+        );
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            json["link_group"],
+            serde_json::json!({"name": "sales", "columns": {"State": "state"}})
+        );
+    }
+}
@@ -22,16 +22,24 @@ use yew::*;
 
 use crate::components::filter_dropdown::*;
 use crate::custom_elements::modal::*;
-use crate::session::Session;
+use crate::session::{ColumnValuesOptions, Session};
 use crate::utils::ApiFuture;
 use crate::*;
 
+/// Columns with more distinct values than this are searched by the engine as
+/// the user types (by prefix), rather than fetched once and searched locally.
+const MAX_CACHED_VALUES: usize = 1000;
+
+const MAX_SUGGESTIONS: usize = 10;
+
 #[wasm_bindgen]
 #[derive(Clone)]
 pub struct FilterDropDownElement {
     modal: ModalElement<FilterDropDown>,
     session: Session,
     column: Rc<RefCell<Option<(usize, String)>>>,
+
+    /// All of `column`'s values, or `None` if it has too many to cache.
     values: Rc<RefCell<Option<Vec<String>>>>,
     target: Rc<RefCell<Option<HtmlElement>>>,
 }
@@ -82,8 +90,9 @@ impl FilterDropDownElement {
         callback: Callback<String>,
     ) {
         let current_column = self.column.borrow().clone();
+        let is_cached = self.values.borrow().is_some();
         match current_column {
-            Some(filter_col) if filter_col == column => {
+            Some(filter_col) if filter_col == column && is_cached => {
                 let values = filter_values(&input, &self.values, &exclude);
                 if values.len() == 1 && values[0] == input {
                     self.hide().unwrap();
@@ -94,6 +103,20 @@ impl FilterDropDownElement {
                     ]);
                 }
             },
+            Some(filter_col) if filter_col == column => {
+                ApiFuture::spawn({
+                    clone!(self.modal, self.session);
+                    async move {
+                        let values = search_values(&session, column.1, &input, &exclude).await?;
+                        modal.send_message_batch(vec![
+                            FilterDropDownMsg::SetCallback(callback),
+                            FilterDropDownMsg::SetValues(values),
+                        ]);
+
+                        Ok(())
+                    }
+                });
+            },
             _ => {
                 // TODO is this a race condition? `column` and `values` are out-of-sync
                 // across an `await` point.
@@ -102,9 +125,24 @@ impl FilterDropDownElement {
                 ApiFuture::spawn({
                     clone!(self.modal, self.session, self.values);
                     async move {
-                        let all_values = session.get_column_values(column.1).await?;
-                        *values.borrow_mut() = Some(all_values);
-                        let filter_values = filter_values(&input, &values, &exclude);
+                        let options = ColumnValuesOptions {
+                            limit: Some(MAX_CACHED_VALUES),
+                            ..ColumnValuesOptions::default()
+                        };
+
+                        let page = session
+                            .get_column_values(column.1.clone(), &options)
+                            .await?;
+                        let filter_values = if page.total > MAX_CACHED_VALUES {
+                            *values.borrow_mut() = None;
+                            search_values(&session, column.1, &input, &exclude).await?
+                        } else {
+                            let all_values = page.values.into_iter();
+                            *values.borrow_mut() =
+                                Some(all_values.map(|x| x.value.to_string()).collect());
+                            filter_values(&input, &values, &exclude)
+                        };
+
                         if filter_values.len() == 1 && filter_values[0] == input {
                             modal.hide()
                         } else {
@@ -152,10 +190,33 @@ fn filter_values(
         values
             .iter()
             .filter(|x| x.to_lowercase().contains(&input) && !exclude.contains(x.as_str()))
-            .take(10)
+            .take(MAX_SUGGESTIONS)
             .cloned()
             .collect::<Vec<String>>()
     } else {
         vec![]
     }
 }
+
+/// Search a column with too many values to cache, by prefix.
+async fn search_values(
+    session: &Session,
+    column: String,
+    input: &str,
+    exclude: &HashSet<String>,
+) -> ApiResult<Vec<String>> {
+    let options = ColumnValuesOptions {
+        search: Some(input.to_owned()),
+        limit: Some(MAX_SUGGESTIONS + exclude.len()),
+        ..ColumnValuesOptions::default()
+    };
+
+    let page = session.get_column_values(column, &options).await?;
+    Ok(page
+        .values
+        .into_iter()
+        .map(|x| x.value.to_string())
+        .filter(|x| !exclude.contains(x))
+        .take(MAX_SUGGESTIONS)
+        .collect())
+}
@@ -29,7 +29,7 @@ use crate::js::*;
 use crate::model::*;
 use crate::presentation::*;
 use crate::renderer::*;
-use crate::session::Session;
+use crate::session::{ColumnValuesOptions, LoadOptions, Session, TableSwapReport};
 use crate::utils::*;
 use crate::*;
 
@@ -60,18 +60,21 @@ pub struct PerspectiveViewerElement {
     root: Rc<RefCell<Option<AppHandle<PerspectiveViewer>>>>,
     resize_handle: Rc<RefCell<Option<ResizeObserverHandle>>>,
     intersection_handle: Rc<RefCell<Option<IntersectionObserverHandle>>>,
+    link_handle: Rc<RefCell<Option<LinkGroupHandle>>>,
+    _undo_redo_handle: Rc<UndoRedoKeyHandle>,
     session: Session,
     renderer: Renderer,
     presentation: Presentation,
     _events: CustomEvents,
-    _subscriptions: Rc<Subscription>,
+    _subscriptions: Rc<[Subscription; 3]>,
 }
 
 derive_model!(Renderer, Session, Presentation for PerspectiveViewerElement);
 
 impl CustomElementMetadata for PerspectiveViewerElement {
     const CUSTOM_ELEMENT_NAME: &'static str = "perspective-viewer";
-    const STATICS: &'static [&'static str] = ["registerPlugin", "getExprTKCommands"].as_slice();
+    const STATICS: &'static [&'static str] =
+        ["registerPlugin", "getExprTKCommands", "getConfigSchema"].as_slice();
 }
 
 #[wasm_bindgen]
@@ -107,13 +110,40 @@ impl PerspectiveViewerElement {
         // Create callbacks
         let update_sub = session.table_updated.add_listener({
             clone!(renderer, session);
-            move |_| {
+            move |delta| {
                 clone!(renderer, session);
-                ApiFuture::spawn(async move { renderer.update(&session).await })
+                ApiFuture::spawn(async move {
+                    if session.get_view().is_some() {
+                        renderer.update_with_delta(&session, delta).await
+                    } else {
+                        // The update invalidated the `View`, e.g. by changing
+                        // the rows selected by its `top_n`.
+                        let valid = session.validate().await?;
+                        renderer.draw(valid.create_view()).await
+                    }
+                })
+            }
+        });
+
+        let plugin_sub = renderer.plugin_changed.add_listener({
+            clone!(session);
+            move |plugin: JsPerspectiveViewerPlugin| {
+                session.set_delta_updates(plugin.delta_updates().unwrap_or_default())
+            }
+        });
+
+        let aliases_sub = presentation.column_aliases_changed.add_listener({
+            clone!(renderer, presentation);
+            move |_| {
+                if let Err(err) = renderer.set_column_aliases(presentation.get_column_aliases()) {
+                    web_sys::console::error_1(&err.into());
+                }
             }
         });
 
         let resize_handle = ResizeObserverHandle::new(&elem, &renderer, &root);
+        let undo_redo_handle = UndoRedoKeyHandle::new(&elem, &session, &renderer, &presentation);
+
         Self {
             elem,
             root: Rc::new(RefCell::new(Some(root))),
@@ -122,8 +152,10 @@ impl PerspectiveViewerElement {
             presentation,
             resize_handle: Rc::new(RefCell::new(Some(resize_handle))),
             intersection_handle: Rc::new(RefCell::new(None)),
+            link_handle: Rc::new(RefCell::new(None)),
+            _undo_redo_handle: Rc::new(undo_redo_handle),
             _events: events,
-            _subscriptions: Rc::new(update_sub),
+            _subscriptions: Rc::new([update_sub, plugin_sub, aliases_sub]),
         }
     }
 
@@ -136,7 +168,14 @@ impl PerspectiveViewerElement {
     /// `<perspective-viewer>` has accepted either a `Promise` or `Table` as an
     /// argument, so we preserve that behavior here with some loss of type
     /// precision.
-    pub fn load(&self, table: JsValue) -> ApiFuture<()> {
+    ///
+    /// # Arguments
+    /// - `table` A `Table`, or a `Promise` which resolves to one.
+    /// - `options` An optional `LoadOptions`, e.g. `{preserve_config: true,
+    ///   renames: {px: "price"}}` to map the current config onto a `Table`
+    ///   whose columns have been renamed.  With `preserve_config`, resolves to
+    ///   a `TableSwapReport` of the schema changes and dropped config entries.
+    pub fn load(&self, table: JsValue, options: JsValue) -> ApiFuture<JsValue> {
         tracing::info!("Loading Table");
         let promise = table
             .clone()
@@ -152,8 +191,16 @@ impl PerspectiveViewerElement {
             .set_update_column_defaults(&mut config, &self.renderer.metadata());
 
         self.session.update_view_config(config);
-        clone!(self.renderer, self.session);
+        clone!(self.renderer, self.session, self.presentation);
         ApiFuture::new(async move {
+            let options: LoadOptions = if options.is_undefined() {
+                LoadOptions::default()
+            } else {
+                options.into_serde_ext()?
+            };
+
+            let mut schema_diff = None;
+            let mut dropped = None;
             let task = async {
                 let table = JsFuture::from(promise)
                     .await?
@@ -165,12 +212,32 @@ impl PerspectiveViewerElement {
                 );
 
                 session.reset_stats();
-                session.set_table(table).await?;
-                session.validate().await?.create_view().await
+                if options.preserve_config {
+                    let diff = session.swap_table(table, &options.renames).await?;
+                    if !diff.renamed_columns.is_empty() {
+                        presentation.rename_column_aliases(&diff.renames());
+                        renderer.rename_plugin_columns(&diff.renames())?;
+                    }
+
+                    schema_diff = Some(diff);
+                } else {
+                    session.set_table(table).await?;
+                }
+
+                let valid = session.validate().await?;
+                dropped = Some(valid.report().clone());
+                valid.create_view().await
             };
 
             renderer.set_throttle(None);
-            renderer.draw(task).await
+            renderer.draw(task).await?;
+            match schema_diff {
+                Some(schema) => Ok(JsValue::from_serde_ext(&TableSwapReport {
+                    schema,
+                    dropped: dropped.unwrap_or_default(),
+                })?),
+                None => Ok(JsValue::UNDEFINED),
+            }
         })
     }
 
@@ -180,8 +247,9 @@ impl PerspectiveViewerElement {
     /// callee).  Allowing a `<perspective-viewer>` to be garbage-collected
     /// without calling `delete()` will leak WASM memory.
     pub fn delete(&mut self) -> ApiFuture<bool> {
-        clone!(self.renderer, self.session, self.root);
+        clone!(self.renderer, self.session, self.root, self.link_handle);
         ApiFuture::new(self.renderer.clone().with_lock(async move {
+            link_handle.borrow_mut().take();
             renderer.delete()?;
             let result = session.delete();
             root.borrow_mut()
@@ -250,16 +318,34 @@ impl PerspectiveViewerElement {
     }
 
     /// Restores this element from a full/partial `JsPerspectiveViewConfig`.
+    /// Entries of the config which are invalid for this element's `Table` are
+    /// skipped, and the resulting `ViewConfigReport` is returned (or
+    /// `undefined` if there were none).
     ///
     /// # Arguments
     /// - `update` The config to restore to, as returned by `.save()` in either
-    ///   "json", "string" or "arraybuffer" format.
-    pub fn restore(&self, update: JsValue) -> ApiFuture<()> {
+    ///   "json", "string", "arraybuffer" or "query" format.
+    /// - `options` An optional `RestoreOptions`, e.g. `{strict: true}` to
+    ///   reject `update` if it has any unknown fields.
+    pub fn restore(&self, update: JsValue, options: JsValue) -> ApiFuture<JsValue> {
         tracing::info!("Restoring ViewerConfig");
         global::document().blur_active_element();
-        clone!(self.session, self.renderer, self.root, self.presentation);
+        clone!(
+            self.elem,
+            self.session,
+            self.renderer,
+            self.root,
+            self.presentation,
+            self.link_handle
+        );
         ApiFuture::new(async move {
-            let decoded_update = ViewerConfigUpdate::decode(&update)?;
+            let options: RestoreOptions = if options.is_undefined() {
+                RestoreOptions::default()
+            } else {
+                options.into_serde_ext()?
+            };
+
+            let decoded_update = ViewerConfigUpdate::decode(&update, options.strict)?;
 
             let ViewerConfigUpdate {
                 plugin,
@@ -267,6 +353,9 @@ impl PerspectiveViewerElement {
                 settings,
                 theme: theme_name,
                 title,
+                column_aliases,
+                expansion,
+                link_group,
                 mut view_config,
                 ..//version
             } = decoded_update;
@@ -283,6 +372,29 @@ impl PerspectiveViewerElement {
                 presentation.set_title(None);
             }
 
+            match column_aliases {
+                OptionalUpdate::Update(aliases) => presentation.set_column_aliases(aliases),
+                OptionalUpdate::SetDefault => presentation.set_column_aliases(Default::default()),
+                OptionalUpdate::Missing => (),
+            }
+
+            match link_group {
+                OptionalUpdate::Update(group)
+                    if session.get_link_group().as_ref() != Some(&group) =>
+                {
+                    let handle = LinkGroupHandle::new(&elem, &session, &renderer, group.clone());
+                    *link_handle.borrow_mut() = Some(handle);
+                    session.set_link_group(Some(group));
+                    session.set_linked_filter(vec![]);
+                },
+                OptionalUpdate::SetDefault => {
+                    link_handle.borrow_mut().take();
+                    session.set_link_group(None);
+                    session.set_linked_filter(vec![]);
+                },
+                _ => (),
+            }
+
             let needs_restyle = match theme_name {
                 OptionalUpdate::SetDefault => {
                     let current_name = presentation.get_selected_theme_name().await;
@@ -311,6 +423,7 @@ impl PerspectiveViewerElement {
             }
 
             session.update_view_config(view_config);
+            let mut report = None;
             let draw_task = renderer.draw(async {
                 let task = root
                     .borrow()
@@ -321,13 +434,10 @@ impl PerspectiveViewerElement {
                     });
 
                 let internal_task = async {
-                    let plugin = renderer.get_active_plugin()?;
-                    if let Some(plugin_config) = &plugin_config {
-                        let js_config = JsValue::from_serde_ext(plugin_config)?;
-                        plugin.restore(&js_config);
-                    }
-
-                    session.validate().await?.create_view().await
+                    renderer.restore_plugin(plugin_config.as_ref(), expansion)?;
+                    let valid = session.validate().await?;
+                    report = Some(valid.report().clone());
+                    valid.create_view().await
                 }
                 .await;
 
@@ -344,7 +454,27 @@ impl PerspectiveViewerElement {
                 renderer.restyle_all(&view).await?;
             }
 
-            Ok(())
+            match report {
+                Some(report) if !report.is_empty() => Ok(JsValue::from_serde_ext(&report)?),
+                _ => Ok(JsValue::UNDEFINED),
+            }
+        })
+    }
+
+    /// Expand the `group_by` rows of this element's plugin to `depth`, such
+    /// that `0` shows only the top-level groups, replacing any rows expanded
+    /// or collapsed individually.  This is saved as the `expansion` field of
+    /// the `ViewerConfig`.
+    ///
+    /// # Arguments
+    /// - `depth` The number of `group_by` levels to expand.
+    #[wasm_bindgen(js_name = "expandToDepth")]
+    pub fn expand_to_depth(&self, depth: usize) -> ApiFuture<()> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            let expansion = ExpansionState::to_depth(depth);
+            renderer.restore_plugin(None, ExpansionUpdate::Update(expansion))?;
+            renderer.draw(async { Ok(&session) }).await
         })
     }
 
@@ -352,7 +482,8 @@ impl PerspectiveViewerElement {
     /// via the `.restore()` method.
     ///
     /// # Arguments
-    /// - `format` Supports "json" (default), "arraybuffer" or "string".
+    /// - `format` Supports "json" (default), "arraybuffer", "string" or
+    ///   "query".
     pub fn save(&self, format: Option<String>) -> ApiFuture<JsValue> {
         let viewer_config_task = self.get_viewer_config();
         ApiFuture::new(async move {
@@ -366,6 +497,64 @@ impl PerspectiveViewerElement {
         })
     }
 
+    /// Compute the minimal difference between two configs, as a `{patch,
+    /// inverse}` pair of partial configs which can be passed to `restore()`
+    /// to move from `from` to `to` and back, respectively.
+    ///
+    /// # Arguments
+    /// - `from` The original config, in any format returned by `save()`.
+    /// - `to` The new config, in any format returned by `save()`, or
+    ///   `undefined` for this viewer's current config.
+    pub fn diff(&self, from: JsValue, to: JsValue) -> ApiFuture<JsValue> {
+        let decode = |update: &JsValue| -> ApiResult<ViewerConfig> {
+            let mut config = ViewerConfig::default();
+            config.apply_update(ViewerConfigUpdate::decode(update, false)?);
+            Ok(config)
+        };
+
+        let from = decode(&from);
+        let to = if to.is_undefined() || to.is_null() {
+            None
+        } else {
+            Some(decode(&to))
+        };
+
+        let viewer_config_task = self.get_viewer_config();
+        ApiFuture::new(async move {
+            let to = match to {
+                Some(to) => to?,
+                None => viewer_config_task.await?,
+            };
+
+            Ok(JsValue::from_serde_ext(&from?.diff(&to))?)
+        })
+    }
+
+    /// Revert the last change to this viewer's config, as recorded by the
+    /// `"perspective-config-update"` event.  Changes made in quick succession
+    /// are undone together.  Resolves to `false` if there was nothing to undo.
+    pub fn undo(&self) -> ApiFuture<bool> {
+        UndoRedoModel::undo(self)
+    }
+
+    /// Re-apply the last change reverted by `undo()`.  Resolves to `false` if
+    /// there was nothing to redo.
+    pub fn redo(&self) -> ApiFuture<bool> {
+        UndoRedoModel::redo(self)
+    }
+
+    /// Whether there is a change which `undo()` can revert.
+    #[wasm_bindgen(js_name = "canUndo")]
+    pub fn can_undo(&self) -> bool {
+        self.presentation.can_undo()
+    }
+
+    /// Whether there is a change which `redo()` can re-apply.
+    #[wasm_bindgen(js_name = "canRedo")]
+    pub fn can_redo(&self) -> bool {
+        self.presentation.can_redo()
+    }
+
     /// Download this viewer's `View` or `Table` data as a `.csv` file.
     ///
     /// # Arguments
@@ -400,6 +589,66 @@ impl PerspectiveViewerElement {
         ApiFuture::new(copy_task)
     }
 
+    /// Set the table name which the SQL export's query selects from, or reset
+    /// it to the default (this viewer's title).
+    ///
+    /// # Arguments
+    /// - `name` The table name, which may be schema-qualified with `.`.
+    #[wasm_bindgen(js_name = "setSqlTableName")]
+    pub fn set_sql_table_name(&self, name: Option<String>) {
+        self.presentation.set_sql_table_name(name)
+    }
+
+    /// Get the display names for columns set by the `column_aliases` field of
+    /// the config, keyed by column name, so plugins may label columns the same
+    /// way as the settings panel does.
+    #[wasm_bindgen(js_name = "getColumnAliases")]
+    pub fn get_column_aliases(&self) -> ApiResult<JsValue> {
+        Ok(JsValue::from_serde_ext(
+            &*self.presentation.get_column_aliases(),
+        )?)
+    }
+
+    /// Get the distinct values of a column (or expression column) of the
+    /// `Table`, in ascending order, with the number of rows with each value.
+    ///
+    /// # Arguments
+    /// - `column` The column name.
+    /// - `options` An optional `ColumnValuesOptions`, e.g. `{search: "ab",
+    ///   limit: 10}` for the first 10 values which begin with `"ab"`.
+    #[wasm_bindgen(js_name = "getColumnValues")]
+    pub fn get_column_values(&self, column: String, options: JsValue) -> ApiFuture<JsValue> {
+        clone!(self.session);
+        ApiFuture::new(async move {
+            let options: ColumnValuesOptions = if options.is_undefined() {
+                ColumnValuesOptions::default()
+            } else {
+                options.into_serde_ext()?
+            };
+
+            session.await_table().await?;
+            let values = session.get_column_values(column, &options).await?;
+            Ok(JsValue::from_serde_ext(&values)?)
+        })
+    }
+
+    /// Get summary statistics of a column (or expression column) of the
+    /// `Table`: the row, `null` and distinct value counts, the `min` and `max`
+    /// and, for numeric columns, the `mean` and a `histogram`.  Statistics are
+    /// cached until the `Table` is updated.
+    ///
+    /// # Arguments
+    /// - `column` The column name.
+    #[wasm_bindgen(js_name = "getColumnStats")]
+    pub fn get_column_stats(&self, column: String) -> ApiFuture<JsValue> {
+        clone!(self.session);
+        ApiFuture::new(async move {
+            session.await_table().await?;
+            let stats = session.get_column_stats(column).await?;
+            Ok(JsValue::from_serde_ext(&stats)?)
+        })
+    }
+
     /// Reset the viewer's `ViewerConfig` to the default.
     ///
     /// # Arguments
@@ -474,6 +723,23 @@ impl PerspectiveViewerElement {
         }
     }
 
+    /// Pause this viewer, deleting its `View` and ignoring render calls.  The
+    /// number of `Table` updates since pausing is shown in the status bar
+    /// until `resume()` is called.
+    pub fn pause(&self) {
+        self.session.set_pause(true);
+    }
+
+    /// Resume a paused viewer, rendering once to catch up with the updates
+    /// which arrived while it was paused.
+    pub fn resume(&self) -> ApiFuture<()> {
+        if self.session.set_pause(false) {
+            self.update_and_render(ViewConfigUpdate::default())
+        } else {
+            ApiFuture::new(async { Ok(()) })
+        }
+    }
+
     /// Get this viewer's edit port for the currently loaded `Table`.
     #[wasm_bindgen(js_name = "getEditPort")]
     pub fn get_edit_port(&self) -> Result<f64, JsValue> {
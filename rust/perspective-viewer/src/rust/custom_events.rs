@@ -23,7 +23,7 @@ use crate::js::JsPerspectiveViewerPlugin;
 use crate::model::*;
 use crate::presentation::Presentation;
 use crate::renderer::*;
-use crate::session::Session;
+use crate::session::{Session, ViewConfigReport};
 use crate::utils::*;
 use crate::*;
 
@@ -32,7 +32,7 @@ use crate::*;
 /// on `CustomElements`, but when it is `drop()` the Custom Element will no
 /// longer dispatch events such as `"perspective-config-change"`.
 #[derive(Clone)]
-pub struct CustomEvents(Rc<(CustomEventsDataRc, [Subscription; 5])>);
+pub struct CustomEvents(Rc<(CustomEventsDataRc, [Subscription; 8])>);
 
 impl ImplicitClone for CustomEvents {}
 impl PartialEq for CustomEvents {
@@ -113,12 +113,30 @@ impl CustomEvents {
             }
         });
 
+        let table_sub = session.table_loaded.add_listener({
+            clone!(data);
+            move |_| data.presentation.clear_history()
+        });
+
+        let invalid_sub = session.config_invalid.add_listener({
+            clone!(data);
+            move |report| data.dispatch_config_error(&report)
+        });
+
+        let aliases_sub = presentation.column_aliases_changed.add_listener({
+            clone!(data);
+            move |_| data.clone().dispatch_config_update()
+        });
+
         Self(Rc::new((data, [
             theme_sub,
             settings_sub,
             column_settings_sub,
             plugin_sub,
             view_sub,
+            table_sub,
+            invalid_sub,
+            aliases_sub,
         ])))
     }
 
@@ -162,6 +180,15 @@ impl CustomEventsDataRc {
         self.elem.dispatch_event(&event.unwrap()).unwrap();
     }
 
+    fn dispatch_config_error(&self, report: &ViewConfigReport) {
+        let mut event_init = web_sys::CustomEventInit::new();
+        event_init.detail(&JsValue::from_serde_ext(report).unwrap());
+        let event =
+            web_sys::CustomEvent::new_with_event_init_dict("perspective-config-error", &event_init);
+
+        self.elem.dispatch_event(&event.unwrap()).unwrap();
+    }
+
     fn dispatch_plugin_changed(&self, plugin: &JsPerspectiveViewerPlugin) {
         let mut event_init = web_sys::CustomEventInit::new();
         event_init.detail(plugin);
@@ -179,16 +206,31 @@ impl CustomEventsDataRc {
             if viewer_config.view_config != Default::default()
                 && Some(&viewer_config) != self.last_dispatched.borrow().as_ref()
             {
-                let json_config = JsValue::from_serde_ext(&viewer_config)?;
+                let diff = self
+                    .last_dispatched
+                    .borrow()
+                    .as_ref()
+                    .map(|x| x.diff(&viewer_config))
+                    .unwrap_or_else(|| ViewerConfig::default().diff(&viewer_config));
+
                 let mut event_init = web_sys::CustomEventInit::new();
-                event_init.detail(&json_config);
+                event_init.detail(&JsValue::from_serde_ext(&viewer_config)?);
                 let event = web_sys::CustomEvent::new_with_event_init_dict(
                     "perspective-config-update",
                     &event_init,
                 );
 
+                let mut diff_event_init = web_sys::CustomEventInit::new();
+                diff_event_init.detail(&JsValue::from_serde_ext(&diff.patch)?);
+                let diff_event = web_sys::CustomEvent::new_with_event_init_dict(
+                    "perspective-config-diff",
+                    &diff_event_init,
+                );
+
+                self.presentation.record_history(&viewer_config);
                 *self.last_dispatched.borrow_mut() = Some(viewer_config);
                 self.elem.dispatch_event(&event.unwrap()).unwrap();
+                self.elem.dispatch_event(&diff_event.unwrap()).unwrap();
             }
 
             Ok(())
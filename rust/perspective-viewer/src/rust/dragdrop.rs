@@ -42,6 +42,10 @@ pub enum DragEffect {
 struct DragFrom {
     column: String,
     effect: DragEffect,
+
+    /// The index of the dragged entry, for lists which may contain the same
+    /// column more than once (e.g. `columns`).
+    index: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -72,7 +76,9 @@ impl DragState {
 #[derive(Default)]
 pub struct DragDropState {
     drag_state: RefCell<DragState>,
-    pub drop_received: PubSub<(String, DragTarget, DragEffect, usize)>,
+    /// The dragged column, the drop target, the drag effect, the drop index and
+    /// the index of the dragged entry, if known.
+    pub drop_received: PubSub<(String, DragTarget, DragEffect, usize, Option<usize>)>,
     pub dragstart_received: PubSub<DragEffect>,
     pub dragend_received: PubSub<()>,
 }
@@ -174,9 +180,13 @@ impl DragDrop {
         event.stop_propagation();
         let action = match &*self.drag_state.borrow() {
             DragState::DragOverInProgress(
-                DragFrom { column, effect },
+                DragFrom {
+                    column,
+                    effect,
+                    index: from_index,
+                },
                 DragOver { target, index },
-            ) => Some((column.to_string(), *target, *effect, *index)),
+            ) => Some((column.to_string(), *target, *effect, *index, *from_index)),
             _ => None,
         };
 
@@ -188,7 +198,19 @@ impl DragDrop {
 
     /// Start the drag/drop action with the name of the column being dragged.
     pub fn notify_drag_start(&self, column: String, effect: DragEffect) {
-        *self.drag_state.borrow_mut() = DragState::DragInProgress(DragFrom { column, effect });
+        self.notify_drag_start_at(column, effect, None)
+    }
+
+    /// Start the drag/drop action with the name and index of the column entry
+    /// being dragged, which identifies the entry when the column occurs more
+    /// than once.
+    pub fn notify_drag_start_at(&self, column: String, effect: DragEffect, index: Option<usize>) {
+        *self.drag_state.borrow_mut() = DragState::DragInProgress(DragFrom {
+            column,
+            effect,
+            index,
+        });
+
         let emit = self.dragstart_received.callback();
         ApiFuture::spawn(async move {
             request_animation_frame().await;
@@ -210,14 +232,18 @@ impl DragDrop {
     pub fn notify_drag_leave(&self, drag_target: DragTarget) {
         let reset = match *self.drag_state.borrow() {
             DragState::DragOverInProgress(
-                DragFrom { ref column, effect },
+                DragFrom {
+                    ref column,
+                    effect,
+                    index,
+                },
                 DragOver { target, .. },
-            ) if target == drag_target => Some((column.clone(), effect)),
+            ) if target == drag_target => Some((column.clone(), effect, index)),
             _ => None,
         };
 
-        if let Some((column, effect)) = reset {
-            self.notify_drag_start(column, effect);
+        if let Some((column, effect, index)) = reset {
+            self.notify_drag_start_at(column, effect, index);
         }
     }
 
@@ -11,7 +11,7 @@
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
 use itertools::Itertools;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 
@@ -106,12 +106,25 @@ extern "C" {
         this: &JsPerspectiveView,
     ) -> ApiResult<JsValue>;
 
+    #[wasm_bindgen(method, catch, js_name = to_columns)]
+    pub async fn _to_columns_with_options(
+        this: &JsPerspectiveView,
+        options: js_sys::Object,
+    ) -> ApiResult<JsValue>;
+
     #[wasm_bindgen(method, catch, js_name = dimensions)]
     pub async fn _dimensions(this: &JsPerspectiveView) -> ApiResult<JsValue>;
 
     #[wasm_bindgen(method)]
     pub fn on_update(this: &JsPerspectiveView, callback: &js_sys::Function);
 
+    #[wasm_bindgen(method, js_name = on_update)]
+    pub fn on_update_with_options(
+        this: &JsPerspectiveView,
+        callback: &js_sys::Function,
+        options: js_sys::Object,
+    );
+
     #[wasm_bindgen(method)]
     pub fn remove_update(this: &JsPerspectiveView, callback: &js_sys::Function);
 
@@ -183,6 +196,8 @@ impl JsPerspectiveView {
 
     async_typed!(_to_columns, to_columns(&self) -> js_sys::Object);
 
+    async_typed!(_to_columns_with_options, to_columns_with_options(&self, options: js_sys::Object) -> js_sys::Object);
+
     async_typed!(_dimensions, dimensions(&self) ->  JsPerspectiveViewDimensions);
 
     async_typed!(_schema, schema(&self) -> JsPerspectiveViewSchema);
@@ -202,7 +217,7 @@ impl JsPerspectiveView {
     }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 #[serde()]
 pub struct PerspectiveValidationError {
     pub error_message: String,
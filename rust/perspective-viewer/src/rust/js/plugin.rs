@@ -57,6 +57,12 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn priority(this: &JsPerspectiveViewerPlugin) -> Option<i32>;
 
+    #[wasm_bindgen(method, getter)]
+    pub fn delta_updates(this: &JsPerspectiveViewerPlugin) -> Option<bool>;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn persist_expansion(this: &JsPerspectiveViewerPlugin) -> Option<bool>;
+
     #[wasm_bindgen(method)]
     pub fn save(this: &JsPerspectiveViewerPlugin) -> JsValue;
 
@@ -87,7 +93,8 @@ extern "C" {
         view: &JsPerspectiveView,
         column_limit: Option<usize>,
         row_limit: Option<usize>,
-        force: bool
+        force: bool,
+        deltas: Option<js_sys::Array>
     ) -> ApiResult<()>;
 
     #[wasm_bindgen(method, catch)]
@@ -124,6 +131,10 @@ pub struct ViewConfigRequirements {
     pub max_cells: Option<usize>,
     pub name: String,
     pub render_warning: bool,
+
+    /// Whether the plugin's `update()` accepts the row deltas of the `Table`
+    /// updates since the last draw, see `Renderer::update_with_delta()`.
+    pub delta_updates: bool,
 }
 
 impl ViewConfigRequirements {
@@ -147,6 +158,7 @@ impl JsPerspectiveViewerPlugin {
             max_cells: self.max_cells(),
             name: self.name(),
             render_warning: self.render_warning().unwrap_or(true),
+            delta_updates: self.delta_updates().unwrap_or_default(),
         })
     }
 }
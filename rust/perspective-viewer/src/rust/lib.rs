@@ -63,6 +63,12 @@ pub fn js_get_exprtk_commands() -> ApiResult<Box<[JsValue]>> {
     })
 }
 
+/// Export JSON Schema for the persisted config types, keyed by type name.
+#[wasm_bindgen(js_name = "getConfigSchema")]
+pub fn js_get_config_schema() -> ApiResult<JsValue> {
+    Ok(JsValue::from_serde_ext(&crate::config::get_config_schemas())?)
+}
+
 /// Register this crate's Custom Elements in the browser's current session.
 /// This must occur before calling any public API methods on these Custom
 /// Elements from JavaScript, as the methods themselves won't be defined yet.
@@ -23,6 +23,7 @@ use super::get_viewer_config::*;
 use super::structural::*;
 use crate::config::*;
 use crate::js::JsPerspectiveViewerPlugin;
+use crate::session::ColumnValuesOptions;
 use crate::utils::*;
 
 fn tag_name_to_package(plugin: &JsPerspectiveViewerPlugin) -> String {
@@ -84,6 +85,32 @@ pub trait CopyExportModel:
         })
     }
 
+    /// Create a blob of this viewer's `ViewConfig` translated to SQL, querying
+    /// `split_by` columns' distinct values to expand them into conditional
+    /// aggregates.
+    fn sql_as_jsvalue(&self) -> ApiFuture<web_sys::Blob> {
+        let session = self.session().clone();
+        let table = self.presentation().get_sql_table_name();
+        ApiFuture::new(async move {
+            let view_config = session.get_view_config().clone();
+            let mut split_values = vec![];
+            for name in view_config.split_by.iter() {
+                let options = ColumnValuesOptions::default();
+                let values = session.get_column_values(name.clone(), &options).await?;
+                split_values.push(values.values.into_iter().map(|x| x.value).collect());
+            }
+
+            let metadata = session.metadata();
+            let sql = view_config.to_sql(
+                &table,
+                |name| metadata.get_column_table_type(name),
+                &split_values,
+            );
+
+            js_sys::JsString::from(sql).as_blob()
+        })
+    }
+
     /// Generate a result `Blob` for all types of `ExportMethod`.
     fn export_method_to_jsvalue(&self, method: ExportMethod) -> ApiFuture<web_sys::Blob> {
         match method {
@@ -129,6 +156,10 @@ pub trait CopyExportModel:
                         .as_blob()
                 })
             },
+            ExportMethod::Sql => {
+                let sql_task = self.sql_as_jsvalue();
+                ApiFuture::new(sql_task)
+            },
         }
     }
 }
@@ -27,6 +27,7 @@ pub enum ExportMethod {
     Arrow,
     ArrowAll,
     JsonConfig,
+    Sql,
 }
 
 impl ExportMethod {
@@ -41,6 +42,7 @@ impl ExportMethod {
             Self::Arrow => ".arrow",
             Self::ArrowAll => ".all.arrow",
             Self::JsonConfig => ".config.json",
+            Self::Sql => ".sql",
         }
     }
 
@@ -49,13 +49,28 @@ pub trait GetViewerConfigModel: HasSession + HasRenderer + HasPresentation {
             let js_plugin = renderer.get_active_plugin()?;
             let settings = presentation.is_settings_open();
             let plugin = js_plugin.name();
-            let plugin_config: serde_json::Value = js_plugin.save().into_serde_ext()?;
+            let mut plugin_config: serde_json::Value = js_plugin.save().into_serde_ext()?;
+            if let Some(plugin_config) = plugin_config.as_object_mut() {
+                plugin_config.remove("column_aliases");
+            }
+
+            let expansion = plugin_config
+                .as_object_mut()
+                .and_then(|x| x.remove("expansion"))
+                .and_then(|x| serde_json::from_value(x).ok())
+                .unwrap_or_else(|| renderer.get_expansion());
+
             let theme = presentation.get_selected_theme_name().await;
             let title = presentation.get_title();
+            let column_aliases = (*presentation.get_column_aliases()).clone();
+            let link_group = session.get_link_group();
             Ok(ViewerConfig {
                 version,
                 plugin,
                 title,
+                column_aliases,
+                expansion,
+                link_group,
                 plugin_config,
                 settings,
                 view_config,
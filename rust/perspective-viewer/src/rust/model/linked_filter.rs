@@ -0,0 +1,276 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::*;
+
+use crate::config::*;
+use crate::model::*;
+use crate::renderer::*;
+use crate::session::Session;
+use crate::utils::*;
+use crate::*;
+
+thread_local! {
+    static LINK_GROUPS: RefCell<HashMap<String, LinkGroupEntry>> = RefCell::new(HashMap::new());
+
+    static NEXT_LINK_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The `PubSub` shared by the members of a `LinkGroup`, which is removed from
+/// `LINK_GROUPS` when its last member leaves.
+#[derive(Default)]
+struct LinkGroupEntry {
+    pubsub: Rc<PubSub<LinkSelection>>,
+    num_members: usize,
+}
+
+/// A selection made in the viewer `source`, as the filter it implies in terms
+/// of that viewer's columns.  An empty `filter` clears the selection.
+#[derive(Clone)]
+struct LinkSelection {
+    source: usize,
+    filter: Vec<Filter>,
+}
+
+#[derive(Deserialize)]
+struct SelectEventDetail {
+    #[serde(default)]
+    selected: Option<bool>,
+    config: SelectEventConfig,
+}
+
+#[derive(Deserialize)]
+struct SelectEventConfig {
+    #[serde(default)]
+    filter: Vec<FilterItem>,
+}
+
+const EVENTS: [&str; 2] = ["perspective-click", "perspective-select"];
+
+/// Joins a `<perspective-viewer>` to a `LinkGroup`, publishing the selections
+/// made in its plugin to the group and applying the other members'
+/// selections as its `Session`'s linked filter.  Leaving the group (by
+/// dropping this handle) clears any selection this viewer made.
+pub struct LinkGroupHandle {
+    id: usize,
+    name: String,
+    elem: HtmlElement,
+    pubsub: Rc<PubSub<LinkSelection>>,
+    callback: Closure<dyn Fn(CustomEvent)>,
+    _sub: Subscription,
+}
+
+impl LinkGroupHandle {
+    pub fn new(
+        elem: &HtmlElement,
+        session: &Session,
+        renderer: &Renderer,
+        group: LinkGroup,
+    ) -> Self {
+        let id = NEXT_LINK_ID.with(|x| x.replace(x.get() + 1));
+        let name = group.name.clone();
+        let pubsub = LINK_GROUPS.with(|groups| {
+            let mut groups = groups.borrow_mut();
+            let entry = groups.entry(name.clone()).or_default();
+            entry.num_members += 1;
+            entry.pubsub.clone()
+        });
+
+        let current_source = Cell::new(None);
+        let state = LinkGroupState {
+            session: session.clone(),
+            renderer: renderer.clone(),
+        };
+
+        let _sub = pubsub.add_listener(move |selection: LinkSelection| {
+            if selection.source == id
+                || (selection.filter.is_empty() && current_source.get() != Some(selection.source))
+            {
+                return;
+            }
+
+            let is_clear = selection.filter.is_empty();
+            current_source.set((!is_clear).then_some(selection.source));
+            if state
+                .session
+                .set_linked_filter(group.map_filter(&selection.filter))
+            {
+                let state = state.clone();
+                ApiFuture::spawn(async move {
+                    state.update_and_render(ViewConfigUpdate::default()).await?;
+                    Ok(())
+                });
+            }
+        });
+
+        let callback = Closure::<dyn Fn(CustomEvent)>::new({
+            clone!(elem, session, pubsub);
+            move |event: CustomEvent| {
+                // Plugins which support selection also emit a click, which is
+                // redundant (and does not deselect).
+                if event.type_() == "perspective-click" && elem.has_attribute("selectable") {
+                    return;
+                }
+
+                if let Ok(detail) = event.detail().into_serde_ext::<SelectEventDetail>() {
+                    let filter = if detail.selected == Some(false) {
+                        vec![]
+                    } else {
+                        selection_filter(&session, detail.config.filter)
+                    };
+
+                    pubsub.emit(LinkSelection { source: id, filter });
+                }
+            }
+        });
+
+        for event in EVENTS {
+            elem.add_event_listener_with_callback(event, callback.as_ref().unchecked_ref())
+                .unwrap();
+        }
+
+        Self {
+            id,
+            name,
+            elem: elem.clone(),
+            pubsub,
+            callback,
+            _sub,
+        }
+    }
+}
+
+impl Drop for LinkGroupHandle {
+    fn drop(&mut self) {
+        for event in EVENTS {
+            self.elem
+                .remove_event_listener_with_callback(event, self.callback.as_ref().unchecked_ref())
+                .unwrap();
+        }
+
+        self.pubsub.emit(LinkSelection {
+            source: self.id,
+            filter: vec![],
+        });
+
+        LINK_GROUPS.with(|groups| {
+            let mut groups = groups.borrow_mut();
+            if let Some(entry) = groups.get_mut(&self.name) {
+                entry.num_members -= 1;
+                if entry.num_members == 0 {
+                    groups.remove(&self.name);
+                }
+            }
+        });
+    }
+}
+
+/// The part of a plugin event's filter which describes the selection, e.g.
+/// the `group_by` row path of a clicked row, excluding the filters the
+/// selecting viewer already had.
+fn selection_filter(session: &Session, filter: Vec<FilterItem>) -> Vec<Filter> {
+    let config = session.get_view_config();
+    let linked_filter = session.get_linked_filter();
+    filter
+        .into_iter()
+        .filter_map(|item| match item {
+            FilterItem::Filter(filter) => Some(filter),
+            FilterItem::Group(_) => None,
+        })
+        .filter(|filter| {
+            filter.1 == FilterOp::EQ
+                && (config.group_by.contains(&filter.0) || config.split_by.contains(&filter.0))
+                && !config.filter.iter().any(|x| x == filter)
+                && !linked_filter.contains(filter)
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+struct LinkGroupState {
+    session: Session,
+    renderer: Renderer,
+}
+
+derive_model!(Renderer, Session for LinkGroupState);
+
+#[cfg(test)]
+mod tests {
+    use futures::join;
+    use wasm_bindgen_test::*;
+
+    use super::*;
+    use crate::js::*;
+
+    wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn member(group: &str) -> (HtmlElement, Session, LinkGroupHandle) {
+        let document = window().unwrap().document().unwrap();
+        let elem: HtmlElement = document.create_element("div").unwrap().unchecked_into();
+        let session = Session::default();
+        let renderer = Renderer::new(&elem);
+        session.set_table(get_mock_table().await).await.unwrap();
+        let group = LinkGroup {
+            name: group.to_owned(),
+            ..LinkGroup::default()
+        };
+
+        let handle = LinkGroupHandle::new(&elem, &session, &renderer, group);
+        (elem, session, handle)
+    }
+
+    fn has_group(name: &str) -> bool {
+        LINK_GROUPS.with(|groups| groups.borrow().contains_key(name))
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_empty_link_group_is_removed() {
+        let (_, _, first) = member("test_empty_link_group_is_removed").await;
+        let (_, _, second) = member("test_empty_link_group_is_removed").await;
+        drop(first);
+        assert!(has_group("test_empty_link_group_is_removed"));
+        drop(second);
+        assert!(!has_group("test_empty_link_group_is_removed"));
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_selection_redraws_linked_viewer() {
+        let (elem, session, _source) = member("test_selection_redraws_linked_viewer").await;
+        let (_, linked, _target) = member("test_selection_redraws_linked_viewer").await;
+        session.update_view_config(ViewConfigUpdate {
+            group_by: Some(vec!["A".to_owned()]),
+            ..ViewConfigUpdate::default()
+        });
+
+        let mut event_init = CustomEventInit::new();
+        let detail = json!({"config": {"filter": [["A", "==", 2]]}});
+        event_init.detail(&detail.into());
+        let event =
+            CustomEvent::new_with_event_init_dict("perspective-select", &event_init).unwrap();
+
+        let (created, _) = join!(linked.view_created.listen_once(), async {
+            elem.dispatch_event(&event).unwrap();
+        });
+
+        created.unwrap();
+        let view = linked.get_view().unwrap();
+        let num_rows = view.dimensions().await.unwrap().num_view_rows();
+        assert_eq!(num_rows as u32, 1);
+    }
+}
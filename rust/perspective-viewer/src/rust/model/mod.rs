@@ -73,9 +73,11 @@ mod export_method;
 mod get_viewer_config;
 mod intersection_observer;
 mod is_invalid_drop;
+mod linked_filter;
 mod plugin_config;
 mod resize_observer;
 mod structural;
+mod undo_redo;
 mod update_and_render;
 
 pub use self::columns_iter_set::*;
@@ -85,7 +87,9 @@ pub use self::export_method::*;
 pub use self::get_viewer_config::*;
 pub use self::intersection_observer::*;
 pub use self::is_invalid_drop::*;
+pub use self::linked_filter::*;
 pub use self::plugin_config::*;
 pub use self::resize_observer::*;
 pub use self::structural::*;
+pub use self::undo_redo::*;
 pub use self::update_and_render::*;
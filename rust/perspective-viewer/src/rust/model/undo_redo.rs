@@ -0,0 +1,179 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::*;
+
+use super::get_viewer_config::*;
+use super::structural::*;
+use crate::config::*;
+use crate::presentation::Presentation;
+use crate::renderer::*;
+use crate::session::*;
+use crate::utils::*;
+use crate::*;
+
+/// Every change to the `ViewerConfig` is recorded by `Presentation` when the
+/// `"perspective-config-update"` event fires;  `UndoRedoModel` steps through
+/// this history and applies the recorded `ViewerConfig` across the
+/// `Session`, `Renderer` and `Presentation`.
+pub trait UndoRedoModel: GetViewerConfigModel {
+    /// Revert to the previous `ViewerConfig`, resolving to `false` if there
+    /// was nothing to undo.
+    fn undo(&self) -> ApiFuture<bool> {
+        self.step_history(false)
+    }
+
+    /// Re-apply the last undone `ViewerConfig`, resolving to `false` if there
+    /// was nothing to redo.
+    fn redo(&self) -> ApiFuture<bool> {
+        self.step_history(true)
+    }
+
+    fn step_history(&self, redo: bool) -> ApiFuture<bool> {
+        let config = self.presentation().step_history(redo);
+        let model = self.cloned();
+        ApiFuture::new(async move {
+            let Some(config) = config else {
+                return Ok(false);
+            };
+
+            let result = apply_viewer_config(&model, config).await;
+            let current = model.get_viewer_config().await.ok();
+            model.presentation().resume_history(current.as_ref());
+            result.map(|_| true)
+        })
+    }
+}
+
+impl<T: GetViewerConfigModel> UndoRedoModel for T {}
+
+async fn apply_viewer_config(
+    model: &GetViewerConfigModelCloned,
+    config: ViewerConfig,
+) -> ApiResult<()> {
+    let ViewerConfig {
+        plugin,
+        plugin_config,
+        theme,
+        title,
+        column_aliases,
+        expansion,
+        view_config,
+        ..
+    } = config;
+
+    let session = model.session();
+    let renderer = model.renderer();
+    let presentation = model.presentation();
+    presentation.set_title(title);
+    presentation.set_column_aliases(column_aliases);
+    let needs_restyle = presentation.get_selected_theme_name().await != theme;
+    if needs_restyle {
+        presentation.set_theme_name(theme.as_deref()).await?;
+    }
+
+    renderer.update_plugin(&PluginUpdate::Update(plugin))?;
+    let update = session.get_view_config().diff(&view_config).patch;
+    session.update_view_config(update);
+    renderer
+        .draw(async {
+            renderer.restore_plugin(Some(&plugin_config), ExpansionUpdate::Update(expansion))?;
+            session.validate().await?.create_view().await
+        })
+        .await?;
+
+    if needs_restyle {
+        let view = session.get_view().into_apierror()?;
+        renderer.restyle_all(&view).await?;
+    }
+
+    Ok(())
+}
+
+/// Binds Ctrl+Z (undo) and Ctrl+Shift+Z (redo) on the `<perspective-viewer>`
+/// element, for as long as this handle lives.  Key events from text inputs
+/// are left alone so they keep their native undo behavior.
+pub struct UndoRedoKeyHandle {
+    elem: HtmlElement,
+    callback: Closure<dyn Fn(KeyboardEvent)>,
+}
+
+impl UndoRedoKeyHandle {
+    pub fn new(
+        elem: &HtmlElement,
+        session: &Session,
+        renderer: &Renderer,
+        presentation: &Presentation,
+    ) -> Self {
+        let model = UndoRedoKeyModel {
+            session: session.clone(),
+            renderer: renderer.clone(),
+            presentation: presentation.clone(),
+        };
+
+        let callback = (move |event: KeyboardEvent| {
+            if !(event.ctrl_key() || event.meta_key())
+                || !event.key().eq_ignore_ascii_case("z")
+                || is_editable_target(&event)
+            {
+                return;
+            }
+
+            event.prevent_default();
+            if event.shift_key() {
+                ApiFuture::spawn(model.redo());
+            } else {
+                ApiFuture::spawn(model.undo());
+            }
+        })
+        .into_closure();
+
+        elem.add_event_listener_with_callback("keydown", callback.as_ref().unchecked_ref())
+            .unwrap();
+
+        Self {
+            elem: elem.clone(),
+            callback,
+        }
+    }
+}
+
+impl Drop for UndoRedoKeyHandle {
+    fn drop(&mut self) {
+        self.elem
+            .remove_event_listener_with_callback("keydown", self.callback.as_ref().unchecked_ref())
+            .unwrap();
+    }
+}
+
+struct UndoRedoKeyModel {
+    session: Session,
+    renderer: Renderer,
+    presentation: Presentation,
+}
+
+derive_model!(Renderer, Session, Presentation for UndoRedoKeyModel);
+
+/// The event's original target, which may be inside the viewer's shadow root.
+fn is_editable_target(event: &KeyboardEvent) -> bool {
+    event
+        .composed_path()
+        .get(0)
+        .dyn_into::<HtmlElement>()
+        .map(|elem| {
+            elem.is_content_editable()
+                || matches!(elem.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        })
+        .unwrap_or_default()
+}
@@ -10,7 +10,8 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::ops::Deref;
 use std::rc::Rc;
 
@@ -22,6 +23,7 @@ use yew::html::ImplicitClone;
 
 use crate::components::column_settings_sidebar::ColumnSettingsTab;
 use crate::components::viewer::ColumnLocator;
+use crate::config::ViewerConfig;
 use crate::utils::*;
 
 /// The available themes as detected in the browser environment or set
@@ -65,14 +67,20 @@ pub struct PresentationHandle {
     viewer_elem: HtmlElement,
     theme_data: Mutex<ThemeData>,
     name: RefCell<Option<String>>,
+    sql_table_name: RefCell<Option<String>>,
+    column_aliases: RefCell<Rc<HashMap<String, String>>>,
     is_settings_open: RefCell<bool>,
     open_column_settings: RefCell<OpenColumnSettings>,
     is_workspace: RefCell<Option<bool>>,
+    history: RefCell<UndoHistory<ViewerConfig>>,
+    is_history_paused: Cell<bool>,
+    pub history_changed: PubSub<(bool, bool)>,
     pub settings_open_changed: PubSub<bool>,
     pub column_settings_open_changed: PubSub<(bool, Option<String>)>,
     pub column_settings_updated: PubSub<JsValue>,
     pub theme_config_updated: PubSub<(Vec<String>, Option<usize>)>,
     pub title_changed: PubSub<Option<String>>,
+    pub column_aliases_changed: PubSub<()>,
 }
 
 #[derive(Default)]
@@ -85,6 +93,8 @@ impl Presentation {
         let theme = Self(Rc::new(PresentationHandle {
             viewer_elem: elem.clone(),
             name: Default::default(),
+            sql_table_name: Default::default(),
+            column_aliases: Default::default(),
             theme_data: Default::default(),
             settings_open_changed: Default::default(),
             column_settings_open_changed: Default::default(),
@@ -92,8 +102,12 @@ impl Presentation {
             is_settings_open: Default::default(),
             is_workspace: Default::default(),
             open_column_settings: Default::default(),
+            history: Default::default(),
+            is_history_paused: Default::default(),
+            history_changed: PubSub::default(),
             theme_config_updated: PubSub::default(),
             title_changed: PubSub::default(),
+            column_aliases_changed: PubSub::default(),
         }));
 
         ApiFuture::spawn(theme.clone().init());
@@ -109,6 +123,67 @@ impl Presentation {
         self.title_changed.emit(title);
     }
 
+    /// The table name `ExportMethod::Sql` queries, which defaults to the
+    /// title.
+    pub fn get_sql_table_name(&self) -> String {
+        self.sql_table_name
+            .borrow()
+            .clone()
+            .or_else(|| self.get_title())
+            .unwrap_or_else(|| "untitled".to_owned())
+    }
+
+    pub fn set_sql_table_name(&self, name: Option<String>) {
+        *self.sql_table_name.borrow_mut() = name;
+    }
+
+    /// Display names for columns, by column name.
+    pub fn get_column_aliases(&self) -> Rc<HashMap<String, String>> {
+        self.column_aliases.borrow().clone()
+    }
+
+    pub fn set_column_aliases(&self, aliases: HashMap<String, String>) {
+        if **self.column_aliases.borrow() != aliases {
+            *self.column_aliases.borrow_mut() = Rc::new(aliases);
+            self.column_aliases_changed.emit(());
+        }
+    }
+
+    /// Set (or with `None`, remove) the alias for column `name`.
+    pub fn set_column_alias(&self, name: &str, alias: Option<String>) {
+        let mut aliases = (*self.get_column_aliases()).clone();
+        match alias.filter(|x| x != name) {
+            Some(alias) => aliases.insert(name.to_owned(), alias),
+            None => aliases.remove(name),
+        };
+
+        self.set_column_aliases(aliases);
+    }
+
+    /// Re-key the column aliases for columns which have been renamed.
+    ///
+    /// # Arguments
+    /// - `renames` New column names, keyed by old column name.
+    pub fn rename_column_aliases(&self, renames: &HashMap<String, String>) {
+        let aliases = self
+            .get_column_aliases()
+            .iter()
+            .map(|(name, alias)| (renames.get(name).unwrap_or(name).clone(), alias.clone()))
+            .collect();
+
+        self.set_column_aliases(aliases);
+    }
+
+    /// The name to display for column `name`, which is its alias if it has
+    /// one.
+    pub fn get_column_label(&self, name: &str) -> String {
+        self.column_aliases
+            .borrow()
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.to_owned())
+    }
+
     pub fn get_is_workspace(&self) -> bool {
         if self.is_workspace.borrow().is_none() {
             let is_workspace = self
@@ -161,6 +236,71 @@ impl Presentation {
         self.open_column_settings.borrow().deref().clone()
     }
 
+    pub fn can_undo(&self) -> bool {
+        self.history.borrow().can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.borrow().can_redo()
+    }
+
+    /// Record a `ViewerConfig` snapshot in the undo history.  The settings
+    /// panel state is not part of the history, and changes made while the
+    /// history is paused (e.g. while applying an undo) only replace the
+    /// current snapshot.
+    pub fn record_history(&self, config: &ViewerConfig) {
+        let config = ViewerConfig {
+            settings: false,
+            ..config.clone()
+        };
+
+        if self.is_history_paused.get() {
+            self.history.borrow_mut().set_current(config);
+        } else if self
+            .history
+            .borrow_mut()
+            .record(config, js_sys::Date::now())
+        {
+            self.emit_history_changed();
+        }
+    }
+
+    /// Forget all undo and redo steps, e.g. when a new `Table` is loaded.
+    pub fn clear_history(&self) {
+        self.history.borrow_mut().clear();
+        self.emit_history_changed();
+    }
+
+    /// Step the history back (or forward if `redo`), returning the
+    /// `ViewerConfig` to apply.  The history is paused until
+    /// `resume_history()` is called.
+    pub fn step_history(&self, redo: bool) -> Option<ViewerConfig> {
+        let mut history = self.history.borrow_mut();
+        let config = if redo { history.redo() } else { history.undo() }.cloned();
+        drop(history);
+        if config.is_some() {
+            self.is_history_paused.set(true);
+            self.emit_history_changed();
+        }
+
+        config
+    }
+
+    /// Resume recording history after `step_history()`, with `config` as the
+    /// applied state.
+    pub fn resume_history(&self, config: Option<&ViewerConfig>) {
+        if let Some(config) = config {
+            self.record_history(config);
+        }
+
+        self.is_history_paused.set(false);
+    }
+
+    fn emit_history_changed(&self) {
+        self.history_changed
+            .emit((self.can_undo(), self.can_redo()));
+    }
+
     async fn init(self) -> ApiResult<()> {
         self.set_theme_attribute(self.get_selected_theme_name().await.as_deref())
     }
@@ -66,6 +66,8 @@ pub struct RendererMutData {
     plugin_store: PluginStore,
     plugins_idx: Option<usize>,
     timer: MovingWindowRenderTimer,
+    expansion: ExpansionState,
+    column_aliases: Rc<HashMap<String, String>>,
 }
 
 type RenderLimits = (usize, usize, Option<usize>, Option<usize>);
@@ -109,6 +111,8 @@ impl Renderer {
                 plugin_store: PluginStore::default(),
                 plugins_idx: None,
                 timer: MovingWindowRenderTimer::default(),
+                expansion: ExpansionState::default(),
+                column_aliases: Rc::default(),
             }),
             draw_lock: Default::default(),
             plugin_changed: Default::default(),
@@ -118,6 +122,7 @@ impl Renderer {
 
     pub async fn reset(&self) {
         self.0.borrow_mut().plugins_idx = None;
+        self.0.borrow_mut().expansion = ExpansionState::default();
         if let Ok(plugin) = self.get_active_plugin() {
             plugin.restore(&json!({}));
         }
@@ -132,6 +137,103 @@ impl Renderer {
         Ref::map(self.borrow(), |x| &x.metadata)
     }
 
+    /// The `ExpansionState` last restored to the plugin, for plugins which do
+    /// not report their own in the `"expansion"` field of `save()`.
+    pub fn get_expansion(&self) -> ExpansionState {
+        self.borrow().expansion.clone()
+    }
+
+    /// Re-key the per-column entries (the `"columns"` field) of the active
+    /// plugin's `plugin_config` for columns which have been renamed.
+    ///
+    /// # Arguments
+    /// - `renames` New column names, keyed by old column name.
+    pub fn rename_plugin_columns(&self, renames: &HashMap<String, String>) -> ApiResult<()> {
+        let plugin = self.get_active_plugin()?;
+        let mut token: serde_json::Value = plugin.save().into_serde_ext()?;
+        if let Some(serde_json::Value::Object(columns)) = token.get_mut("columns") {
+            *columns = std::mem::take(columns)
+                .into_iter()
+                .map(|(name, config)| (renames.get(&name).cloned().unwrap_or(name), config))
+                .collect();
+
+            self.restore_plugin(Some(&token), ExpansionUpdate::Missing)?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the display names for columns, which every plugin is restored with
+    /// as the `"column_aliases"` field of its token, and restore the active
+    /// plugin with them.  The plugin is not redrawn.
+    ///
+    /// # Arguments
+    /// - `aliases` Display names, keyed by column name.
+    pub fn set_column_aliases(&self, aliases: Rc<HashMap<String, String>>) -> ApiResult<()> {
+        self.borrow_mut().column_aliases = aliases;
+        if self.borrow().plugins_idx.is_some() {
+            self.restore_column_aliases()?;
+        }
+
+        Ok(())
+    }
+
+    /// Restore the active plugin with the current column aliases, unless its
+    /// `save()` token already has them.
+    fn restore_column_aliases(&self) -> ApiResult<()> {
+        let plugin = self.get_active_plugin()?;
+        let token: serde_json::Value = plugin.save().into_serde_ext()?;
+        let aliases = serde_json::to_value(&*self.borrow().column_aliases)?;
+        let current = token.get("column_aliases").cloned();
+        if current.unwrap_or_else(|| serde_json::Value::Object(Default::default())) != aliases {
+            self.restore_plugin(Some(&token), ExpansionUpdate::Missing)?;
+        }
+
+        Ok(())
+    }
+
+    /// Restore the active plugin from `plugin_config`, with the column aliases
+    /// as its `"column_aliases"` field and, if the plugin declares
+    /// `persist_expansion`, the (possibly updated) `ExpansionState` as its
+    /// `"expansion"` field.  If only the
+    /// `ExpansionState` is updated, the plugin's current `save()` token is
+    /// restored with it instead.
+    ///
+    /// # Arguments
+    /// - `plugin_config` The plugin's token, as returned by its `save()`.
+    /// - `expansion` The `ExpansionUpdate` to apply.
+    pub fn restore_plugin(
+        &self,
+        plugin_config: Option<&serde_json::Value>,
+        expansion: ExpansionUpdate,
+    ) -> ApiResult<()> {
+        let plugin = self.get_active_plugin()?;
+        let mut token = match (plugin_config, &expansion) {
+            (Some(plugin_config), _) => plugin_config.clone(),
+            (None, ExpansionUpdate::Missing) => return Ok(()),
+            (None, _) => plugin.save().into_serde_ext()?,
+        };
+
+        match expansion {
+            ExpansionUpdate::Update(expansion) => self.borrow_mut().expansion = expansion,
+            ExpansionUpdate::SetDefault => self.borrow_mut().expansion = Default::default(),
+            ExpansionUpdate::Missing => (),
+        }
+
+        if let serde_json::Value::Object(token) = &mut token {
+            if plugin.persist_expansion().unwrap_or_default() {
+                let expansion = serde_json::to_value(self.get_expansion())?;
+                token.insert("expansion".to_owned(), expansion);
+            }
+
+            let aliases = serde_json::to_value(&*self.borrow().column_aliases)?;
+            token.insert("column_aliases".to_owned(), aliases);
+        }
+
+        plugin.restore(&JsValue::from_serde_ext(&token)?);
+        Ok(())
+    }
+
     /// Return all plugin instances, whether they are active or not.  Useful
     /// for configuring all or specific plugins at application init.
     pub fn get_all_plugins(&self) -> Vec<JsPerspectiveViewerPlugin> {
@@ -217,6 +319,7 @@ impl Renderer {
             self.borrow_mut().plugins_idx = Some(idx);
             let plugin: JsPerspectiveViewerPlugin = self.get_active_plugin()?;
             self.borrow_mut().metadata = plugin.get_requirements()?;
+            self.restore_column_aliases()?;
             self.plugin_changed.emit(plugin);
         }
 
@@ -242,7 +345,8 @@ impl Renderer {
     }
 
     /// This will take a future which _should_ create a new view and then will
-    /// draw it.
+    /// draw it.  Nothing is drawn if the view was made obsolete by a config
+    /// change while it was being created, see `ValidSession::create_view()`.
     pub async fn draw(
         &self,
         session: impl Future<Output = Result<&Session, ApiError>>,
@@ -252,6 +356,19 @@ impl Renderer {
 
     /// This will update an already existing view
     pub async fn update(&self, session: &Session) -> ApiResult<()> {
+        self.update_with_delta(session, None).await
+    }
+
+    /// Update an already existing view in response to a `Table` update.  The
+    /// row `delta`s of updates within the throttle window are passed together
+    /// to the plugin's `update()`, if the plugin supports `delta_updates`.
+    /// Without a `delta`, the plugin re-pulls the viewport.
+    pub async fn update_with_delta(
+        &self,
+        session: &Session,
+        delta: Option<js_sys::ArrayBuffer>,
+    ) -> ApiResult<()> {
+        self.render_timer().push_delta(delta);
         self.draw_plugin(async { Ok(session) }, true).await
     }
 
@@ -266,7 +383,14 @@ impl Renderer {
                 set_timeout(timer.get_throttle()).await?;
             }
 
-            if let Some(view) = session.await?.get_view() {
+            let session = session.await?;
+            if is_update {
+                // A `Table` update may change the rows selected by `top_n`, in
+                // which case the `View` is invalidated and recreated instead.
+                session.update_top_n().await?;
+            }
+
+            if let Some(view) = session.get_view() {
                 timer.capture_time(self.draw_view(&view, is_update)).await
             } else {
                 Ok(())
@@ -284,11 +408,17 @@ impl Renderer {
     async fn draw_view(&self, view: &JsPerspectiveView, is_update: bool) -> ApiResult<()> {
         let plugin = self.get_active_plugin()?;
         let meta = self.metadata().clone();
+        let deltas = self
+            .render_timer()
+            .take_deltas()
+            .filter(|_| meta.delta_updates)
+            .map(|deltas| deltas.into_iter().collect::<js_sys::Array>());
+
         let limits = get_row_and_col_limits(view, &meta).await?;
         self.session_changed.emit((is_update, limits));
         let viewer_elem = &self.0.borrow().viewer_elem.clone();
         if is_update {
-            let task = plugin.update(view, limits.2, limits.3, false);
+            let task = plugin.update(view, limits.2, limits.3, false, deltas);
             activate_plugin(viewer_elem, &plugin, task).await
         } else {
             let task = plugin.draw(view, limits.2, limits.3, false);
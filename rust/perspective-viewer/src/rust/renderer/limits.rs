@@ -10,29 +10,25 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-use wasm_bindgen::prelude::*;
-use wasm_bindgen::JsCast;
-#[cfg(test)]
-use {crate::utils::*, wasm_bindgen_futures::future_to_promise, wasm_bindgen_test::*};
-
-use crate::js::perspective::*;
 use crate::js::plugin::*;
-#[cfg(test)]
-use crate::*;
-
-pub async fn get_row_and_col_limits(
-    view: &JsPerspectiveView,
+use crate::session::*;
+use crate::utils::*;
+
+/// The dimensions of `view` and, if the active plugin should only render part
+/// of it, the limits to render.  A `ViewConfig::top_n` is applied to `view` as
+/// a filter, so the counted rows (and thus the render warning) are those left
+/// after the `top_n` limit.
+pub async fn get_row_and_col_limits<V: ViewBackend>(
+    view: &V,
     plugin_metadata: &ViewConfigRequirements,
-) -> Result<(usize, usize, Option<usize>, Option<usize>), JsValue> {
+) -> ApiResult<(usize, usize, Option<usize>, Option<usize>)> {
     let dimensions = view.dimensions().await?;
-    let num_cols = dimensions.num_view_columns() as usize;
-    let num_rows = dimensions.num_view_rows() as usize;
+    let num_cols = dimensions.num_view_columns;
+    let num_rows = dimensions.num_view_rows;
     match (plugin_metadata.max_columns, plugin_metadata.render_warning) {
         (Some(_), false) => Ok((num_cols, num_rows, None, None)),
         (max_columns, _) => {
-            let schema = view.schema().await?;
-            let keys = js_sys::Object::keys(schema.unchecked_ref::<js_sys::Object>());
-            let num_schema_columns = std::cmp::max(1, keys.length() as usize);
+            let num_schema_columns = std::cmp::max(1, view.schema().await?.len());
             let max_cols = max_columns.and_then(|max_columns| {
                 let column_group_diff = max_columns % num_schema_columns;
                 let column_limit = max_columns + column_group_diff;
@@ -58,141 +54,99 @@ pub async fn get_row_and_col_limits(
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use std::collections::HashMap;
 
-    fn closure_helper<T>(x: T) -> Closure<dyn Fn(JsValue) -> js_sys::Promise>
-    where
-        T: Into<JsValue>,
-    {
-        let val = x.into();
-        (move |_: JsValue| {
-            clone!(val);
-            future_to_promise(async move { Ok(val.clone()) })
-        })
-        .into_closure()
-    }
+    use futures::executor::block_on;
 
-    #[wasm_bindgen_test]
-    pub async fn test_emtpy_schema_no_columns() {
-        let closure = closure_helper(0);
-        let closure2 = closure_helper(json!({}));
-        let view = json!({
-            "num_columns": closure.as_ref().unchecked_ref::<JsValue>(),
-            "num_rows": closure.as_ref().unchecked_ref::<JsValue>(),
-            "schema": closure2.as_ref().unchecked_ref::<JsValue>()
-        })
-        .unchecked_into::<JsPerspectiveView>();
+    use super::*;
+    use crate::config::Type;
+
+    fn mock_view(num_view_columns: usize, schema: &[&str]) -> MockView {
+        MockView {
+            dimensions: ViewDimensions {
+                num_view_columns,
+                ..ViewDimensions::default()
+            },
+            schema: schema
+                .iter()
+                .map(|name| (name.to_string(), Type::String))
+                .collect::<HashMap<_, _>>(),
+            ..MockView::default()
+        }
+    }
 
+    #[test]
+    pub fn test_emtpy_schema_no_columns() {
+        let view = mock_view(0, &[]);
         let reqs = ViewConfigRequirements {
             render_warning: true,
             ..ViewConfigRequirements::default()
         };
-        let (_, _, max_cols, max_rows) = get_row_and_col_limits(&view, &reqs).await.unwrap();
+
+        let (_, _, max_cols, max_rows) = block_on(get_row_and_col_limits(&view, &reqs)).unwrap();
         assert_eq!(max_cols, None);
         assert_eq!(max_rows, None);
     }
 
-    #[wasm_bindgen_test]
-    pub async fn test_columns_do_not_exceed_max_columns() {
-        let closure = closure_helper(1);
-        let closure2 = closure_helper(0);
-        let closure3 = closure_helper(json!({}));
-        let view = json!({
-            "num_columns": closure.as_ref().unchecked_ref::<JsValue>(),
-            "num_rows": closure2.as_ref().unchecked_ref::<JsValue>(),
-            "schema": closure3.as_ref().unchecked_ref::<JsValue>()
-        })
-        .unchecked_into::<JsPerspectiveView>();
-
+    #[test]
+    pub fn test_columns_do_not_exceed_max_columns() {
+        let view = mock_view(1, &[]);
         let reqs = ViewConfigRequirements {
             max_columns: Some(2),
             render_warning: true,
             ..ViewConfigRequirements::default()
         };
 
-        let (_, _, max_cols, max_rows) = get_row_and_col_limits(&view, &reqs).await.unwrap();
+        let (_, _, max_cols, max_rows) = block_on(get_row_and_col_limits(&view, &reqs)).unwrap();
         assert_eq!(max_cols, None);
         assert_eq!(max_rows, None);
     }
 
-    #[wasm_bindgen_test]
-    pub async fn test_columns_exceed_max_columns() {
-        let closure = closure_helper(2);
-        let closure2 = closure_helper(0);
-        let closure3 = closure_helper(json!({}));
-        let view = json!({
-            "num_columns": closure.as_ref().unchecked_ref::<JsValue>(),
-            "num_rows": closure2.as_ref().unchecked_ref::<JsValue>(),
-            "schema": closure3.as_ref().unchecked_ref::<JsValue>()
-        })
-        .unchecked_into::<JsPerspectiveView>();
-
+    #[test]
+    pub fn test_columns_exceed_max_columns() {
+        let view = mock_view(2, &[]);
         let reqs = ViewConfigRequirements {
             max_columns: Some(1),
             render_warning: true,
             ..ViewConfigRequirements::default()
         };
-        let (_, _, max_cols, max_rows) = get_row_and_col_limits(&view, &reqs).await.unwrap();
+
+        let (_, _, max_cols, max_rows) = block_on(get_row_and_col_limits(&view, &reqs)).unwrap();
         assert_eq!(max_cols, Some(1));
         assert_eq!(max_rows, None);
     }
 
-    #[wasm_bindgen_test]
-    pub async fn test_when_schema_columns_are_present() {
-        let closure = closure_helper(100);
-        let closure2 = closure_helper(0);
-        let closure3 = closure_helper(json!({"x": "string", "y": "string"}));
-        let view = json!({
-            "num_columns": closure.as_ref().unchecked_ref::<JsValue>(),
-            "num_rows": closure2.as_ref().unchecked_ref::<JsValue>(),
-            "schema": closure3.as_ref().unchecked_ref::<JsValue>()
-        })
-        .unchecked_into::<JsPerspectiveView>();
-
+    #[test]
+    pub fn test_when_schema_columns_are_present() {
+        let view = mock_view(100, &["x", "y"]);
         let reqs = ViewConfigRequirements {
             max_columns: Some(3),
             render_warning: true,
             ..ViewConfigRequirements::default()
         };
-        let (_, _, max_cols, max_rows) = get_row_and_col_limits(&view, &reqs).await.unwrap();
+
+        let (_, _, max_cols, max_rows) = block_on(get_row_and_col_limits(&view, &reqs)).unwrap();
         assert_eq!(max_cols, Some(4));
         assert_eq!(max_rows, None);
     }
 
-    #[wasm_bindgen_test]
-    pub async fn test_when_max_cells_exits() {
-        let closure = closure_helper(1);
-        let closure2 = closure_helper(0);
-        let closure3 = closure_helper(json!({}));
-        let view = json!({
-            "num_columns": closure.as_ref().unchecked_ref::<JsValue>(),
-            "num_rows": closure2.as_ref().unchecked_ref::<JsValue>(),
-            "schema": closure3.as_ref().unchecked_ref::<JsValue>()
-        })
-        .unchecked_into::<JsPerspectiveView>();
-
+    #[test]
+    pub fn test_when_max_cells_exits() {
+        let view = mock_view(1, &[]);
         let reqs = ViewConfigRequirements {
             max_cells: Some(2),
             render_warning: true,
             ..ViewConfigRequirements::default()
         };
-        let (_, _, max_cols, max_rows) = get_row_and_col_limits(&view, &reqs).await.unwrap();
+
+        let (_, _, max_cols, max_rows) = block_on(get_row_and_col_limits(&view, &reqs)).unwrap();
         assert_eq!(max_cols, None);
         assert_eq!(max_rows, Some(2));
     }
 
-    #[wasm_bindgen_test]
-    pub async fn test_when_columns_exceed_max_columns_and_max_cells_exists() {
-        let closure = closure_helper(4);
-        let closure2 = closure_helper(0);
-        let closure3 = closure_helper(json!({}));
-        let view = json!({
-            "num_columns": closure.as_ref().unchecked_ref::<JsValue>(),
-            "num_rows": closure2.as_ref().unchecked_ref::<JsValue>(),
-            "schema": closure3.as_ref().unchecked_ref::<JsValue>()
-        })
-        .unchecked_into::<JsPerspectiveView>();
-
+    #[test]
+    pub fn test_when_columns_exceed_max_columns_and_max_cells_exists() {
+        let view = mock_view(4, &[]);
         let reqs = ViewConfigRequirements {
             max_columns: Some(2),
             max_cells: Some(10),
@@ -200,7 +154,7 @@ mod tests {
             ..ViewConfigRequirements::default()
         };
 
-        let (_, _, max_cols, max_rows) = get_row_and_col_limits(&view, &reqs).await.unwrap();
+        let (_, _, max_cols, max_rows) = block_on(get_row_and_col_limits(&view, &reqs)).unwrap();
         assert_eq!(max_cols, Some(2));
         assert_eq!(max_rows, Some(5));
     }
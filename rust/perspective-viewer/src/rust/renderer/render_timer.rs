@@ -22,9 +22,20 @@ use web_sys::*;
 
 use crate::utils::*;
 
-/// A utility struct to track and calculate framerate metrics.
+/// The most row deltas to coalesce between draws, beyond which the plugin
+/// re-pulls the viewport instead.
+const MAX_PENDING_DELTAS: usize = 64;
+
+/// A utility struct to track and calculate framerate metrics, and to coalesce
+/// the row deltas of `Table` updates which arrive within the throttle window.
 #[derive(Default, Clone)]
-pub struct MovingWindowRenderTimer(Rc<RefCell<RenderTimerType>>);
+pub struct MovingWindowRenderTimer {
+    timer: Rc<RefCell<RenderTimerType>>,
+
+    /// The row deltas received since the last draw, or `None` if an update
+    /// without a delta (or too many) was received since.
+    deltas: Rc<RefCell<Option<Vec<js_sys::ArrayBuffer>>>>,
+}
 
 enum RenderTimerType {
     Moving(Closure<dyn Fn(JsValue)>, Rc<RefCell<RenderTimerState>>),
@@ -50,13 +61,13 @@ pub struct RenderTimerStats {
 impl MovingWindowRenderTimer {
     pub async fn capture_time<T>(&self, f: impl Future<Output = T>) -> T {
         let perf = window().unwrap().performance().unwrap();
-        let start = match *self.0.borrow() {
+        let start = match *self.timer.borrow() {
             RenderTimerType::Constant(_) => 0_f64,
             RenderTimerType::Moving(..) => perf.now(),
         };
 
         let result = f.await;
-        match &mut *self.0.borrow_mut() {
+        match &mut *self.timer.borrow_mut() {
             RenderTimerType::Moving(_, timings) => {
                 let mut stats = timings.borrow_mut();
                 let now = perf.now();
@@ -73,8 +84,26 @@ impl MovingWindowRenderTimer {
         result
     }
 
+    /// Queue the row delta of a `Table` update, or if `delta` is `None`,
+    /// invalidate the queue so that the next draw re-pulls the viewport.
+    pub fn push_delta(&self, delta: Option<js_sys::ArrayBuffer>) {
+        let mut deltas = self.deltas.borrow_mut();
+        match (&mut *deltas, delta) {
+            (Some(deltas), Some(delta)) if deltas.len() < MAX_PENDING_DELTAS => deltas.push(delta),
+            _ => *deltas = None,
+        }
+    }
+
+    /// Take the row deltas queued since the last call, which are `None` if
+    /// there are none or the queue was invalidated.
+    pub fn take_deltas(&self) -> Option<Vec<js_sys::ArrayBuffer>> {
+        self.deltas
+            .replace(Some(vec![]))
+            .filter(|deltas| !deltas.is_empty())
+    }
+
     pub fn get_stats(&self) -> Option<RenderTimerStats> {
-        match &*self.0.borrow_mut() {
+        match &*self.timer.borrow_mut() {
             RenderTimerType::Constant(_) => None,
             RenderTimerType::Moving(_, timings) => {
                 let perf = window().unwrap().performance().unwrap();
@@ -90,16 +119,16 @@ impl MovingWindowRenderTimer {
     pub fn set_throttle(&mut self, val: Option<f64>) {
         match val {
             None => {
-                *self.0.borrow_mut() = RenderTimerType::default();
+                *self.timer.borrow_mut() = RenderTimerType::default();
             },
             Some(val) => {
-                *self.0.borrow_mut() = RenderTimerType::Constant(val);
+                *self.timer.borrow_mut() = RenderTimerType::Constant(val);
             },
         }
     }
 
     pub fn get_throttle(&self) -> i32 {
-        match &*self.0.borrow() {
+        match &*self.timer.borrow() {
             RenderTimerType::Constant(constant) => *constant as i32,
             RenderTimerType::Moving(_, timings) => {
                 let state = timings.borrow();
@@ -10,25 +10,43 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
+mod backend;
 mod column_defaults_update;
+mod column_stats;
+mod column_values;
 mod drag_drop_update;
 mod metadata;
+mod pause_subscription;
+mod rename_columns_update;
 mod replace_expression_update;
+mod table_schema_diff;
+#[cfg(test)]
+mod tests;
+mod validate_view_config;
+mod validation_report;
 mod view;
 mod view_subscription;
 
 use std::cell::{Ref, RefCell};
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::iter::IntoIterator;
 use std::ops::Deref;
 use std::rc::Rc;
 
+use serde::Deserialize;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use yew::html::ImplicitClone;
 use yew::prelude::*;
 
+pub use self::backend::*;
+pub use self::column_stats::*;
+pub use self::column_values::*;
 use self::metadata::*;
+use self::pause_subscription::PauseSubscription;
+pub use self::table_schema_diff::*;
+use self::validate_view_config::*;
+pub use self::validation_report::*;
 use self::view::{PerspectiveOwned, View};
 pub use self::view_subscription::ViewStats;
 use self::view_subscription::*;
@@ -51,11 +69,12 @@ impl ImplicitClone for Session {}
 #[derive(Default)]
 pub struct SessionHandle {
     session_data: RefCell<SessionData>,
-    pub table_updated: PubSub<()>,
+    pub table_updated: PubSub<Option<js_sys::ArrayBuffer>>,
     pub table_loaded: PubSub<()>,
     pub view_created: PubSub<()>,
     pub view_config_changed: PubSub<()>,
     pub stats_changed: PubSub<()>,
+    pub config_invalid: PubSub<ViewConfigReport>,
 }
 
 /// Mutable state for `Session`.
@@ -65,9 +84,37 @@ pub struct SessionData {
     metadata: SessionMetadata,
     config: ViewConfig,
     view_sub: Option<ViewSubscription>,
+    pause_sub: Option<PauseSubscription>,
     stats: Option<ViewStats>,
     is_clean: bool,
     is_paused: bool,
+    delta_updates: bool,
+
+    /// Incremented whenever the `View` is invalidated, so a `View` whose
+    /// creation started in an earlier generation is known to be obsolete.
+    view_generation: u64,
+    column_stats: HashMap<String, ColumnStats>,
+
+    /// Incremented whenever `column_stats` is cleared, so `ColumnStats` whose
+    /// computation started in an earlier generation are known to be obsolete.
+    column_stats_generation: u64,
+    link_group: Option<LinkGroup>,
+
+    /// The filter implied by a selection in another viewer of `link_group`,
+    /// which is applied to the `View` but is not part of the `ViewConfig`.
+    linked_filter: Vec<Filter>,
+    top_n: Option<TopNState>,
+}
+
+/// The filter which the `top_n` of the `View` created in `generation` was
+/// resolved to, and whether the `Table` has been updated since, see
+/// `Session::update_top_n()`.
+#[derive(Clone)]
+struct TopNState {
+    generation: u64,
+    config: ViewConfig,
+    filter: Option<FilterItem>,
+    is_dirty: bool,
 }
 
 impl Deref for Session {
@@ -111,6 +158,7 @@ impl Session {
     pub fn reset(&self, reset_expressions: bool) {
         self.borrow_mut().is_clean = false;
         self.borrow_mut().view_sub = None;
+        self.borrow_mut().view_generation += 1;
         self.borrow_mut().config.reset(reset_expressions);
     }
 
@@ -122,6 +170,7 @@ impl Session {
         self.reset(false);
         self.borrow_mut().metadata = SessionMetadata::default();
         self.borrow_mut().table = None;
+        self.borrow_mut().pause_sub = None;
         false
     }
 
@@ -137,28 +186,179 @@ impl Session {
     /// `ViewSubscription`, which will need to be re-initialized later via
     /// `create_view()`.
     pub async fn set_table(&self, table: JsPerspectiveTable) -> ApiResult<JsValue> {
-        let metadata = SessionMetadata::from_table(&table).await?;
-        self.borrow_mut().view_sub = None;
-        self.borrow_mut().metadata = metadata;
+        self.load_table_metadata(&table).await?;
         self.borrow_mut().table = Some(table);
+        if self.borrow().is_paused {
+            self.track_pending_updates();
+        }
+
         self.table_loaded.emit(());
         Ok(JsValue::UNDEFINED)
     }
 
+    /// The part of `set_table()` which only needs the `Table`'s metadata,
+    /// which resets this `Session`'s `View` state for `table` without setting
+    /// it.
+    async fn load_table_metadata<T: TableBackend>(&self, table: &T) -> ApiResult<()> {
+        let metadata = SessionMetadata::from_table(table).await?;
+        self.borrow_mut().view_sub = None;
+        self.borrow_mut().view_generation += 1;
+        self.borrow_mut().metadata = metadata;
+        self.clear_column_stats();
+        Ok(())
+    }
+
+    /// Replace this `Session`'s `Table` with one whose schema may differ,
+    /// renaming columns in the `ViewConfig` per `renames` (keyed by their old
+    /// name).  Entries which still reference columns missing from the new
+    /// `Table` are dropped (and reported) by the next `validate()`.
+    ///
+    /// # Arguments
+    /// - `table` The new `Table`.
+    /// - `renames` New column names, keyed by old column name.
+    pub async fn swap_table(
+        &self,
+        table: JsPerspectiveTable,
+        renames: &HashMap<String, String>,
+    ) -> ApiResult<TableSchemaDiff> {
+        let old_schema = self.metadata().get_table_schema().cloned();
+        self.set_table(table).await?;
+        let new_schema = self.metadata().get_table_schema().cloned();
+        let diff = TableSchemaDiff::new(
+            &old_schema.unwrap_or_default(),
+            &new_schema.unwrap_or_default(),
+            renames,
+        );
+
+        if !diff.renamed_columns.is_empty() {
+            let update = self
+                .get_view_config()
+                .create_rename_columns_update(&diff.renames());
+
+            self.update_view_config(update);
+        }
+
+        Ok(diff)
+    }
+
+    /// Pause or resume this `Session`, returning whether this changed its
+    /// paused state.  While paused, the `View` is deleted but updates to the
+    /// `Table` are counted in `ViewStats::num_pending_updates`.
     pub fn set_pause(&self, pause: bool) -> bool {
         self.borrow_mut().is_clean = false;
         if pause == self.borrow().is_paused {
             false
         } else if pause {
             self.borrow_mut().view_sub = None;
+            self.borrow_mut().view_generation += 1;
             self.borrow_mut().is_paused = true;
+            self.track_pending_updates();
             true
         } else {
+            self.borrow_mut().pause_sub = None;
             self.borrow_mut().is_paused = false;
+            self.update_pending_updates(None);
             true
         }
     }
 
+    pub fn is_paused(&self) -> bool {
+        self.borrow().is_paused
+    }
+
+    /// Set whether the `View` should be subscribed to in row delta mode, e.g.
+    /// when the active plugin changes.  This takes effect when the `View` is
+    /// next created, which changing it forces.
+    pub fn set_delta_updates(&self, delta_updates: bool) {
+        if self.borrow().delta_updates != delta_updates {
+            self.borrow_mut().delta_updates = delta_updates;
+            self.borrow_mut().is_clean = false;
+        }
+    }
+
+    pub fn get_link_group(&self) -> Option<LinkGroup> {
+        self.borrow().link_group.clone()
+    }
+
+    pub fn set_link_group(&self, link_group: Option<LinkGroup>) {
+        self.borrow_mut().link_group = link_group;
+    }
+
+    pub fn get_linked_filter(&self) -> Vec<Filter> {
+        self.borrow().linked_filter.clone()
+    }
+
+    /// Replace the filter implied by a selection in a linked viewer, returning
+    /// whether this invalidated the `View`.  Filters on columns this
+    /// `Session`'s `Table` lacks are dropped, as the selecting viewer may
+    /// have a different schema.
+    pub fn set_linked_filter(&self, filter: Vec<Filter>) -> bool {
+        let filter = {
+            let metadata = self.metadata();
+            filter
+                .into_iter()
+                .filter_map(|mut filter| {
+                    let ty = metadata.get_column_table_type(&filter.0)?;
+                    filter.coerce_type(ty);
+                    Some(filter)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if filter == self.borrow().linked_filter {
+            false
+        } else {
+            self.borrow_mut().linked_filter = filter;
+            self.borrow_mut().view_sub = None;
+            self.borrow_mut().view_generation += 1;
+            self.borrow_mut().is_clean = false;
+            self.view_config_changed.emit(());
+            true
+        }
+    }
+
+    /// Replace the `PauseSubscription` with one on the current `Table`, which
+    /// is dropped if this `Session` is resumed before it is created.
+    fn track_pending_updates(&self) {
+        self.borrow_mut().pause_sub = None;
+        self.update_pending_updates(Some((0, 0)));
+        let Some(table) = self.borrow().table.clone() else {
+            return;
+        };
+
+        let this = self.clone();
+        ApiFuture::spawn(async move {
+            let on_pending = Callback::from({
+                let this = this.clone();
+                move |pending| {
+                    this.clear_column_stats();
+                    this.update_pending_updates(Some(pending))
+                }
+            });
+
+            let sub = PauseSubscription::new(&table, on_pending).await?;
+            let is_current = this
+                .borrow()
+                .table
+                .as_ref()
+                .is_some_and(|x| js_sys::Object::is(x, &table));
+
+            if this.borrow().is_paused && is_current {
+                this.borrow_mut().pause_sub = Some(sub);
+            }
+
+            Ok(())
+        });
+    }
+
+    fn update_pending_updates(&self, pending: Option<(u32, i64)>) {
+        let stats = self.borrow().stats.clone();
+        if let Some(mut stats) = stats.filter(|x| x.num_pending_updates != pending) {
+            stats.num_pending_updates = pending;
+            self.update_stats(stats);
+        }
+    }
+
     pub async fn await_table(&self) -> ApiResult<()> {
         if self.js_get_table().is_none() {
             self.table_loaded.listen_once().await?;
@@ -192,13 +392,20 @@ impl Session {
     pub fn create_drag_drop_update(
         &self,
         column: String,
+        from_index: Option<usize>,
         index: usize,
         drop: DragTarget,
         drag: DragEffect,
         requirements: &ViewConfigRequirements,
     ) -> ViewConfigUpdate {
-        self.get_view_config()
-            .create_drag_drop_update(column, index, drop, drag, requirements)
+        self.get_view_config().create_drag_drop_update(
+            column,
+            from_index,
+            index,
+            drop,
+            drag,
+            requirements,
+        )
     }
 
     /// An async task which replaces a `column` aliased expression with another.
@@ -238,16 +445,10 @@ impl Session {
         &self,
         expr: &str,
     ) -> Result<Option<PerspectiveValidationError>, JsValue> {
-        let arr = json!({"_": expr});
+        let expressions = std::iter::once(Expression::new(Some("_".into()), expr.into())).collect();
         let table = self.borrow().table.as_ref().unwrap().clone();
-        let errors = table.validate_expressions(arr).await?.errors();
-        let error_keys = js_sys::Object::keys(&errors);
-        if error_keys.length() > 0 {
-            let js_err = js_sys::Reflect::get(&errors, &error_keys.get(0))?;
-            Ok(Some(js_err.into_serde_ext().unwrap()))
-        } else {
-            Ok(None)
-        }
+        let mut valid_recs = TableBackend::validate_expressions(&table, &expressions).await?;
+        Ok(valid_recs.errors.remove("_"))
     }
 
     pub async fn arrow_as_vec(&self, flat: bool) -> Result<Vec<u8>, JsValue> {
@@ -286,51 +487,375 @@ impl Session {
         Ref::map(self.borrow(), |x| &x.config)
     }
 
-    /// Get all unique column values for a given column name.
-    ///
-    /// Use the `.to_csv()` method, as I suspected copying this large string
-    /// once was more efficient than copying many smaller strings, and
-    /// string copying shows up frequently when doing performance analysis.
-    ///
-    /// TODO Does not work with expressions yet.
+    /// Get the distinct values of a column (or expression), in ascending order,
+    /// with the number of rows which have each value.  `search` is applied by
+    /// the engine for `"string"` columns, as is paging, so this is suitable
+    /// for high-cardinality columns.
     ///
     /// # Arguments
     /// - `column` The name of the column (or expression).
-    pub async fn get_column_values(&self, column: String) -> Result<Vec<String>, JsValue> {
+    /// - `options` The `search`, `offset` and `limit` to apply.
+    pub async fn get_column_values(
+        &self,
+        column: String,
+        options: &ColumnValuesOptions,
+    ) -> ApiResult<ColumnValues> {
+        let table = self.borrow().table.clone().into_apierror()?;
+        self.get_column_values_for(&table, column, options).await
+    }
+
+    /// The body of `get_column_values()`, against an explicit `table`.
+    async fn get_column_values_for<T: TableBackend>(
+        &self,
+        table: &T,
+        column: String,
+        options: &ColumnValuesOptions,
+    ) -> ApiResult<ColumnValues> {
+        #[derive(Deserialize)]
+        struct ColumnValuesColumns {
+            #[serde(rename = "__ROW_PATH__", default)]
+            row_paths: Vec<Vec<Scalar>>,
+
+            #[serde(flatten)]
+            counts: HashMap<String, Vec<Option<f64>>>,
+        }
+
+        let ty = self.metadata().get_column_table_type(&column);
+        let is_searchable = ty == Some(Type::String);
+        let count = Aggregate::SingleAggregate(SingleAggregate::Count);
+        let mut config = ViewConfig {
+            group_by: vec![column.clone()],
+            columns: vec![Some(column.clone())],
+            expressions: self.borrow().config.expressions.clone(),
+            aggregates: HashMap::from([(column.clone(), count.into())]),
+            ..ViewConfig::default()
+        };
+
+        if let Some(search) = options.search().filter(|_| is_searchable) {
+            let term = FilterTerm::Scalar(Scalar::String(search.to_owned()));
+            let filter = Filter(column.clone(), FilterOp::BeginsWithIgnoreCase, term);
+            config.filter.push(filter.into());
+        }
+
+        // Row `0` is the total, and the engine can only page if it can search.
+        let is_paged = is_searchable || options.search().is_none();
+        let start_row = if is_paged { options.offset + 1 } else { 1 };
+        let to_columns_options = ToColumnsOptions {
+            start_row: Some(start_row),
+            end_row: options
+                .limit
+                .filter(|_| is_paged)
+                .map(|limit| start_row + limit),
+        };
+
+        let view = table.view(&config).await?;
+        let result = async {
+            let num_rows = view.dimensions().await?.num_view_rows;
+            let columns: ColumnValuesColumns = view.to_columns(to_columns_options).await?;
+            ApiResult::Ok((num_rows, columns))
+        }
+        .await;
+
+        view.delete().await?;
+        let (num_rows, mut columns) = result?;
+        let counts = columns.counts.remove(&column).unwrap_or_default();
+        let values = columns
+            .row_paths
+            .into_iter()
+            .zip(counts)
+            .map(|(mut row_path, count)| ColumnValue {
+                value: match (row_path.pop(), ty) {
+                    (Some(value), Some(ty)) => value.coerce(ty),
+                    (value, _) => value.unwrap_or(Scalar::Null),
+                },
+                count: count.unwrap_or_default() as usize,
+            })
+            .collect();
+
+        if is_paged {
+            Ok(ColumnValues {
+                values,
+                total: num_rows.saturating_sub(1),
+            })
+        } else {
+            Ok(ColumnValues::new(values, options))
+        }
+    }
+
+    /// Compute summary statistics of `column` from auxiliary `View`s of the
+    /// `Table`.  These are cached until the `Table` is updated (even while this
+    /// `Session` is paused) or replaced, or the expressions change.
+    pub async fn get_column_stats(&self, column: String) -> ApiResult<ColumnStats> {
+        let table = self.borrow().table.clone().into_apierror()?;
+        self.get_column_stats_for(&table, column).await
+    }
+
+    /// The body of `get_column_stats()`, against an explicit `table`.
+    async fn get_column_stats_for<T: TableBackend>(
+        &self,
+        table: &T,
+        column: String,
+    ) -> ApiResult<ColumnStats> {
+        #[derive(Deserialize)]
+        struct ColumnStatsColumns {
+            #[serde(rename = "__ROW_PATH__", default)]
+            row_paths: Vec<Vec<Scalar>>,
+
+            #[serde(flatten)]
+            values: HashMap<String, Vec<Option<f64>>>,
+        }
+
+        if let Some(stats) = self.borrow().column_stats.get(&column) {
+            return Ok(stats.clone());
+        }
+
+        let generation = self.borrow().column_stats_generation;
+
+        let ty = self
+            .metadata()
+            .get_column_table_type(&column)
+            .ok_or_else(|| format!("Unknown column \"{}\"", column))?;
+
+        let is_numeric = matches!(ty, Type::Integer | Type::Float);
         let expressions = self.borrow().config.expressions.clone();
-        let config = ViewConfig {
-            group_by: vec![column],
-            columns: vec![],
-            expressions,
+        let aggregate = if is_numeric {
+            SingleAggregate::Avg
+        } else {
+            SingleAggregate::Count
+        };
+
+        // Row `0` is the total and the groups are sorted, so the first and last
+        // non-`null` groups are the `min` and `max`.
+        let summary_config = ViewConfig {
+            group_by: vec![column.clone()],
+            columns: vec![Some(column.clone())],
+            expressions: expressions.clone(),
+            aggregates: HashMap::from([(
+                column.clone(),
+                Aggregate::SingleAggregate(aggregate).into(),
+            )]),
             ..ViewConfig::default()
         };
 
-        let js_config = config.as_jsvalue()?;
-        let table = self.borrow().table.clone().unwrap();
-        let view = table.view(&js_config).await?;
-        let csv = view
-            .to_csv(json!({}))
-            .await?
-            .as_string()
-            .ok_or_else(|| JsValue::from("Bad CSV"))?;
+        let null_config = ViewConfig {
+            columns: vec![Some(column.clone())],
+            expressions: expressions.clone(),
+            filter: vec![Filter(
+                column.clone(),
+                FilterOp::IsNull,
+                FilterTerm::Scalar(Scalar::Null),
+            )
+            .into()],
+            ..ViewConfig::default()
+        };
 
-        ApiFuture::spawn(async move {
-            view.delete().await?;
-            Ok(())
-        });
+        let view = table.view(&summary_config).await?;
+        let result = async {
+            let dimensions = view.dimensions().await?;
+            let num_rows = dimensions.num_view_rows;
+            let head: ColumnStatsColumns = view
+                .to_columns(ToColumnsOptions {
+                    end_row: Some(3),
+                    ..ToColumnsOptions::default()
+                })
+                .await?;
+
+            let tail: ColumnStatsColumns = view
+                .to_columns(ToColumnsOptions {
+                    start_row: Some(num_rows.saturating_sub(2).max(1)),
+                    ..ToColumnsOptions::default()
+                })
+                .await?;
+
+            ApiResult::Ok((dimensions.num_table_rows, num_rows, head, tail))
+        }
+        .await;
+
+        view.delete().await?;
+        let (count, num_rows, mut head, tail) = result?;
+        let mean = head
+            .values
+            .remove(&column)
+            .and_then(|x| x.into_iter().next().flatten())
+            .filter(|_| is_numeric);
+
+        let group_value = |mut row_path: Vec<Scalar>| match row_path.pop() {
+            Some(Scalar::Null) | None => None,
+            Some(value) => Some(value.coerce(ty)),
+        };
 
-        let res = csv
-            .lines()
-            .map(|val| {
-                if val.starts_with('\"') && val.ends_with('\"') {
-                    (val[1..val.len() - 1]).to_owned()
-                } else {
-                    val.to_owned()
-                }
+        let min = head.row_paths.into_iter().skip(1).find_map(group_value);
+        let max = tail
+            .row_paths
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .rev()
+            .find_map(group_value);
+
+        let view = table.view(&null_config).await?;
+        let null_count = view.dimensions().await.map(|x| x.num_view_rows);
+        view.delete().await?;
+        let histogram = match (&min, &max) {
+            (Some(Scalar::Float(min)), Some(Scalar::Float(max))) if min < max => {
+                self.get_histogram(table, &column, *min, *max).await?
+            },
+            (Some(Scalar::Int(min)), Some(Scalar::Int(max))) if min < max => {
+                self.get_histogram(table, &column, *min as f64, *max as f64)
+                    .await?
+            },
+            _ => vec![],
+        };
+
+        let stats = ColumnStats {
+            count,
+            null_count: null_count?,
+            distinct_count: num_rows.saturating_sub(1),
+            min,
+            max,
+            mean,
+            histogram,
+        };
+
+        // The `Table` may have been updated (or replaced) while unlocked.
+        if self.borrow().column_stats_generation == generation {
+            self.borrow_mut().column_stats.insert(column, stats.clone());
+        }
+
+        Ok(stats)
+    }
+
+    /// Discard the cached `ColumnStats`, including any still being computed.
+    fn clear_column_stats(&self) {
+        self.borrow_mut().column_stats.clear();
+        self.borrow_mut().column_stats_generation += 1;
+    }
+
+    /// The `ColumnStats::histogram` of the numeric `column` with values in
+    /// `[min, max]`, from an auxiliary `View` grouped by bucket index.
+    async fn get_histogram<T: TableBackend>(
+        &self,
+        table: &T,
+        column: &str,
+        min: f64,
+        max: f64,
+    ) -> ApiResult<Vec<HistogramBucket>> {
+        #[derive(Deserialize)]
+        struct HistogramColumns {
+            #[serde(rename = "__ROW_PATH__", default)]
+            row_paths: Vec<Vec<Scalar>>,
+
+            #[serde(flatten)]
+            counts: HashMap<String, Vec<Option<f64>>>,
+        }
+
+        let count = Aggregate::SingleAggregate(SingleAggregate::Count);
+        let expr = histogram_expression(column, min, max);
+        let mut config = ViewConfig {
+            group_by: vec![HISTOGRAM_EXPRESSION.to_owned()],
+            columns: vec![Some(column.to_owned())],
+            expressions: self.borrow().config.expressions.clone(),
+            aggregates: HashMap::from([(column.to_owned(), count.into())]),
+            ..ViewConfig::default()
+        };
+
+        config.expressions.insert(&Expression::new(
+            Some(HISTOGRAM_EXPRESSION.into()),
+            expr.into(),
+        ));
+
+        let view = table.view(&config).await?;
+        let columns = view
+            .to_columns(ToColumnsOptions {
+                start_row: Some(1),
+                ..ToColumnsOptions::default()
             })
-            .skip(2)
-            .collect::<Vec<String>>();
-        Ok(res)
+            .await;
+
+        view.delete().await?;
+        let mut columns: HistogramColumns = columns?;
+        let counts = columns.counts.remove(column).unwrap_or_default();
+        let indices = columns
+            .row_paths
+            .into_iter()
+            .zip(counts)
+            .map(|(mut row_path, count)| {
+                let index = row_path.pop().unwrap_or(Scalar::Null);
+                (index, count.unwrap_or_default() as usize)
+            });
+
+        Ok(histogram_buckets(min, max, indices))
+    }
+
+    /// Resolve the `top_n` of `config` (which must be validated) to the filter
+    /// which implements it, from an auxiliary `View` of `table`.  See `TopN`.
+    /// As this depends on the `Table`'s data, it is re-resolved after each
+    /// update, see `Session::update_top_n()`.
+    async fn get_top_n_filter<T: TableBackend>(
+        &self,
+        table: &T,
+        config: &ViewConfig,
+    ) -> ApiResult<Option<FilterItem>> {
+        #[derive(Deserialize)]
+        struct TopNColumns {
+            #[serde(rename = "__ROW_PATH__", default)]
+            row_paths: Vec<Vec<Scalar>>,
+
+            #[serde(flatten)]
+            values: HashMap<String, Vec<Scalar>>,
+        }
+
+        let Some(top_n) = &config.top_n else {
+            return Ok(None);
+        };
+
+        let aux_config = top_n.aux_config(config);
+        let view = table.view(&aux_config).await?;
+        let options = ToColumnsOptions {
+            end_row: aux_config.group_by.is_empty().then_some(top_n.n.get()),
+            ..ToColumnsOptions::default()
+        };
+
+        let columns = view.to_columns(options).await;
+        view.delete().await?;
+        let mut columns: TopNColumns = columns?;
+        let values = columns.values.remove(&top_n.column).unwrap_or_default();
+        Ok(top_n.to_filter(&config.group_by, &columns.row_paths, &values))
+    }
+
+    /// Re-resolve the `top_n` filter of the current `View`, if the `Table` has
+    /// been updated since it was resolved.  This is called by the `Renderer`
+    /// once per throttled update, rather than for every `Table` update.  If
+    /// the top rows changed, the `View` is invalidated and `table_updated` is
+    /// emitted without a delta, so it is recreated with the new filter rather
+    /// than updated.
+    pub async fn update_top_n(&self) -> ApiResult<()> {
+        let table = self.borrow().table.clone();
+        match table {
+            Some(table) => self.update_top_n_for(&table).await,
+            None => Ok(()),
+        }
+    }
+
+    /// The body of `update_top_n()`, against an explicit `table`.
+    async fn update_top_n_for<T: TableBackend>(&self, table: &T) -> ApiResult<()> {
+        let state = self.borrow_mut().top_n.as_mut().and_then(|state| {
+            let is_dirty = std::mem::take(&mut state.is_dirty);
+            is_dirty.then(|| state.clone())
+        });
+
+        let Some(state) = state else {
+            return Ok(());
+        };
+
+        let filter = self.get_top_n_filter(table, &state.config).await?;
+        if self.borrow().view_generation == state.generation && filter != state.filter {
+            self.borrow_mut().view_sub = None;
+            self.borrow_mut().view_generation += 1;
+            self.borrow_mut().is_clean = false;
+            self.table_updated.emit(None);
+        }
+
+        Ok(())
     }
 
     pub fn set_update_column_defaults(
@@ -348,8 +873,14 @@ impl Session {
     /// Update the config, setting the `columns` property to the plugin defaults
     /// if provided.
     pub fn update_view_config(&self, config_update: ViewConfigUpdate) {
+        // Expression columns may have been redefined.
+        if config_update.expressions.is_some() {
+            self.clear_column_stats();
+        }
+
         if self.borrow_mut().config.apply_update(config_update) {
             self.borrow_mut().view_sub = None;
+            self.borrow_mut().view_generation += 1;
             self.0.borrow_mut().is_clean = false;
             self.view_config_changed.emit(());
         }
@@ -365,20 +896,34 @@ impl Session {
     }
 
     /// In order to create a new view in this session, the session must first be
-    /// validated to create a `ValidSession<'_>` guard.
+    /// validated to create a `ValidSession<'_>` guard.  Invalid entries are
+    /// removed from the `ViewConfig` and reported via `config_invalid`; the
+    /// config is only reset to the default if it cannot be validated at all.
     pub async fn validate(&self) -> Result<ValidSession<'_>, JsValue> {
-        if let Err(err) = self.validate_view_config().await {
-            web_sys::console::error_3(
-                &"Invalid config, resetting to default".into(),
-                &JsValue::from_serde_ext(&self.borrow().config).unwrap(),
-                &err.into(),
+        let report = match self.validate_view_config().await {
+            Ok(report) => report,
+            Err(err) => {
+                web_sys::console::error_3(
+                    &"Invalid config, resetting to default".into(),
+                    &JsValue::from_serde_ext(&self.borrow().config).unwrap(),
+                    &err.into(),
+                );
+
+                self.reset(true);
+                self.validate_view_config().await?
+            },
+        };
+
+        if !report.is_empty() {
+            web_sys::console::warn_2(
+                &"Invalid config entries removed".into(),
+                &JsValue::from_serde_ext(&report)?,
             );
 
-            self.reset(true);
-            self.validate_view_config().await?;
+            self.config_invalid.emit(report.clone());
         }
 
-        Ok(ValidSession(self))
+        Ok(ValidSession(self, report))
     }
 
     // async fn get_validated_expression_name(&self, expr: &JsValue) ->
@@ -411,20 +956,10 @@ impl Session {
         self.stats_changed.emit(());
     }
 
-    async fn validate_view_config(&self) -> ApiResult<()> {
-        let config = self.borrow().config.clone();
-        let table_columns = self
-            .metadata()
-            .get_table_columns()
-            .into_iter()
-            .flatten()
-            .cloned()
-            .collect::<Vec<String>>();
-
-        let all_columns: HashSet<String> = table_columns.iter().cloned().collect();
-
-        let mut view_columns: HashSet<&str> = HashSet::new();
-
+    /// Validate the `ViewConfig` against the `Table`, removing (and reporting)
+    /// any entry which references an unknown column or is otherwise invalid.
+    /// Only fails if the `Table` itself cannot be queried.
+    async fn validate_view_config(&self) -> ApiResult<ViewConfigReport> {
         let table = self
             .borrow()
             .table
@@ -432,65 +967,56 @@ impl Session {
             .ok_or("`restore()` called before `load()`")?
             .clone();
 
-        let arr = JsValue::from_serde_ext(&config.expressions)?.unchecked_into::<js_sys::Object>();
+        self.validate_view_config_for(&table).await
+    }
 
-        let valid_recs = table.validate_expressions(arr).await?;
+    /// The body of `validate_view_config()`, against an explicit `table`.
+    async fn validate_view_config_for<T: TableBackend>(
+        &self,
+        table: &T,
+    ) -> ApiResult<ViewConfigReport> {
+        let expressions = self.borrow().config.expressions.clone();
+        let (valid_recs, invalid_expressions) = validate_expressions(table, expressions).await?;
         let expression_names = self.metadata_mut().update_expressions(&valid_recs)?;
 
-        // re-fetch config after `await`; `expressions` and `all_columns` are ok,
-        // but `config` may have changed as it is unlocked.
+        // re-fetch config after `await`, as it may have changed while unlocked.
         let mut config = self.borrow().config.clone();
+        let report = config.validate(&self.metadata(), &expression_names, invalid_expressions);
+        self.borrow_mut().config = config;
+        Ok(report)
+    }
 
-        if config.columns.is_empty() {
-            config.columns = table_columns.into_iter().map(Some).collect();
-        }
-
-        for column in config.columns.iter().flatten() {
-            if all_columns.contains(column) || expression_names.contains(column) {
-                let _existed = view_columns.insert(column);
-            } else {
-                return Err(format!("Unknown \"{}\" in `columns`", column).into());
-            }
-        }
-
-        for column in config.group_by.iter() {
-            if all_columns.contains(column) || expression_names.contains(column) {
-                let _existed = view_columns.insert(column);
-            } else {
-                return Err(format!("Unknown \"{}\" in `group_by`", column).into());
-            }
-        }
-
-        for column in config.split_by.iter() {
-            if all_columns.contains(column) || expression_names.contains(column) {
-                let _existed = view_columns.insert(column);
-            } else {
-                return Err(format!("Unknown \"{}\" in `split_by`", column).into());
-            }
+    /// Whether the `View` generation `generation` has been invalidated, in
+    /// which case this `Session` is marked dirty for the `create_view()` call
+    /// which follows the invalidating change.
+    fn is_stale(&self, generation: u64) -> bool {
+        let is_stale = self.borrow().view_generation != generation;
+        if is_stale {
+            self.borrow_mut().is_clean = false;
         }
 
-        for sort in config.sort.iter() {
-            if all_columns.contains(&sort.0) || expression_names.contains(&sort.0) {
-                let _existed = view_columns.insert(&sort.0);
-            } else {
-                return Err(format!("Unknown \"{}\" in `sort`", sort.0).into());
-            }
-        }
+        is_stale
+    }
 
-        for filter in config.filter.iter() {
-            if all_columns.contains(&filter.0) || expression_names.contains(&filter.0) {
-                let _existed = view_columns.insert(&filter.0);
-            } else {
-                return Err(format!("Unknown \"{}\" in `filter`", &filter.0).into());
-            }
+    /// Create a `View` of `table` for `config` and update the `View` schema
+    /// metadata from it, unless the `View` generation `generation` is
+    /// invalidated while it is being created, in which case it is deleted and
+    /// `None` is returned.
+    async fn create_backend_view<T: TableBackend>(
+        &self,
+        table: &T,
+        config: &ViewConfig,
+        generation: u64,
+    ) -> ApiResult<Option<T::View>> {
+        let view = table.view(config).await?;
+        let view_schema = view.schema().await;
+        if self.is_stale(generation) {
+            view.delete().await?;
+            return Ok(None);
         }
 
-        config
-            .aggregates
-            .retain(|column, _| view_columns.contains(column.as_str()));
-
-        self.borrow_mut().config = config;
-        Ok(())
+        self.metadata_mut().update_view_schema(view_schema?);
+        Ok(Some(view))
     }
 
     fn reset_clean(&self) -> bool {
@@ -501,15 +1027,39 @@ impl Session {
 }
 
 /// A newtype wrapper which only provides `create_view()`
-pub struct ValidSession<'a>(&'a Session);
+pub struct ValidSession<'a>(&'a Session, ViewConfigReport);
 
 impl<'a> ValidSession<'a> {
+    /// The entries removed from the `ViewConfig` by the `validate()` call
+    /// which created this `ValidSession`.
+    pub fn report(&self) -> &ViewConfigReport {
+        &self.1
+    }
+
     /// Set a new `View` (derived from this `Session`'s `Table`), and create the
     /// `update()` subscription, consuming this `ValidSession<'_>` and returning
     /// the original `&Session`.
     pub async fn create_view(&self) -> Result<&'a Session, ApiError> {
-        let js_config = self.0.borrow().config.as_jsvalue()?;
+        let mut config = self.0.borrow().config.clone();
+        let linked_filter = self.0.borrow().linked_filter.clone();
+        config
+            .filter
+            .extend(linked_filter.into_iter().map(FilterItem::from));
+        {
+            let now = get_local_now()?;
+            let metadata = self.0.metadata();
+            config.filter.for_each_filter_mut(&mut |filter| {
+                let is_date = metadata.get_column_table_type(&filter.0) == Some(Type::Date);
+                filter.resolve_relative_date(now, is_date);
+            });
+        }
+
         if !self.0.reset_clean() && !self.0.borrow().is_paused {
+            // If the `View` is invalidated while it is being created, e.g. by a
+            // rapid series of config changes, it is deleted rather than drawn
+            // and the `Session` is left dirty for the `create_view()` call
+            // which follows the change.
+            let generation = self.0.borrow().view_generation;
             let table = self
                 .0
                 .borrow()
@@ -517,9 +1067,21 @@ impl<'a> ValidSession<'a> {
                 .clone()
                 .ok_or("`restore()` called before `load()`")?;
 
-            let view = table.view(&js_config).await?;
-            let view_schema = view.schema().await?;
-            self.0.metadata_mut().update_view_schema(&view_schema)?;
+            let top_n_config = config.top_n.is_some().then(|| config.clone());
+            let top_n_filter = self.0.get_top_n_filter(&table, &config).await?;
+            if let Some(filter) = top_n_filter.clone() {
+                config.filter.push(filter);
+                config.coerce_filters(&self.0.metadata());
+            }
+
+            if self.0.is_stale(generation) {
+                return Ok(self.0);
+            }
+
+            let view = self.0.create_backend_view(&table, &config, generation);
+            let Some(view) = view.await? else {
+                return Ok(self.0);
+            };
 
             let on_stats = Callback::from({
                 let this = self.0.clone();
@@ -528,13 +1090,31 @@ impl<'a> ValidSession<'a> {
 
             let sub = {
                 let config = self.0.borrow().config.clone();
-                let on_update = self.0.table_updated.callback();
-                ViewSubscription::new(view, config, on_stats, on_update)
+                let delta_updates = self.0.borrow().delta_updates;
+                let on_update = Callback::from({
+                    let this = self.0.clone();
+                    move |delta| {
+                        this.clear_column_stats();
+                        if let Some(state) = this.borrow_mut().top_n.as_mut() {
+                            state.is_dirty = true;
+                        }
+
+                        this.table_updated.emit(delta)
+                    }
+                });
+
+                ViewSubscription::new(view, config, delta_updates, on_stats, on_update)
             };
 
             // self.0.borrow_mut().metadata.as_mut().unwrap().view_schema =
             // Some(view_schema);
             self.0.borrow_mut().view_sub = Some(sub);
+            self.0.borrow_mut().top_n = top_n_config.map(|config| TopNState {
+                generation,
+                config,
+                filter: top_n_filter,
+                is_dirty: false,
+            });
         }
 
         Ok(self.0)
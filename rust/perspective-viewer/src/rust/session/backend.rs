@@ -0,0 +1,152 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A Rust interface to the Perspective engine's `Table` and `View`, for the
+//! parts of `Session` which only need their schemas, expression metadata,
+//! dimensions and (for auxiliary `View`s) data.  It is implemented for the
+//! `JsPerspectiveTable` and `JsPerspectiveView` wasm-bindgen externs, and (for
+//! tests) by the in-memory `MockTable` and `MockView`, which do not need a
+//! browser.
+
+#[cfg(test)]
+mod mock;
+
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+#[cfg(test)]
+pub use self::mock::*;
+use crate::config::*;
+use crate::js::perspective::*;
+use crate::utils::*;
+
+/// The result of validating `Expressions` against a `Table`, keyed by
+/// expression name.
+#[derive(Clone, Debug, Default)]
+pub struct ValidatedExpressions {
+    pub errors: HashMap<String, PerspectiveValidationError>,
+    pub expression_schema: HashMap<String, Type>,
+    pub expression_alias: HashMap<String, String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ViewDimensions {
+    pub num_table_rows: usize,
+    pub num_table_columns: usize,
+    pub num_view_rows: usize,
+    pub num_view_columns: usize,
+}
+
+/// The row range of `ViewBackend::to_columns()`, which is all rows by default.
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct ToColumnsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_row: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_row: Option<usize>,
+}
+
+pub trait TableBackend {
+    type View: ViewBackend;
+
+    async fn columns(&self) -> ApiResult<Vec<String>>;
+
+    async fn schema(&self) -> ApiResult<HashMap<String, Type>>;
+
+    async fn make_port(&self) -> ApiResult<f64>;
+
+    async fn validate_expressions(
+        &self,
+        expressions: &Expressions,
+    ) -> ApiResult<ValidatedExpressions>;
+
+    async fn view(&self, config: &ViewConfig) -> ApiResult<Self::View>;
+}
+
+pub trait ViewBackend {
+    async fn dimensions(&self) -> ApiResult<ViewDimensions>;
+
+    async fn schema(&self) -> ApiResult<HashMap<String, Type>>;
+
+    /// The rows of this `View` in `View::to_columns()` format, including the
+    /// `__ROW_PATH__` column if it has a `group_by`.
+    async fn to_columns<T: DeserializeOwned>(&self, options: ToColumnsOptions) -> ApiResult<T>;
+
+    async fn delete(self) -> ApiResult<()>;
+}
+
+impl TableBackend for JsPerspectiveTable {
+    type View = JsPerspectiveView;
+
+    async fn columns(&self) -> ApiResult<Vec<String>> {
+        let columns = self.columns().await?;
+        Ok(columns.iter().filter_map(|x| x.as_string()).collect())
+    }
+
+    async fn schema(&self) -> ApiResult<HashMap<String, Type>> {
+        Ok(self.schema().await?.into_serde_ext()?)
+    }
+
+    async fn make_port(&self) -> ApiResult<f64> {
+        self.make_port().await
+    }
+
+    async fn validate_expressions(
+        &self,
+        expressions: &Expressions,
+    ) -> ApiResult<ValidatedExpressions> {
+        let arr = JsValue::from_serde_ext(expressions)?.unchecked_into::<js_sys::Object>();
+        let valid_recs = self.validate_expressions(arr).await?;
+        Ok(ValidatedExpressions {
+            errors: valid_recs.errors().into_serde_ext()?,
+            expression_schema: valid_recs.expression_schema().into_serde_ext()?,
+            expression_alias: valid_recs.expression_alias().into_serde_ext()?,
+        })
+    }
+
+    async fn view(&self, config: &ViewConfig) -> ApiResult<JsPerspectiveView> {
+        self.view(&config.as_jsvalue()?).await
+    }
+}
+
+impl ViewBackend for JsPerspectiveView {
+    async fn dimensions(&self) -> ApiResult<ViewDimensions> {
+        let dimensions = self.dimensions().await?;
+        Ok(ViewDimensions {
+            num_table_rows: dimensions.num_table_rows() as usize,
+            num_table_columns: dimensions.num_table_columns() as usize,
+            num_view_rows: dimensions.num_view_rows() as usize,
+            num_view_columns: dimensions.num_view_columns() as usize,
+        })
+    }
+
+    async fn schema(&self) -> ApiResult<HashMap<String, Type>> {
+        Ok(self.schema().await?.into_serde_ext()?)
+    }
+
+    async fn to_columns<T: DeserializeOwned>(&self, options: ToColumnsOptions) -> ApiResult<T> {
+        let options = JsValue::from_serde_ext(&options)?.unchecked_into::<js_sys::Object>();
+        Ok(self
+            .to_columns_with_options(options)
+            .await?
+            .into_serde_ext()?)
+    }
+
+    async fn delete(self) -> ApiResult<()> {
+        self.delete().await
+    }
+}
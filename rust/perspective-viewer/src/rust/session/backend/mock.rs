@@ -0,0 +1,438 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use super::*;
+use crate::config::*;
+use crate::js::perspective::PerspectiveValidationError;
+use crate::utils::*;
+
+/// An in-memory `TableBackend` for tests.  Expressions are only valid if
+/// registered with `with_expression()`, and the `View`s it creates have the
+/// `Table` type of each of their columns, regardless of aggregate.  Column
+/// (and expression) values are set with `with_values()`, and are otherwise all
+/// `null`.  `View`s support the subset of the engine used by `Session`'s
+/// auxiliary `View`s: comparison, `null` and `begins with (ignore case)`
+/// filters, `asc` and `desc` sorts, and the `count`, `sum` and `avg`
+/// aggregates.
+#[derive(Clone, Debug, Default)]
+pub struct MockTable {
+    columns: Vec<(String, Type)>,
+    expressions: HashMap<String, Type>,
+    values: HashMap<String, Vec<Scalar>>,
+    num_rows: usize,
+}
+
+impl MockTable {
+    pub fn new<'a>(columns: impl IntoIterator<Item = (&'a str, Type)>) -> Self {
+        Self {
+            columns: columns
+                .into_iter()
+                .map(|(name, ty)| (name.to_owned(), ty))
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Register `expression` as a valid expression of type `ty`.
+    pub fn with_expression(mut self, expression: &str, ty: Type) -> Self {
+        self.expressions.insert(expression.to_owned(), ty);
+        self
+    }
+
+    pub fn with_num_rows(mut self, num_rows: usize) -> Self {
+        self.num_rows = num_rows;
+        self
+    }
+
+    /// Set the values of the column (or registered expression) `name`, and
+    /// the number of rows to their length.
+    pub fn with_values(mut self, name: &str, values: impl IntoIterator<Item = Scalar>) -> Self {
+        let values = values.into_iter().collect::<Vec<_>>();
+        self.num_rows = values.len();
+        self.values.insert(name.to_owned(), values);
+        self
+    }
+
+    /// The values of column (or expression) `name` of `config`.
+    fn column_values(&self, config: &ViewConfig, name: &str) -> Vec<Scalar> {
+        self.values
+            .get(name)
+            .or_else(|| self.values.get(config.expressions.get(name)?))
+            .cloned()
+            .unwrap_or_else(|| vec![Scalar::Null; self.num_rows])
+    }
+
+    fn column_type(&self, config: &ViewConfig, name: &str) -> Option<Type> {
+        self.columns
+            .iter()
+            .find(|(x, _)| x == name)
+            .map(|(_, ty)| *ty)
+            .or_else(|| {
+                let expression = config.expressions.get(name)?;
+                self.expressions.get(expression).cloned()
+            })
+    }
+}
+
+impl TableBackend for MockTable {
+    type View = MockView;
+
+    async fn columns(&self) -> ApiResult<Vec<String>> {
+        Ok(self.columns.iter().map(|(name, _)| name.clone()).collect())
+    }
+
+    async fn schema(&self) -> ApiResult<HashMap<String, Type>> {
+        Ok(self.columns.iter().cloned().collect())
+    }
+
+    async fn make_port(&self) -> ApiResult<f64> {
+        Ok(0.0)
+    }
+
+    async fn validate_expressions(
+        &self,
+        expressions: &Expressions,
+    ) -> ApiResult<ValidatedExpressions> {
+        let mut valid_recs = ValidatedExpressions::default();
+        for (name, expression) in expressions.iter() {
+            match self.expressions.get(expression) {
+                Some(ty) => {
+                    valid_recs.expression_schema.insert(name.clone(), *ty);
+                    valid_recs
+                        .expression_alias
+                        .insert(name.clone(), expression.clone());
+                },
+                None => {
+                    valid_recs
+                        .errors
+                        .insert(name.clone(), PerspectiveValidationError {
+                            error_message: format!("Unknown expression \"{}\"", expression),
+                            line: 0,
+                            column: 0,
+                        });
+                },
+            }
+        }
+
+        Ok(valid_recs)
+    }
+
+    async fn view(&self, config: &ViewConfig) -> ApiResult<MockView> {
+        let schema = config
+            .columns
+            .iter()
+            .flatten()
+            .filter_map(|name| Some((name.clone(), self.column_type(config, name)?)))
+            .collect::<HashMap<_, _>>();
+
+        let names = config
+            .columns
+            .iter()
+            .flatten()
+            .chain(config.group_by.iter())
+            .chain(config.sort.iter().map(|x| &x.0))
+            .chain(config.filter.iter_filters().map(|x| &x.0));
+
+        let data = names
+            .map(|name| (name.clone(), self.column_values(config, name)))
+            .collect::<HashMap<_, _>>();
+
+        let mut rows = vec![];
+        for row in 0..self.num_rows {
+            if is_match_all(&config.filter, &data, row)? {
+                rows.push(row);
+            }
+        }
+
+        let aggregate = |name: &str, rows: &[usize]| {
+            let aggregate = config.get_aggregate(name, 0).cloned().unwrap_or_else(|| {
+                self.column_type(config, name)
+                    .unwrap_or(Type::String)
+                    .default_aggregate()
+            });
+
+            aggregate_values(&aggregate, rows.iter().map(|row| &data[name][*row]))
+        };
+
+        let groups = if config.group_by.is_empty() {
+            for Sort(name, dir) in config.sort.iter().rev() {
+                let is_desc = is_desc(*dir)?;
+                rows.sort_by(|x, y| {
+                    let order = compare(&data[name][*x], &data[name][*y]);
+                    if is_desc {
+                        order.reverse()
+                    } else {
+                        order
+                    }
+                });
+            }
+
+            rows.into_iter().map(|row| (None, vec![row])).collect()
+        } else {
+            let mut groups = vec![];
+            group_rows(config, &data, &aggregate, rows, vec![], &mut groups)?;
+            groups
+                .into_iter()
+                .map(|(path, rows)| (Some(path), rows))
+                .collect::<Vec<_>>()
+        };
+
+        let mut row_paths = vec![];
+        let mut columns = config
+            .columns
+            .iter()
+            .flatten()
+            .map(|name| (name.clone(), vec![]))
+            .collect::<Vec<_>>();
+
+        for (path, rows) in groups.iter() {
+            if let Some(path) = path {
+                row_paths.push(path.clone());
+            }
+
+            for (name, values) in columns.iter_mut() {
+                values.push(match path {
+                    Some(_) => aggregate(name, rows)?,
+                    None => data[name][rows[0]].clone(),
+                });
+            }
+        }
+
+        let dimensions = ViewDimensions {
+            num_table_rows: self.num_rows,
+            num_table_columns: self.columns.len(),
+            num_view_rows: groups.len(),
+            num_view_columns: schema.len(),
+        };
+
+        Ok(MockView {
+            dimensions,
+            schema,
+            row_paths: (!config.group_by.is_empty()).then_some(row_paths),
+            columns,
+        })
+    }
+}
+
+/// Group `rows` by `config.group_by[path.len()]`, appending each group's
+/// `path` and rows to `groups` in the order of the engine's `__ROW_PATH__`:
+/// the parent group, then each child group (sorted by value, or by their
+/// aggregate of a `config.sort` column) recursively.
+fn group_rows(
+    config: &ViewConfig,
+    data: &HashMap<String, Vec<Scalar>>,
+    aggregate: &impl Fn(&str, &[usize]) -> ApiResult<Scalar>,
+    rows: Vec<usize>,
+    path: Vec<Scalar>,
+    groups: &mut Vec<(Vec<Scalar>, Vec<usize>)>,
+) -> ApiResult<()> {
+    let Some(name) = config.group_by.get(path.len()) else {
+        groups.push((path, rows));
+        return Ok(());
+    };
+
+    let mut children: Vec<(Scalar, Vec<usize>)> = vec![];
+    for row in rows.iter() {
+        let value = &data[name][*row];
+        match children.iter_mut().find(|(x, _)| x == value) {
+            Some((_, group)) => group.push(*row),
+            None => children.push((value.clone(), vec![*row])),
+        }
+    }
+
+    children.sort_by(|(x, _), (y, _)| compare(x, y));
+    for Sort(name, dir) in config.sort.iter().rev() {
+        let is_desc = is_desc(*dir)?;
+        let mut keyed = children
+            .into_iter()
+            .map(|(value, rows)| Ok((aggregate(name, &rows)?, value, rows)))
+            .collect::<ApiResult<Vec<_>>>()?;
+
+        keyed.sort_by(|(x, ..), (y, ..)| {
+            let order = compare(x, y);
+            if is_desc {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+
+        children = keyed
+            .into_iter()
+            .map(|(_, value, rows)| (value, rows))
+            .collect();
+    }
+
+    groups.push((path.clone(), rows));
+    for (value, rows) in children {
+        let mut path = path.clone();
+        path.push(value);
+        group_rows(config, data, aggregate, rows, path, groups)?;
+    }
+
+    Ok(())
+}
+
+fn is_desc(dir: SortDir) -> ApiResult<bool> {
+    match dir {
+        SortDir::Asc => Ok(false),
+        SortDir::Desc => Ok(true),
+        dir => Err(format!("Unsupported sort `{}`", dir).into()),
+    }
+}
+
+/// Order `Scalar`s as the engine sorts them, with `null` first.
+fn compare(x: &Scalar, y: &Scalar) -> Ordering {
+    match (x, y) {
+        (Scalar::Null, Scalar::Null) => Ordering::Equal,
+        (Scalar::Null, _) => Ordering::Less,
+        (_, Scalar::Null) => Ordering::Greater,
+        (Scalar::String(x), Scalar::String(y)) => x.cmp(y),
+        (Scalar::Bool(x), Scalar::Bool(y)) => x.cmp(y),
+        (x, y) => as_f64(x).partial_cmp(&as_f64(y)).unwrap_or(Ordering::Equal),
+    }
+}
+
+fn as_f64(x: &Scalar) -> Option<f64> {
+    match x {
+        Scalar::Float(x) | Scalar::DateTime(x) => Some(*x),
+        Scalar::Int(x) | Scalar::Date(x) => Some(*x as f64),
+        _ => None,
+    }
+}
+
+fn is_match_all(
+    filters: &[FilterItem],
+    data: &HashMap<String, Vec<Scalar>>,
+    row: usize,
+) -> ApiResult<bool> {
+    for item in filters {
+        if !is_match(item, data, row)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn is_match(item: &FilterItem, data: &HashMap<String, Vec<Scalar>>, row: usize) -> ApiResult<bool> {
+    let Filter(name, op, term) = match item {
+        FilterItem::Filter(filter) => filter,
+        FilterItem::Group(FilterGroup {
+            op: FilterReducer::And,
+            filters,
+        }) => return is_match_all(filters, data, row),
+        FilterItem::Group(FilterGroup {
+            op: FilterReducer::Or,
+            filters,
+        }) => {
+            for item in filters {
+                if is_match(item, data, row)? {
+                    return Ok(true);
+                }
+            }
+
+            return Ok(false);
+        },
+    };
+
+    let value = &data[name][row];
+    let term = match term {
+        FilterTerm::Scalar(x) => x,
+        _ => return Err(format!("Unsupported filter `{}`", op).into()),
+    };
+
+    Ok(match (op, value) {
+        (FilterOp::IsNull, _) => *value == Scalar::Null,
+        (FilterOp::IsNotNull, _) => *value != Scalar::Null,
+        (_, Scalar::Null) => false,
+        (FilterOp::EQ, _) => compare(value, term) == Ordering::Equal,
+        (FilterOp::NE, _) => compare(value, term) != Ordering::Equal,
+        (FilterOp::LT, _) => compare(value, term) == Ordering::Less,
+        (FilterOp::LTE, _) => compare(value, term) != Ordering::Greater,
+        (FilterOp::GT, _) => compare(value, term) == Ordering::Greater,
+        (FilterOp::GTE, _) => compare(value, term) != Ordering::Less,
+        (FilterOp::BeginsWithIgnoreCase, Scalar::String(x)) => x
+            .to_lowercase()
+            .starts_with(&term.to_string().to_lowercase()),
+        _ => return Err(format!("Unsupported filter `{}`", op).into()),
+    })
+}
+
+fn aggregate_values<'a>(
+    aggregate: &Aggregate,
+    values: impl Iterator<Item = &'a Scalar>,
+) -> ApiResult<Scalar> {
+    let values = values.collect::<Vec<_>>();
+    let numbers = values.iter().filter_map(|x| as_f64(x)).collect::<Vec<_>>();
+    match aggregate {
+        Aggregate::SingleAggregate(SingleAggregate::Count) => {
+            Ok(Scalar::Float(values.len() as f64))
+        },
+        Aggregate::SingleAggregate(SingleAggregate::Sum) => Ok(Scalar::Float(numbers.iter().sum())),
+        Aggregate::SingleAggregate(SingleAggregate::Avg) if numbers.is_empty() => Ok(Scalar::Null),
+        Aggregate::SingleAggregate(SingleAggregate::Avg) => Ok(Scalar::Float(
+            numbers.iter().sum::<f64>() / numbers.len() as f64,
+        )),
+        aggregate => Err(format!("Unsupported aggregate `{}`", aggregate).into()),
+    }
+}
+
+/// An in-memory `ViewBackend` for tests, see `MockTable`.
+#[derive(Clone, Debug, Default)]
+pub struct MockView {
+    pub dimensions: ViewDimensions,
+    pub schema: HashMap<String, Type>,
+    pub row_paths: Option<Vec<Vec<Scalar>>>,
+    pub columns: Vec<(String, Vec<Scalar>)>,
+}
+
+impl ViewBackend for MockView {
+    async fn dimensions(&self) -> ApiResult<ViewDimensions> {
+        Ok(self.dimensions)
+    }
+
+    async fn schema(&self) -> ApiResult<HashMap<String, Type>> {
+        Ok(self.schema.clone())
+    }
+
+    async fn to_columns<T: DeserializeOwned>(&self, options: ToColumnsOptions) -> ApiResult<T> {
+        let num_rows = self.dimensions.num_view_rows;
+        let start_row = options.start_row.unwrap_or_default().min(num_rows);
+        let end_row = options
+            .end_row
+            .unwrap_or(num_rows)
+            .clamp(start_row, num_rows);
+        let mut json = Map::new();
+        if let Some(row_paths) = &self.row_paths {
+            let row_paths = serde_json::to_value(&row_paths[start_row..end_row])?;
+            json.insert("__ROW_PATH__".to_owned(), row_paths);
+        }
+
+        for (name, values) in self.columns.iter() {
+            let values = serde_json::to_value(&values[start_row..end_row])?;
+            json.insert(name.clone(), values);
+        }
+
+        Ok(serde_json::from_value(Value::Object(json))?)
+    }
+
+    async fn delete(self) -> ApiResult<()> {
+        Ok(())
+    }
+}
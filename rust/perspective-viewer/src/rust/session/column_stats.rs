@@ -0,0 +1,163 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use serde::Serialize;
+
+use crate::config::*;
+
+/// The number of equal-width buckets in a `ColumnStats::histogram`.
+pub const HISTOGRAM_BUCKETS: usize = 10;
+
+/// The name of the bucket index expression of the histogram `View`.
+pub const HISTOGRAM_EXPRESSION: &str = "__HISTOGRAM_BUCKET__";
+
+/// Summary statistics of a `Table` column, as returned by
+/// `Session::get_column_stats()`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ColumnStats {
+    /// The number of rows in the `Table`.
+    pub count: usize,
+    pub null_count: usize,
+
+    /// The number of distinct values, including `null`.
+    pub distinct_count: usize,
+    pub min: Option<Scalar>,
+    pub max: Option<Scalar>,
+
+    /// The mean, for numeric columns only.
+    pub mean: Option<f64>,
+
+    /// `HISTOGRAM_BUCKETS` buckets spanning `min` to `max`, for numeric
+    /// columns with more than one distinct value only.
+    pub histogram: Vec<HistogramBucket>,
+}
+
+/// The number of rows whose value is in `[start, end)`, or `[start, end]` for
+/// the last bucket of a histogram.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HistogramBucket {
+    pub start: f64,
+    pub end: f64,
+    pub count: usize,
+}
+
+impl ColumnStats {
+    /// A one-line summary, e.g. for a tooltip.
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} rows, {} distinct, {} null",
+            self.count, self.distinct_count, self.null_count
+        );
+
+        if let (Some(min), Some(max)) = (&self.min, &self.max) {
+            summary.push_str(&format!(", {} to {}", min, max));
+        }
+
+        if let Some(mean) = self.mean {
+            summary.push_str(&format!(", mean {}", mean));
+        }
+
+        summary
+    }
+}
+
+/// The bucket width of a histogram of `[min, max]`.
+fn bucket_width(min: f64, max: f64) -> f64 {
+    (max - min) / HISTOGRAM_BUCKETS as f64
+}
+
+/// The expression which computes the bucket index of `column`'s values, for a
+/// histogram of `[min, max]`.  The index of `max` itself is
+/// `HISTOGRAM_BUCKETS`, which `histogram_buckets()` folds into the last bucket.
+pub fn histogram_expression(column: &str, min: f64, max: f64) -> String {
+    let column = column.replace('\\', "\\\\").replace('"', "\\\"");
+    format!(
+        "floor((\"{}\" - {:?}) / {:?})",
+        column,
+        min,
+        bucket_width(min, max)
+    )
+}
+
+/// Collect the row counts of a `View` grouped by `histogram_expression()`
+/// into `HISTOGRAM_BUCKETS` buckets, where `indices` are the group values (as
+/// `Scalar::Float`, `null` for `null` values, which are skipped).
+pub fn histogram_buckets(
+    min: f64,
+    max: f64,
+    indices: impl IntoIterator<Item = (Scalar, usize)>,
+) -> Vec<HistogramBucket> {
+    let width = bucket_width(min, max);
+    let mut buckets = (0..HISTOGRAM_BUCKETS)
+        .map(|idx| HistogramBucket {
+            start: min + width * idx as f64,
+            end: min + width * (idx + 1) as f64,
+            count: 0,
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(last) = buckets.last_mut() {
+        last.end = max;
+    }
+
+    for (index, count) in indices {
+        let index = match index {
+            Scalar::Float(x) if x >= 0.0 => x as usize,
+            Scalar::Int(x) if x >= 0 => x as usize,
+            _ => continue,
+        };
+
+        buckets[index.min(HISTOGRAM_BUCKETS - 1)].count += count;
+    }
+
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_histogram_buckets() {
+        assert_eq!(
+            histogram_expression("a \"b\"", 0.0, 20.0),
+            "floor((\"a \\\"b\\\"\" - 0.0) / 2.0)"
+        );
+
+        let indices = vec![
+            (Scalar::Null, 4),
+            (Scalar::Float(0.0), 1),
+            (Scalar::Float(3.0), 2),
+            (Scalar::Float(9.0), 3),
+            (Scalar::Float(10.0), 1),
+        ];
+
+        let buckets = histogram_buckets(0.0, 20.0, indices);
+        assert_eq!(buckets.len(), HISTOGRAM_BUCKETS);
+        assert_eq!(buckets[0], HistogramBucket {
+            start: 0.0,
+            end: 2.0,
+            count: 1
+        });
+
+        assert_eq!(buckets[3].count, 2);
+        assert_eq!(buckets[9], HistogramBucket {
+            start: 18.0,
+            end: 20.0,
+            count: 4
+        });
+
+        assert_eq!(buckets.iter().map(|x| x.count).sum::<usize>(), 7);
+    }
+}
@@ -0,0 +1,122 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::*;
+
+/// Options for `Session::get_column_values()`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnValuesOptions {
+    /// Only return values which begin with this string, ignoring case.
+    #[serde(default)]
+    pub search: Option<String>,
+
+    /// The number of (matching) values to skip.
+    #[serde(default)]
+    pub offset: usize,
+
+    /// The maximum number of values to return, or all values if `None`.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// A distinct value of a column, and the number of rows with this value.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ColumnValue {
+    pub value: Scalar,
+    pub count: usize,
+}
+
+/// A page of the distinct values of a column, in ascending order, as returned
+/// by `Session::get_column_values()`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ColumnValues {
+    pub values: Vec<ColumnValue>,
+
+    /// The number of distinct values which match the `search`, including those
+    /// not in this page.
+    pub total: usize,
+}
+
+impl ColumnValuesOptions {
+    /// The non-empty `search` term, if any.
+    pub fn search(&self) -> Option<&str> {
+        self.search.as_deref().filter(|x| !x.is_empty())
+    }
+
+    pub fn matches(&self, value: &Scalar) -> bool {
+        match self.search() {
+            Some(search) => value
+                .to_string()
+                .to_lowercase()
+                .starts_with(&search.to_lowercase()),
+            None => true,
+        }
+    }
+}
+
+impl ColumnValues {
+    /// Search and paginate a complete list of `values`, for columns which
+    /// cannot be searched by the engine.
+    pub fn new(values: Vec<ColumnValue>, options: &ColumnValuesOptions) -> Self {
+        let values = values
+            .into_iter()
+            .filter(|x| options.matches(&x.value))
+            .collect::<Vec<_>>();
+
+        let total = values.len();
+        let values = values
+            .into_iter()
+            .skip(options.offset)
+            .take(options.limit.unwrap_or(usize::MAX))
+            .collect();
+
+        Self { values, total }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_column_values_search_and_page() {
+        let values = [12.0, 120.0, 13.0, 2.0, 125.0]
+            .into_iter()
+            .map(|x| ColumnValue {
+                value: Scalar::Float(x),
+                count: 1,
+            })
+            .collect::<Vec<_>>();
+
+        let options = ColumnValuesOptions {
+            search: Some("12".to_owned()),
+            offset: 1,
+            limit: Some(1),
+        };
+
+        let page = ColumnValues::new(values.clone(), &options);
+        assert_eq!(page.total, 3);
+        assert_eq!(page.values, vec![ColumnValue {
+            value: Scalar::Float(120.0),
+            count: 1
+        }]);
+
+        let all = ColumnValues::new(values.clone(), &ColumnValuesOptions::default());
+        assert_eq!(all.total, 5);
+        assert_eq!(all.values, values);
+    }
+}
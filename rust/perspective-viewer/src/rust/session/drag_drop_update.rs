@@ -17,9 +17,15 @@ use crate::js::plugin::ViewConfigRequirements;
 impl ViewConfig {
     /// Create an update for this `ViewConfig` which applies a drag/drop action.
     /// This method is designed to be called from `crate::session`.
+    ///
+    /// # Arguments
+    /// - `from_index` The index of the dragged entry in `columns`, as `column`
+    ///   may occur in `columns` more than once.  Otherwise (or if it is stale),
+    ///   the first entry of `column` is moved.
     pub(super) fn create_drag_drop_update(
         &self,
         column: String,
+        from_index: Option<usize>,
         index: usize,
         drop: DragTarget,
         drag: DragEffect,
@@ -28,10 +34,20 @@ impl ViewConfig {
         let mut config = self.clone();
         let mut update = ViewConfigUpdate::default();
         let is_to_swap = requirements.is_swap(index);
-        let from_index = config
-            .columns
-            .iter()
-            .position(|x| x.as_ref() == Some(&column));
+
+        // The `Aggregate` of each `columns` entry, kept in step with `columns`
+        // so that every occurrence of a column keeps its own `Aggregate`.
+        let mut aggregates = config.column_slot_aggregates();
+        let mut dragged_aggregate = None;
+        let mut from_index = from_index
+            .filter(|_| drag == DragEffect::Move(DragTarget::Active))
+            .filter(|x| config.columns.get(*x) == Some(&Some(column.clone())))
+            .or_else(|| {
+                config
+                    .columns
+                    .iter()
+                    .position(|x| x.as_ref() == Some(&column))
+            });
 
         let is_from_required = from_index
             .and_then(|x| requirements.min.map(|z| x < z))
@@ -60,16 +76,15 @@ impl ViewConfig {
                     && config.columns.len() > 1
                     && !is_from_required
                     && !is_swap_to_after_last
+                    && let Some(from) = from_index.take()
                 {
                     // Is not a swap
                     if !is_to_swap && !is_to_group_or_split {
-                        config.columns.iter_mut().for_each(|x| {
-                            if x.as_ref() == Some(&column) {
-                                *x = None;
-                            }
-                        });
+                        config.columns[from] = None;
+                        dragged_aggregate = aggregates[from].take();
                     } else {
-                        config.columns.retain(|x| x.as_ref() != Some(&column));
+                        config.columns.remove(from);
+                        dragged_aggregate = aggregates.remove(from);
                     }
 
                     update.columns = Some(config.columns.clone());
@@ -88,7 +103,7 @@ impl ViewConfig {
                 update.sort = Some(config.sort.clone());
             },
             DragEffect::Move(DragTarget::Filter) => {
-                config.filter.retain(|x| x.0 != column);
+                config.filter.retain_filters(&mut |x| x.0 != column);
                 update.filter = Some(config.filter.clone());
             },
         }
@@ -112,13 +127,20 @@ impl ViewConfig {
                             }
                         }));
 
-                        if let Some(prev) = config.columns.iter().position(|x| *x == column) {
+                        aggregates.resize(config.columns.len(), None);
+                        if let Some(prev) = from_index {
                             config.columns.swap(index, prev);
+                            aggregates.swap(index, prev);
                         } else {
                             config.columns[index] = column;
+                            aggregates[index] = dragged_aggregate;
                         }
                     } else {
-                        config.columns.retain(|x| x.as_ref() != Some(&column));
+                        if let Some(from) = from_index {
+                            config.columns.remove(from);
+                            dragged_aggregate = aggregates.remove(from);
+                        }
+
                         config.columns.extend(std::iter::repeat(None).take(
                             if index >= config.columns.len() {
                                 index - config.columns.len()
@@ -127,14 +149,17 @@ impl ViewConfig {
                             },
                         ));
 
+                        aggregates.resize(config.columns.len(), None);
                         if is_to_empty {
-                            config.columns[index] = Some(column)
+                            config.columns[index] = Some(column);
+                            aggregates[index] = dragged_aggregate;
                         } else {
                             config.columns.insert(index, Some(column));
+                            aggregates.insert(index, dragged_aggregate);
                         }
                     }
 
-                    update.columns = Some(config.columns);
+                    update.columns = Some(config.columns.clone());
                 }
             },
             DragTarget::GroupBy => {
@@ -158,12 +183,25 @@ impl ViewConfig {
                 let index = std::cmp::min(index, config.filter.len());
                 config.filter.insert(
                     index,
-                    Filter(column, FilterOp::EQ, FilterTerm::Scalar(Scalar::Null)),
+                    Filter(column, FilterOp::EQ, FilterTerm::Scalar(Scalar::Null)).into(),
                 );
                 update.filter = Some(config.filter);
             },
         }
 
+        if let Some(columns) = &update.columns {
+            let mut config = ViewConfig {
+                columns: columns.clone(),
+                aggregates: self.aggregates.clone(),
+                ..ViewConfig::default()
+            };
+
+            config.set_column_slot_aggregates(aggregates);
+            if config.aggregates != self.aggregates {
+                update.aggregates = Some(config.aggregates);
+            }
+        }
+
         update
     }
 }
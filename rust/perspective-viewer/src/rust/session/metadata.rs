@@ -14,9 +14,9 @@ use std::collections::{HashMap, HashSet};
 use std::iter::IntoIterator;
 use std::ops::{Deref, DerefMut};
 
+use super::backend::*;
 use crate::components::viewer::ColumnLocator;
 use crate::config::*;
-use crate::js::perspective::*;
 use crate::utils::*;
 use crate::*;
 
@@ -59,20 +59,10 @@ pub struct SessionMetadataState {
 }
 
 impl SessionMetadata {
-    /// Creates a new `SessionMetadata` from a `JsPerspectiveTable`.
-    pub(super) async fn from_table(table: &JsPerspectiveTable) -> ApiResult<Self> {
-        let column_names = {
-            let columns = table.columns().await?;
-            if columns.length() > 0 {
-                (0..columns.length())
-                    .map(|i| columns.get(i).as_string().unwrap())
-                    .collect::<Vec<String>>()
-            } else {
-                vec![]
-            }
-        };
-
-        let table_schema = table.schema().await?.into_serde_ext()?;
+    /// Creates a new `SessionMetadata` from a `Table`.
+    pub(super) async fn from_table<T: TableBackend>(table: &T) -> ApiResult<Self> {
+        let column_names = table.columns().await?;
+        let table_schema = table.schema().await?;
         let edit_port = table.make_port().await?;
         Ok(Self(Some(SessionMetadataState {
             column_names,
@@ -82,29 +72,20 @@ impl SessionMetadata {
         })))
     }
 
-    pub(super) fn update_view_schema(
-        &mut self,
-        view_schema: &JsPerspectiveViewSchema,
-    ) -> ApiResult<()> {
-        let view_schema = view_schema.into_serde_ext()?;
+    pub(super) fn update_view_schema(&mut self, view_schema: HashMap<String, Type>) {
         self.as_mut().unwrap().view_schema = Some(view_schema);
-        Ok(())
     }
 
     pub(super) fn update_expressions(
         &mut self,
-        valid_recs: &JsPerspectiveValidatedExpressions,
+        valid_recs: &ValidatedExpressions,
     ) -> ApiResult<HashSet<String>> {
-        if js_sys::Object::keys(&valid_recs.errors()).length() > 0 {
+        if !valid_recs.errors.is_empty() {
             return Err("Expressions invalid".into());
         }
 
-        let expression_alias: HashMap<String, String> =
-            valid_recs.expression_alias().into_serde_ext()?;
-
-        let expression_schema: HashMap<String, Type> =
-            valid_recs.expression_schema().into_serde_ext()?;
-
+        let expression_alias = valid_recs.expression_alias.clone();
+        let expression_schema = valid_recs.expression_schema.clone();
         let expression_names = expression_schema.keys().cloned().collect::<HashSet<_>>();
 
         let mut edited = self
@@ -179,6 +160,10 @@ impl SessionMetadata {
         self.as_ref().map(|meta| &meta.column_names)
     }
 
+    pub fn get_table_schema(&self) -> Option<&'_ HashMap<String, Type>> {
+        self.as_ref().map(|meta| &meta.table_schema)
+    }
+
     pub fn is_column_expression(&self, name: &str) -> bool {
         let is_expr = maybe!(Some(
             self.as_ref()?.expr_meta.as_ref()?.schema.contains_key(name)
@@ -251,6 +236,36 @@ impl SessionMetadata {
         maybe!(self.as_ref()?.view_schema.as_ref()?.get(name)).cloned()
     }
 
+    /// Check that `aggregate` applies to column `name` and that the parameter
+    /// of a `MultiAggregate` is valid.  Unknown columns are not an error here.
+    pub fn validate_aggregate(&self, name: &str, aggregate: &Aggregate) -> Result<(), String> {
+        let Some(coltype) = self.get_column_table_type(name) else {
+            return Ok(());
+        };
+
+        let is_applicable = match aggregate {
+            Aggregate::SingleAggregate(_) => coltype.aggregates_iter().any(|x| &x == aggregate),
+            Aggregate::MultiAggregate(x, _) => x.accepts(coltype),
+        };
+
+        match aggregate {
+            _ if !is_applicable => Err(format!(
+                "`{}` does not apply to a `{}` column",
+                aggregate, coltype
+            )),
+            Aggregate::MultiAggregate(x, param) => match self.get_column_table_type(param) {
+                Some(param_type) if x.accepts_param(param_type) => Ok(()),
+                Some(param_type) => {
+                    Err(format!("`{}` does not accept a `{}` column", x, param_type))
+                },
+                None => Err(format!("Unknown \"{}\" in `{}`", param, aggregate)),
+            },
+            Aggregate::SingleAggregate(_) => Ok(()),
+        }
+    }
+
+    /// The aggregates which apply to column `name`, including each
+    /// `MultiAggregate` for every valid parameter column.
     pub fn get_column_aggregates<'a>(
         &'a self,
         name: &str,
@@ -258,26 +273,28 @@ impl SessionMetadata {
         maybe!({
             let coltype = self.get_column_table_type(name)?;
             let aggregates = coltype.aggregates_iter();
-            Some(match coltype {
-                Type::Float | Type::Integer => {
-                    let num_cols = self
-                        .get_expression_columns()
-                        .cloned()
-                        .chain(self.get_table_columns()?.clone().into_iter())
-                        .map(move |name| {
-                            self.get_column_table_type(&name)
-                                .map(|coltype| (name, coltype))
-                        })
-                        .collect::<Option<Vec<_>>>()?
-                        .into_iter()
-                        .filter(|(_, coltype)| *coltype == Type::Integer || *coltype == Type::Float)
-                        .map(|(name, _)| {
-                            Aggregate::MultiAggregate(MultiAggregate::WeightedMean, name)
-                        });
-                    Box::new(aggregates.chain(num_cols))
-                },
-                _ => aggregates,
-            })
+            let param_cols = self
+                .get_expression_columns()
+                .cloned()
+                .chain(self.get_table_columns()?.clone().into_iter())
+                .map(move |name| {
+                    self.get_column_table_type(&name)
+                        .map(|coltype| (name, coltype))
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            let multi_aggregates = MultiAggregate::ALL
+                .iter()
+                .filter(|x| x.accepts(coltype))
+                .flat_map(|x| {
+                    param_cols
+                        .iter()
+                        .filter(|(_, coltype)| x.accepts_param(*coltype))
+                        .map(|(name, _)| Aggregate::MultiAggregate(*x, name.clone()))
+                })
+                .collect::<Vec<_>>();
+
+            Some(Box::new(aggregates.chain(multi_aggregates)) as Box<dyn Iterator<Item = Aggregate>>)
         })
     }
 }
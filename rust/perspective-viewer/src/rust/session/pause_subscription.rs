@@ -0,0 +1,94 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+use super::view::*;
+use crate::config::*;
+use crate::js::perspective::*;
+use crate::utils::*;
+use crate::*;
+
+#[derive(Clone)]
+struct PauseSubscriptionData {
+    view: View,
+    num_rows: u32,
+    num_updates: Rc<Cell<u32>>,
+    on_pending: Callback<(u32, i64)>,
+}
+
+/// A lightweight subscription to `on_update()` events from a `Table` while its
+/// `Session` is paused, which counts the pending updates without calculating
+/// the paused `View`.  The `View` it subscribes to has no columns.
+pub struct PauseSubscription {
+    data: PauseSubscriptionData,
+    closure: Closure<dyn Fn(JsValue) -> js_sys::Promise>,
+}
+
+impl PauseSubscriptionData {
+    /// Count an update, and the net change in the `Table`'s row count since
+    /// pausing.  This is not the number of rows updated, as updates to
+    /// existing rows of an indexed `Table` do not change its size, and rows
+    /// removed cancel out rows added.
+    async fn on_table_update(self) -> ApiResult<JsValue> {
+        self.num_updates.set(self.num_updates.get() + 1);
+        let num_rows = self.view.dimensions().await?.num_table_rows() as u32;
+        let row_count_change = i64::from(num_rows) - i64::from(self.num_rows);
+        self.on_pending
+            .emit((self.num_updates.get(), row_count_change));
+        Ok(JsValue::UNDEFINED)
+    }
+}
+
+impl PauseSubscription {
+    /// Create a new `PauseSubscription` on `table`.
+    ///
+    /// # Arguments
+    /// * `table` - a Perspective `Table()`
+    /// * `on_pending` - a callback for the number of updates, and the net
+    ///   change in the `Table`'s row count, since this subscription was
+    ///   created.
+    pub async fn new(
+        table: &JsPerspectiveTable,
+        on_pending: Callback<(u32, i64)>,
+    ) -> ApiResult<Self> {
+        let view = View::new(table.view(&ViewConfig::default().as_jsvalue()?).await?);
+        let num_rows = view.dimensions().await?.num_table_rows() as u32;
+        let data = PauseSubscriptionData {
+            view,
+            num_rows,
+            num_updates: Rc::default(),
+            on_pending,
+        };
+
+        let fun = {
+            clone!(data);
+            move |_| js_sys::Promise::from(ApiFuture::new(data.clone().on_table_update()))
+        };
+
+        let closure = fun.into_closure();
+        data.view.on_update(closure.as_ref().unchecked_ref());
+        Ok(Self { data, closure })
+    }
+}
+
+impl Drop for PauseSubscription {
+    fn drop(&mut self) {
+        let update = self.closure.as_ref().unchecked_ref();
+        self.data.view.remove_update(update);
+    }
+}
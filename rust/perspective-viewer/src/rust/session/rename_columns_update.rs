@@ -0,0 +1,175 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+
+use crate::config::*;
+
+impl ViewConfig {
+    /// Create an update for this `ViewConfig` which renames `Table` columns,
+    /// e.g. when a `Table` is replaced by one with a revised schema.  Every
+    /// field which references a renamed column is updated, including the
+    /// `"quoted"` column references in `expressions`.
+    ///
+    /// # Arguments
+    /// - `renames` New column names, keyed by old column name.
+    pub(super) fn create_rename_columns_update(
+        &self,
+        renames: &HashMap<String, String>,
+    ) -> ViewConfigUpdate {
+        let rename = |x: String| renames.get(&x).cloned().unwrap_or(x);
+        let Self {
+            columns,
+            mut expressions,
+            group_by,
+            split_by,
+            sort,
+            mut filter,
+            aggregates,
+            mut top_n,
+        } = self.clone();
+
+        for expr in expressions.values_mut() {
+            *expr = rename_expression_columns(expr, renames);
+        }
+
+        filter.for_each_filter_mut(&mut |x| x.0 = rename(std::mem::take(&mut x.0)));
+        if let Some(top_n) = top_n.as_mut() {
+            top_n.column = rename(std::mem::take(&mut top_n.column));
+        }
+
+        ViewConfigUpdate {
+            columns: Some(columns.into_iter().map(|x| x.map(rename)).collect()),
+            aggregates: Some(aggregates.into_iter().map(|x| (rename(x.0), x.1)).collect()),
+            expressions: Some(expressions),
+            group_by: Some(group_by.into_iter().map(rename).collect()),
+            split_by: Some(split_by.into_iter().map(rename).collect()),
+            sort: Some(sort.into_iter().map(|x| Sort(rename(x.0), x.1)).collect()),
+            filter: Some(filter),
+            top_n: top_n.into(),
+        }
+    }
+}
+
+/// Rewrite the `"quoted"` column references of ExprTK expression `expr` in a
+/// single pass, so that e.g. swapping two names does not rename either twice.
+/// `'quoted'` string literals and `//` comments are copied unchanged.
+fn rename_expression_columns(expr: &str, renames: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(expr.len());
+    let mut chars = expr.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                let mut name = String::new();
+                let mut end = None;
+                while let Some((idx, x)) = chars.next() {
+                    match x {
+                        '\\' => name.extend(chars.next().map(|(_, x)| x)),
+                        x if x == c => {
+                            end = Some(idx + 1);
+                            break;
+                        },
+                        x => name.push(x),
+                    }
+                }
+
+                let raw = &expr[start..end.unwrap_or(expr.len())];
+                match renames.get(&name) {
+                    Some(new) if c == '"' && end.is_some() => {
+                        let new = new.replace('\\', "\\\\").replace('"', "\\\"");
+                        output.push('"');
+                        output.push_str(&new);
+                        output.push('"');
+                    },
+                    _ => output.push_str(raw),
+                }
+            },
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                output.push(c);
+                while let Some((_, x)) = chars.next_if(|(_, x)| *x != '\n') {
+                    output.push(x);
+                }
+            },
+            c => output.push(c),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_rename_columns_update() {
+        let config: ViewConfig = serde_json::from_value(serde_json::json!({
+            "columns": ["px", "qty", null],
+            "group_by": ["sym"],
+            "sort": [["px", "desc"]],
+            "filter": [["sym", "==", "AAPL"], ["qty", ">", 10]],
+            "expressions": {"notional": "\"px\" * \"qty\""},
+            "aggregates": {"px": "avg"},
+            "top_n": {"column": "qty", "dir": "desc", "n": 5}
+        }))
+        .unwrap();
+
+        let renames = HashMap::from([
+            ("px".to_owned(), "price".to_owned()),
+            ("sym".to_owned(), "ticker".to_owned()),
+        ]);
+
+        let mut renamed = config.clone();
+        renamed.apply_update(config.create_rename_columns_update(&renames));
+        let expected: ViewConfig = serde_json::from_value(serde_json::json!({
+            "columns": ["price", "qty", null],
+            "group_by": ["ticker"],
+            "sort": [["price", "desc"]],
+            "filter": [["ticker", "==", "AAPL"], ["qty", ">", 10]],
+            "expressions": {"notional": "\"price\" * \"qty\""},
+            "aggregates": {"price": "avg"},
+            "top_n": {"column": "qty", "dir": "desc", "n": 5}
+        }))
+        .unwrap();
+
+        assert_eq!(renamed, expected);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_rename_swapped_columns() {
+        let renames = HashMap::from([
+            ("a".to_owned(), "b".to_owned()),
+            ("b".to_owned(), "a".to_owned()),
+        ]);
+
+        assert_eq!(
+            rename_expression_columns("\"a\" - \"b\"", &renames),
+            "\"b\" - \"a\""
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_rename_ignores_string_literals() {
+        let renames = HashMap::from([
+            ("a".to_owned(), "x".to_owned()),
+            ("say \"hi\"".to_owned(), "greeting".to_owned()),
+        ]);
+
+        let expr = "// \"a\"\nif (\"a\" == 'a') { '\\'\"a\"' } else { \"say \\\"hi\\\"\" }";
+        assert_eq!(
+            rename_expression_columns(expr, &renames),
+            "// \"a\"\nif (\"x\" == 'a') { '\\'\"a\"' } else { \"greeting\" }"
+        );
+    }
+}
@@ -34,8 +34,9 @@ impl ViewConfig {
             group_by,
             split_by,
             sort,
-            filter,
+            mut filter,
             aggregates,
+            mut top_n,
         } = self.clone();
 
         let expressions = expressions
@@ -102,16 +103,15 @@ impl ViewConfig {
             .collect::<Vec<_>>();
 
         // TODO expression editing can change type, which may invalidate filters
-        let filter = filter
-            .into_iter()
-            .map(|x| {
-                if x.0 == old_expr.name {
-                    Filter(new_expr.name.as_ref().to_owned(), x.1, x.2)
-                } else {
-                    x
-                }
-            })
-            .collect::<Vec<_>>();
+        filter.for_each_filter_mut(&mut |x| {
+            if x.0 == old_expr.name {
+                x.0 = new_expr.name.as_ref().to_owned();
+            }
+        });
+
+        if let Some(top_n) = top_n.as_mut().filter(|x| x.column == old_expr.name) {
+            top_n.column = new_expr.name.as_ref().to_owned();
+        }
 
         ViewConfigUpdate {
             columns: Some(columns),
@@ -121,6 +121,7 @@ impl ViewConfig {
             split_by: Some(split_by),
             sort: Some(sort),
             filter: Some(filter),
+            top_n: top_n.into(),
         }
     }
 }
@@ -0,0 +1,146 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::validation_report::ViewConfigReport;
+use crate::config::Type;
+
+/// Options for `load()`, its optional second argument.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoadOptions {
+    /// Map the current `ViewConfig` onto the new `Table`'s schema (applying
+    /// `renames`), and report how the schema and config changed.
+    #[serde(default)]
+    pub preserve_config: bool,
+
+    /// New column names, keyed by their name in the previous `Table`.
+    #[serde(default)]
+    pub renames: HashMap<String, String>,
+}
+
+/// The difference between the schemas of a `Table` and its replacement.
+/// Renames only apply when the old column is in the previous `Table` and the
+/// new column is in the replacement; other renames are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct TableSchemaDiff {
+    pub added_columns: Vec<String>,
+    pub removed_columns: Vec<String>,
+    pub renamed_columns: BTreeMap<String, String>,
+
+    /// Columns (by their new name) which are in both schemas with a different
+    /// type.
+    pub retyped_columns: Vec<String>,
+}
+
+/// The result of `load()` with `preserve_config`: the `TableSchemaDiff`, and
+/// the entries of the `ViewConfig` which were dropped as they are invalid for
+/// the new `Table`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TableSwapReport {
+    #[serde(flatten)]
+    pub schema: TableSchemaDiff,
+    pub dropped: ViewConfigReport,
+}
+
+impl TableSchemaDiff {
+    pub fn new(
+        old: &HashMap<String, Type>,
+        new: &HashMap<String, Type>,
+        renames: &HashMap<String, String>,
+    ) -> Self {
+        let renamed_columns: BTreeMap<String, String> = renames
+            .iter()
+            .filter(|(from, to)| from != to && old.contains_key(*from) && new.contains_key(*to))
+            .map(|(from, to)| (from.clone(), to.clone()))
+            .collect();
+
+        let rename = |x: &String| renamed_columns.get(x).unwrap_or(x).clone();
+        let mut removed_columns: Vec<String> = old
+            .keys()
+            .filter(|x| !new.contains_key(&rename(x)))
+            .cloned()
+            .collect();
+
+        let mut added_columns: Vec<String> = new
+            .keys()
+            .filter(|x| !old.keys().any(|y| &rename(y) == *x))
+            .cloned()
+            .collect();
+
+        let mut retyped_columns: Vec<String> = old
+            .iter()
+            .filter_map(|(name, ty)| {
+                let name = rename(name);
+                new.get(&name).filter(|x| *x != ty).map(|_| name)
+            })
+            .collect();
+
+        removed_columns.sort();
+        added_columns.sort();
+        retyped_columns.sort();
+        Self {
+            added_columns,
+            removed_columns,
+            renamed_columns,
+            retyped_columns,
+        }
+    }
+
+    pub fn renames(&self) -> HashMap<String, String> {
+        self.renamed_columns.clone().into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::*;
+
+    use super::*;
+
+    fn schema(columns: &[(&str, Type)]) -> HashMap<String, Type> {
+        columns.iter().map(|(x, y)| (x.to_string(), *y)).collect()
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_table_schema_diff() {
+        let old = schema(&[
+            ("sym", Type::String),
+            ("px", Type::Float),
+            ("qty", Type::Integer),
+            ("venue", Type::String),
+        ]);
+
+        let new = schema(&[
+            ("sym", Type::String),
+            ("price", Type::Float),
+            ("qty", Type::Float),
+            ("side", Type::String),
+        ]);
+
+        let renames = HashMap::from([
+            ("px".to_owned(), "price".to_owned()),
+            ("missing".to_owned(), "side".to_owned()),
+        ]);
+
+        let diff = TableSchemaDiff::new(&old, &new, &renames);
+        assert_eq!(diff, TableSchemaDiff {
+            added_columns: vec!["side".to_owned()],
+            removed_columns: vec!["venue".to_owned()],
+            renamed_columns: BTreeMap::from([("px".to_owned(), "price".to_owned())]),
+            retyped_columns: vec!["qty".to_owned()],
+        });
+    }
+}
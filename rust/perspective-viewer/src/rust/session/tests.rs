@@ -0,0 +1,665 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::rc::Rc;
+
+use futures::executor::block_on;
+use futures::join;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+use crate::config::*;
+use crate::custom_events::CustomEvents;
+use crate::dragdrop::*;
+use crate::js::*;
+use crate::presentation::Presentation;
+use crate::renderer::*;
+use crate::session::*;
+use crate::utils::*;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+pub async fn test_stale_view_is_not_created() {
+    let session = Session::default();
+    session.set_table(get_mock_table().await).await.unwrap();
+    session.update_view_config(ViewConfigUpdate {
+        group_by: Some(vec!["A".to_owned()]),
+        ..ViewConfigUpdate::default()
+    });
+
+    // Change the config while the `group_by` `View` is being created.
+    let valid = session.validate().await.unwrap();
+    let (created, _) = join!(valid.create_view(), async {
+        session.update_view_config(ViewConfigUpdate {
+            group_by: Some(vec![]),
+            ..ViewConfigUpdate::default()
+        });
+    });
+
+    assert!(created.unwrap().get_view().is_none());
+    drop(valid);
+
+    // Only the final config is rendered.
+    let valid = session.validate().await.unwrap();
+    let view = valid.create_view().await.unwrap().get_view().unwrap();
+    let num_rows = view.dimensions().await.unwrap().num_view_rows();
+    assert_eq!(num_rows as u32, 3);
+    assert!(session.get_view_config().group_by.is_empty());
+}
+
+#[wasm_bindgen_test]
+pub async fn test_column_stats_computed_before_clear_are_not_cached() {
+    let session = Session::default();
+    session.set_table(get_mock_table().await).await.unwrap();
+
+    // Invalidate the cache while the `ColumnStats` are being computed.
+    let (stats, _) = join!(session.get_column_stats("A".to_owned()), async {
+        session.clear_column_stats();
+    });
+
+    assert_eq!(stats.unwrap().count, 3);
+    assert!(session.borrow().column_stats.is_empty());
+
+    let stats = session.get_column_stats("A".to_owned()).await.unwrap();
+    assert_eq!(stats.count, 3);
+    assert!(session.borrow().column_stats.contains_key("A"));
+}
+
+/// The `detail` of each `perspective-config-error` event dispatched while
+/// validating `update` against the mock `Table`.
+async fn config_error_events(update: ViewConfigUpdate) -> Vec<serde_json::Value> {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let elem: web_sys::HtmlElement = document.create_element("div").unwrap().unchecked_into();
+    let session = Session::default();
+    let renderer = Renderer::new(&elem);
+    let presentation = Presentation::new(&elem);
+    let _events = CustomEvents::new(&elem, &session, &renderer, &presentation);
+    let details = Rc::new(RefCell::new(vec![]));
+    let callback = Closure::<dyn Fn(web_sys::CustomEvent)>::new({
+        let details = details.clone();
+        move |event: web_sys::CustomEvent| details.borrow_mut().push(event.detail())
+    });
+
+    elem.add_event_listener_with_callback(
+        "perspective-config-error",
+        callback.as_ref().unchecked_ref(),
+    )
+    .unwrap();
+
+    session.set_table(get_mock_table().await).await.unwrap();
+    session.update_view_config(update);
+    session.validate().await.unwrap();
+    let details = details.borrow();
+    details
+        .iter()
+        .map(|x| x.clone().into_serde_ext().unwrap())
+        .collect()
+}
+
+#[wasm_bindgen_test]
+pub async fn test_config_error_is_dispatched() {
+    let details = config_error_events(ViewConfigUpdate {
+        columns: Some(vec![Some("A".to_owned()), Some("Z".to_owned())]),
+        group_by: Some(vec!["Q".to_owned()]),
+        ..ViewConfigUpdate::default()
+    })
+    .await;
+
+    assert_eq!(details.len(), 1);
+    assert_eq!(
+        details[0]["unknown_columns"],
+        serde_json::json!({"columns": ["Z"], "group_by": ["Q"]})
+    );
+}
+
+#[wasm_bindgen_test]
+pub async fn test_config_error_is_not_dispatched_for_valid_config() {
+    let details = config_error_events(ViewConfigUpdate {
+        columns: Some(vec![Some("A".to_owned())]),
+        ..ViewConfigUpdate::default()
+    })
+    .await;
+
+    assert!(details.is_empty());
+}
+
+fn mock_table() -> MockTable {
+    MockTable::new([
+        ("A", Type::Integer),
+        ("B", Type::String),
+        ("C", Type::Float),
+    ])
+    .with_expression("\"A\" + 1", Type::Float)
+}
+
+fn expressions(exprs: &[(&str, &str)]) -> Expressions {
+    exprs
+        .iter()
+        .map(|(name, expr)| Expression::new(Some((*name).into()), (*expr).into()))
+        .collect()
+}
+
+#[test]
+pub fn test_validate_removes_invalid_entries() {
+    let table = mock_table();
+    let mut metadata = block_on(SessionMetadata::from_table(&table)).unwrap();
+    let mut config = ViewConfig {
+        columns: vec![
+            Some("A".to_owned()),
+            Some("Z".to_owned()),
+            Some("x".to_owned()),
+        ],
+        group_by: vec!["Q".to_owned()],
+        expressions: expressions(&[("x", "\"A\" + 1"), ("bad", "foo")]),
+        ..ViewConfig::default()
+    };
+
+    let (valid_recs, invalid_expressions) =
+        block_on(validate_expressions(&table, config.expressions.clone())).unwrap();
+
+    let expression_names = metadata.update_expressions(&valid_recs).unwrap();
+    let report = config.validate(&metadata, &expression_names, invalid_expressions);
+    assert_eq!(
+        report.unknown_columns,
+        BTreeMap::from([
+            ("columns", vec!["Z".to_owned()]),
+            ("group_by", vec!["Q".to_owned()])
+        ])
+    );
+
+    assert_eq!(report.invalid_expressions.len(), 1);
+    assert_eq!(report.invalid_expressions[0].name, "bad");
+    assert_eq!(config.columns, vec![
+        Some("A".to_owned()),
+        None,
+        Some("x".to_owned())
+    ]);
+    assert!(config.group_by.is_empty());
+    assert_eq!(config.expressions, expressions(&[("x", "\"A\" + 1")]));
+}
+
+#[test]
+pub fn test_filter_terms_keep_their_column_type() {
+    let table = mock_table();
+    let metadata = block_on(SessionMetadata::from_table(&table)).unwrap();
+    let mut config: ViewConfig = serde_json::from_value(serde_json::json!({
+        "filter": [["A", "==", 9007199254740993_i64], ["C", ">", 2]]
+    }))
+    .unwrap();
+
+    config.validate(&metadata, &HashSet::new(), vec![]);
+    let config = config.to_engine_config();
+    let terms = config
+        .filter
+        .iter_filters()
+        .map(|x| x.2.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(terms, vec![
+        FilterTerm::Scalar(Scalar::Int(9_007_199_254_740_993)),
+        FilterTerm::Scalar(Scalar::Float(2.0)),
+    ]);
+
+    assert_eq!(
+        serde_json::to_value(&config.filter).unwrap(),
+        serde_json::json!([["A", "==", "9007199254740993"], ["C", ">", 2.0]])
+    );
+}
+
+#[test]
+pub fn test_expression_metadata() {
+    let table = mock_table();
+    let mut metadata = block_on(SessionMetadata::from_table(&table)).unwrap();
+    let (valid_recs, _) = block_on(validate_expressions(
+        &table,
+        expressions(&[("x", "\"A\" + 1")]),
+    ))
+    .unwrap();
+
+    metadata.update_expressions(&valid_recs).unwrap();
+    assert!(metadata.is_column_expression("x"));
+    assert!(!metadata.is_column_expression("A"));
+    assert_eq!(metadata.get_column_table_type("x"), Some(Type::Float));
+    assert_eq!(
+        metadata.get_expression_by_alias("x").as_deref(),
+        Some("\"A\" + 1")
+    );
+}
+
+#[test]
+pub fn test_column_defaults_prefer_numeric_columns() {
+    let metadata = block_on(SessionMetadata::from_table(&mock_table())).unwrap();
+    let requirements = ViewConfigRequirements {
+        min: Some(2),
+        ..ViewConfigRequirements::default()
+    };
+
+    let mut update = ViewConfigUpdate::default();
+    update.set_update_column_defaults(&metadata, &[Some("B".to_owned())], &requirements);
+    assert_eq!(
+        update.columns,
+        Some(vec![Some("A".to_owned()), Some("C".to_owned())])
+    );
+}
+
+#[test]
+pub fn test_drag_active_column_to_group_by() {
+    let config = ViewConfig {
+        columns: vec![Some("A".to_owned()), Some("B".to_owned())],
+        ..ViewConfig::default()
+    };
+
+    let update = config.create_drag_drop_update(
+        "A".to_owned(),
+        Some(0),
+        0,
+        DragTarget::GroupBy,
+        DragEffect::Move(DragTarget::Active),
+        &ViewConfigRequirements::default(),
+    );
+
+    assert_eq!(update.columns, Some(vec![Some("B".to_owned())]));
+    assert_eq!(update.group_by, Some(vec!["A".to_owned()]));
+}
+
+#[test]
+pub fn test_drag_filter_column_removes_nested_filters() {
+    let config: ViewConfig = serde_json::from_value(serde_json::json!({
+        "columns": ["A", "B"],
+        "filter": [["A", ">", 1], {"op": "or", "filters": [["A", "<", 0], ["B", "==", "x"]]}, {
+            "op": "and",
+            "filters": [["A", "==", 5]]
+        }]
+    }))
+    .unwrap();
+
+    let update = config.create_drag_drop_update(
+        "A".to_owned(),
+        None,
+        0,
+        DragTarget::GroupBy,
+        DragEffect::Move(DragTarget::Filter),
+        &ViewConfigRequirements::default(),
+    );
+
+    let filter: Vec<FilterItem> = serde_json::from_value(serde_json::json!([{
+        "op": "or",
+        "filters": [["B", "==", "x"]]
+    }]))
+    .unwrap();
+
+    assert_eq!(update.filter, Some(filter));
+    assert_eq!(update.group_by, Some(vec!["A".to_owned()]));
+}
+
+fn repeated_column_config() -> ViewConfig {
+    let max = Aggregate::SingleAggregate(SingleAggregate::Max);
+    let mut aggregates = ColumnAggregates::default();
+    aggregates.set(1, Some(max));
+    ViewConfig {
+        columns: vec![
+            Some("A".to_owned()),
+            Some("B".to_owned()),
+            Some("A".to_owned()),
+        ],
+        aggregates: HashMap::from([("A".to_owned(), aggregates)]),
+        ..ViewConfig::default()
+    }
+}
+
+#[test]
+pub fn test_drag_repeated_column_moves_one_occurrence() {
+    let config = repeated_column_config();
+    let update = config.create_drag_drop_update(
+        "A".to_owned(),
+        Some(2),
+        0,
+        DragTarget::Active,
+        DragEffect::Move(DragTarget::Active),
+        &ViewConfigRequirements::default(),
+    );
+
+    let mut config = config.clone();
+    config.apply_update(update);
+    assert_eq!(config.columns, vec![
+        Some("A".to_owned()),
+        Some("A".to_owned()),
+        Some("B".to_owned())
+    ]);
+
+    let max = Aggregate::SingleAggregate(SingleAggregate::Max);
+    assert_eq!(config.get_aggregate("A", 0), Some(&max));
+    assert_eq!(config.get_aggregate("A", 1), None);
+}
+
+#[test]
+pub fn test_drag_repeated_column_to_group_by_keeps_other_occurrence() {
+    let config = repeated_column_config();
+    let update = config.create_drag_drop_update(
+        "A".to_owned(),
+        Some(0),
+        0,
+        DragTarget::GroupBy,
+        DragEffect::Move(DragTarget::Active),
+        &ViewConfigRequirements::default(),
+    );
+
+    let mut config = config.clone();
+    config.apply_update(update);
+    assert_eq!(config.columns, vec![
+        Some("B".to_owned()),
+        Some("A".to_owned())
+    ]);
+
+    let max = Aggregate::SingleAggregate(SingleAggregate::Max);
+    assert_eq!(config.group_by, vec!["A".to_owned()]);
+    assert_eq!(config.get_aggregate("A", 0), Some(&max));
+}
+
+#[test]
+pub fn test_drag_repeated_column_to_named_slot() {
+    let config = repeated_column_config();
+    let requirements = ViewConfigRequirements {
+        names: Some(vec!["X".to_owned(), "Y".to_owned()]),
+        ..ViewConfigRequirements::default()
+    };
+
+    // Swap the second `A` into the first slot, which holds the first `A`.
+    let update = config.create_drag_drop_update(
+        "A".to_owned(),
+        Some(2),
+        0,
+        DragTarget::Active,
+        DragEffect::Move(DragTarget::Active),
+        &requirements,
+    );
+
+    let mut config = config.clone();
+    config.apply_update(update);
+    assert_eq!(config.columns, vec![
+        Some("A".to_owned()),
+        Some("B".to_owned()),
+        Some("A".to_owned())
+    ]);
+
+    let max = Aggregate::SingleAggregate(SingleAggregate::Max);
+    assert_eq!(config.get_aggregate("A", 0), Some(&max));
+    assert_eq!(config.get_aggregate("A", 1), None);
+}
+
+#[test]
+pub fn test_weighted_mean_requires_a_numeric_column() {
+    let table = MockTable::new([
+        ("A", Type::Integer),
+        ("B", Type::Float),
+        ("C", Type::String),
+    ]);
+    let metadata = block_on(SessionMetadata::from_table(&table)).unwrap();
+    for (param, is_valid) in [("B", true), ("C", false)] {
+        let aggregate = Aggregate::MultiAggregate(MultiAggregate::WeightedMean, param.to_owned());
+        let mut config = ViewConfig {
+            group_by: vec!["C".to_owned()],
+            columns: vec![Some("A".to_owned())],
+            aggregates: HashMap::from([("A".to_owned(), aggregate.clone().into())]),
+            ..ViewConfig::default()
+        };
+
+        let report = config.validate(&metadata, &HashSet::new(), vec![]);
+        let is_offered = metadata
+            .get_column_aggregates("A")
+            .unwrap()
+            .any(|y| y == aggregate);
+
+        assert_eq!(is_offered, is_valid);
+        assert_eq!(report.invalid_aggregates.is_empty(), is_valid);
+        assert_eq!(config.get_aggregate("A", 0).is_some(), is_valid);
+    }
+}
+
+#[test]
+pub fn test_session_validates_against_its_table() {
+    let session = Session::default();
+    let table = mock_table();
+    block_on(session.load_table_metadata(&table)).unwrap();
+    session.update_view_config(ViewConfigUpdate {
+        columns: Some(vec![Some("A".to_owned()), Some("x".to_owned())]),
+        group_by: Some(vec!["Q".to_owned()]),
+        expressions: Some(expressions(&[("x", "\"A\" + 1")])),
+        ..ViewConfigUpdate::default()
+    });
+
+    let report = block_on(session.validate_view_config_for(&table)).unwrap();
+    assert_eq!(
+        report.unknown_columns,
+        BTreeMap::from([("group_by", vec!["Q".to_owned()])])
+    );
+
+    assert!(session.get_view_config().group_by.is_empty());
+    assert!(session.metadata().is_column_expression("x"));
+}
+
+#[test]
+pub fn test_session_creates_view() {
+    let session = Session::default();
+    let table = mock_table().with_num_rows(3);
+    block_on(session.load_table_metadata(&table)).unwrap();
+    session.update_view_config(ViewConfigUpdate {
+        columns: Some(vec![Some("A".to_owned()), Some("B".to_owned())]),
+        ..ViewConfigUpdate::default()
+    });
+
+    let config = session.get_view_config().clone();
+    let generation = session.borrow().view_generation;
+    let view = block_on(session.create_backend_view(&table, &config, generation))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(view.dimensions.num_view_rows, 3);
+    assert_eq!(
+        session.metadata().get_column_view_type("B"),
+        Some(Type::String)
+    );
+    assert_eq!(session.metadata().get_column_view_type("C"), None);
+}
+
+#[test]
+pub fn test_session_does_not_create_stale_view() {
+    let session = Session::default();
+    let table = mock_table().with_num_rows(3);
+    block_on(session.load_table_metadata(&table)).unwrap();
+    let config = session.get_view_config().clone();
+    let generation = session.borrow().view_generation;
+
+    // Invalidate the `View` while it is being created.
+    session.update_view_config(ViewConfigUpdate {
+        group_by: Some(vec!["B".to_owned()]),
+        ..ViewConfigUpdate::default()
+    });
+
+    let view = block_on(session.create_backend_view(&table, &config, generation)).unwrap();
+    assert!(view.is_none());
+    assert_eq!(session.metadata().get_column_view_type("A"), None);
+}
+
+fn floats(values: &[Option<f64>]) -> Vec<Scalar> {
+    values
+        .iter()
+        .map(|x| x.map(Scalar::Float).unwrap_or(Scalar::Null))
+        .collect()
+}
+
+fn strings(values: &[&str]) -> Vec<Scalar> {
+    values
+        .iter()
+        .map(|x| Scalar::String(x.to_string()))
+        .collect()
+}
+
+#[test]
+pub fn test_column_values_are_paged_and_searched() {
+    let session = Session::default();
+    let table = MockTable::new([("B", Type::String)])
+        .with_values("B", strings(&["b", "a", "Ba", "a", "c"]));
+
+    block_on(session.load_table_metadata(&table)).unwrap();
+    let options = ColumnValuesOptions {
+        limit: Some(2),
+        ..ColumnValuesOptions::default()
+    };
+
+    let values = block_on(session.get_column_values_for(&table, "B".to_owned(), &options));
+    let value = |x: &str, count| ColumnValue {
+        value: Scalar::String(x.to_owned()),
+        count,
+    };
+
+    assert_eq!(values.unwrap(), ColumnValues {
+        values: vec![value("Ba", 1), value("a", 2)],
+        total: 4,
+    });
+
+    let options = ColumnValuesOptions {
+        search: Some("b".to_owned()),
+        ..ColumnValuesOptions::default()
+    };
+
+    let values = block_on(session.get_column_values_for(&table, "B".to_owned(), &options));
+    assert_eq!(values.unwrap(), ColumnValues {
+        values: vec![value("Ba", 1), value("b", 1)],
+        total: 2,
+    });
+}
+
+#[test]
+pub fn test_column_stats_are_computed_and_cached() {
+    let session = Session::default();
+    let buckets = histogram_expression("A", 1.0, 4.0);
+    let table = MockTable::new([("A", Type::Float)])
+        .with_values(
+            "A",
+            floats(&[Some(1.0), Some(2.0), Some(4.0), None, Some(3.0)]),
+        )
+        .with_expression(&buckets, Type::Float)
+        .with_values(
+            &buckets,
+            floats(&[Some(0.0), Some(3.0), Some(10.0), None, Some(6.0)]),
+        );
+
+    block_on(session.load_table_metadata(&table)).unwrap();
+    let stats = block_on(session.get_column_stats_for(&table, "A".to_owned())).unwrap();
+    assert_eq!(stats.count, 5);
+    assert_eq!(stats.null_count, 1);
+    assert_eq!(stats.distinct_count, 5);
+    assert_eq!(stats.min, Some(Scalar::Float(1.0)));
+    assert_eq!(stats.max, Some(Scalar::Float(4.0)));
+    assert_eq!(stats.mean, Some(2.5));
+    assert_eq!(
+        stats.histogram.iter().map(|x| x.count).collect::<Vec<_>>(),
+        vec![1, 0, 0, 1, 0, 0, 1, 0, 0, 1]
+    );
+
+    assert!(session.borrow().column_stats.contains_key("A"));
+    session.clear_column_stats();
+    assert!(session.borrow().column_stats.is_empty());
+}
+
+#[test]
+pub fn test_top_n_filter_is_resolved_from_the_table() {
+    let session = Session::default();
+    let table = MockTable::new([("State", Type::String), ("Sales", Type::Float)])
+        .with_values("State", strings(&["NY", "CA", "NY", "TX"]))
+        .with_values(
+            "Sales",
+            floats(&[Some(1.0), Some(4.0), Some(2.0), Some(3.0)]),
+        );
+
+    let top_n = TopN {
+        column: "Sales".to_owned(),
+        dir: SortDir::Desc,
+        n: 2.try_into().unwrap(),
+        level: 0,
+    };
+
+    let mut config = ViewConfig {
+        columns: vec![Some("Sales".to_owned())],
+        top_n: Some(top_n),
+        ..ViewConfig::default()
+    };
+
+    let filter = block_on(session.get_top_n_filter(&table, &config)).unwrap();
+    let term = FilterTerm::Scalar(Scalar::Float(3.0));
+    assert_eq!(
+        filter,
+        Some(Filter("Sales".to_owned(), FilterOp::GTE, term).into())
+    );
+
+    // "NY" and "TX" tie at `3.0`, and both are kept.
+    config.group_by = vec!["State".to_owned()];
+    let filter = block_on(session.get_top_n_filter(&table, &config)).unwrap();
+    assert_eq!(filter, None);
+
+    config.top_n.as_mut().unwrap().n = 1.try_into().unwrap();
+    let filter = block_on(session.get_top_n_filter(&table, &config)).unwrap();
+    let term = FilterTerm::Scalar(Scalar::String("CA".to_owned()));
+    assert_eq!(
+        filter,
+        Some(FilterItem::Group(FilterGroup {
+            op: FilterReducer::Or,
+            filters: vec![Filter("State".to_owned(), FilterOp::EQ, term).into()]
+        }))
+    );
+}
+
+#[test]
+pub fn test_top_n_update_invalidates_view_only_if_filter_changes() {
+    let session = Session::default();
+    let table = MockTable::new([("Sales", Type::Float)])
+        .with_values("Sales", floats(&[Some(1.0), Some(4.0), Some(2.0)]));
+
+    let config = ViewConfig {
+        columns: vec![Some("Sales".to_owned())],
+        top_n: Some(TopN {
+            column: "Sales".to_owned(),
+            dir: SortDir::Desc,
+            n: 2.try_into().unwrap(),
+            level: 0,
+        }),
+        ..ViewConfig::default()
+    };
+
+    let filter = block_on(session.get_top_n_filter(&table, &config)).unwrap();
+    let generation = session.borrow().view_generation;
+    session.borrow_mut().top_n = Some(TopNState {
+        generation,
+        config: config.clone(),
+        filter,
+        is_dirty: true,
+    });
+
+    // An update which does not change the top rows.
+    let table = table.with_values("Sales", floats(&[Some(0.0), Some(4.0), Some(2.0)]));
+    block_on(session.update_top_n_for(&table)).unwrap();
+    assert_eq!(session.borrow().view_generation, generation);
+
+    // A `Table` update is needed to re-resolve the filter.
+    let table = table.with_values("Sales", floats(&[Some(3.0), Some(4.0), Some(2.0)]));
+    block_on(session.update_top_n_for(&table)).unwrap();
+    assert_eq!(session.borrow().view_generation, generation);
+
+    session.borrow_mut().top_n.as_mut().unwrap().is_dirty = true;
+    block_on(session.update_top_n_for(&table)).unwrap();
+    assert_eq!(session.borrow().view_generation, generation + 1);
+}
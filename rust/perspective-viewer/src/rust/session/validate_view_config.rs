@@ -0,0 +1,222 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use super::backend::*;
+use super::metadata::*;
+use super::validation_report::*;
+use crate::config::*;
+use crate::utils::*;
+
+/// Validate `expressions` against `table`, returning the `ValidatedExpressions`
+/// of those which are valid and the errors of those which are not.
+pub(super) async fn validate_expressions<T: TableBackend>(
+    table: &T,
+    mut expressions: Expressions,
+) -> ApiResult<(ValidatedExpressions, Vec<InvalidExpression>)> {
+    let mut valid_recs = table.validate_expressions(&expressions).await?;
+    let mut invalid_expressions = vec![];
+    if !valid_recs.errors.is_empty() {
+        let errors = std::mem::take(&mut valid_recs.errors);
+        for (name, error) in errors.into_iter().sorted_by(|x, y| x.0.cmp(&y.0)) {
+            let expression = expressions.remove(&name).unwrap_or_default();
+            invalid_expressions.push(InvalidExpression {
+                name,
+                expression,
+                error,
+            });
+        }
+
+        valid_recs = table.validate_expressions(&expressions).await?;
+    }
+
+    Ok((valid_recs, invalid_expressions))
+}
+
+impl ViewConfig {
+    /// Remove (and report) any entry of this `ViewConfig` which references an
+    /// unknown column or is otherwise invalid.  `metadata` and
+    /// `expression_names` must be from the `Table` and the expressions which
+    /// `validate_expressions()` found valid, and `invalid_expressions` are
+    /// removed and included in the report.
+    pub(super) fn validate(
+        &mut self,
+        metadata: &SessionMetadata,
+        expression_names: &HashSet<String>,
+        invalid_expressions: Vec<InvalidExpression>,
+    ) -> ViewConfigReport {
+        let mut report = ViewConfigReport {
+            invalid_expressions,
+            ..ViewConfigReport::default()
+        };
+
+        let table_columns = metadata
+            .get_table_columns()
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<String>>();
+
+        let all_columns: HashSet<String> = table_columns.iter().cloned().collect();
+        let is_known = |name: &str| all_columns.contains(name) || expression_names.contains(name);
+
+        self.expressions
+            .retain(|name, _| !report.invalid_expressions.iter().any(|x| &x.name == name));
+
+        if self.columns.is_empty() {
+            self.columns = table_columns.into_iter().map(Some).collect();
+        }
+
+        self.coerce_filters(metadata);
+
+        // `columns` is positional for plugins with named slots, so an unknown
+        // column leaves its slot empty rather than shifting those which follow.
+        for slot in self.columns.iter_mut() {
+            if let Some(column) = slot
+                && !is_known(column)
+            {
+                report.add_unknown_column("columns", column);
+                *slot = None;
+            }
+        }
+
+        for (field, columns) in [
+            ("group_by", &mut self.group_by),
+            ("split_by", &mut self.split_by),
+        ] {
+            columns.retain(|column| {
+                let is_known = is_known(column);
+                if !is_known {
+                    report.add_unknown_column(field, column);
+                }
+
+                is_known
+            });
+        }
+
+        self.sort.retain(|sort| {
+            let is_known = is_known(&sort.0);
+            if !is_known {
+                report.add_unknown_column("sort", &sort.0);
+            }
+
+            is_known
+        });
+
+        if let Some(top_n) = &self.top_n
+            && !is_known(&top_n.column)
+        {
+            report.add_unknown_column("top_n", &top_n.column);
+            self.top_n = None;
+        }
+
+        let empty_groups = self.filter.retain_filters(&mut |filter| {
+            if !is_known(&filter.0) {
+                report.add_unknown_column("filter", &filter.0);
+                return false;
+            }
+
+            let error = match (&filter.1, &filter.2) {
+                (FilterOp::Between, FilterTerm::Relative(_)) => None,
+                (FilterOp::Between, FilterTerm::Array(xs)) if xs.len() == 2 => None,
+                (FilterOp::Between, _) => Some("`between` requires a range".to_owned()),
+                (_, FilterTerm::Relative(_)) => Some("Relative date requires `between`".to_owned()),
+                _ => filter
+                    .validate_regex()
+                    .err()
+                    .map(|err| format!("Invalid pattern: {}", err.error_message)),
+            };
+
+            match error {
+                Some(error) => {
+                    report.invalid_filters.push(InvalidFilter {
+                        column: Some(filter.0.clone()),
+                        error,
+                    });
+
+                    false
+                },
+                None => true,
+            }
+        });
+
+        for _ in 0..empty_groups {
+            report.invalid_filters.push(InvalidFilter {
+                column: None,
+                error: "Empty group".to_owned(),
+            });
+        }
+
+        let view_columns = self
+            .columns
+            .iter()
+            .flatten()
+            .chain(self.group_by.iter())
+            .chain(self.split_by.iter())
+            .chain(self.sort.iter().map(|x| &x.0))
+            .chain(self.filter.iter_filters().map(|x| &x.0))
+            .chain(self.top_n.iter().map(|x| &x.column))
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        self.aggregates
+            .retain(|column, _| view_columns.contains(column.as_str()));
+
+        // Drop the aggregates of occurrences no longer in `columns`, keeping the
+        // first for `group_by`/`sort` columns which are not in `columns`, and
+        // reset those which do not apply to their column to the default.
+        for (column, aggs) in self.aggregates.iter_mut().sorted_by(|x, y| x.0.cmp(y.0)) {
+            let count = self
+                .columns
+                .iter()
+                .filter(|x| x.as_ref() == Some(column))
+                .count();
+
+            aggs.truncate(count.max(1));
+            let invalid = aggs
+                .iter()
+                .enumerate()
+                .filter_map(|(occurrence, agg)| {
+                    let agg = agg?;
+                    let error = metadata.validate_aggregate(column, agg).err()?;
+                    Some((occurrence, agg.to_string(), error))
+                })
+                .collect::<Vec<_>>();
+
+            for (occurrence, aggregate, error) in invalid {
+                aggs.set(occurrence, None);
+                report.invalid_aggregates.push(InvalidAggregate {
+                    column: column.clone(),
+                    aggregate,
+                    error,
+                });
+            }
+        }
+
+        self.aggregates.retain(|_, aggs| !aggs.is_empty());
+        report
+    }
+
+    /// Retype each filter term to its column's type in `metadata`, as numeric
+    /// terms are ambiguous until the column type is known (`Int`, `Date` or
+    /// `Float`).
+    pub(super) fn coerce_filters(&mut self, metadata: &SessionMetadata) {
+        self.filter.for_each_filter_mut(&mut |filter| {
+            if let Some(ty) = metadata.get_column_table_type(&filter.0) {
+                filter.coerce_type(ty);
+            }
+        });
+    }
+}
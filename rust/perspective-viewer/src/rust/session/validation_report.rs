@@ -0,0 +1,69 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::js::perspective::PerspectiveValidationError;
+
+/// The problems found validating a `ViewConfig` against its `Table`.  Rather
+/// than rejecting the whole config, each offending entry is removed from it
+/// (or for an invalid aggregate, reset to the default), so that e.g. a single
+/// stale column does not discard the rest of a saved layout.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ViewConfigReport {
+    /// Names which are neither a `Table` column nor a valid expression, keyed
+    /// by the `ViewConfig` field they were removed from.
+    pub unknown_columns: BTreeMap<&'static str, Vec<String>>,
+    pub invalid_aggregates: Vec<InvalidAggregate>,
+    pub invalid_expressions: Vec<InvalidExpression>,
+    pub invalid_filters: Vec<InvalidFilter>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InvalidAggregate {
+    pub column: String,
+    pub aggregate: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InvalidExpression {
+    pub name: String,
+    pub expression: String,
+    pub error: PerspectiveValidationError,
+}
+
+/// A `Filter` which is invalid for reasons other than an unknown column, or
+/// an empty `FilterGroup` (for which `column` is `None`).
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InvalidFilter {
+    pub column: Option<String>,
+    pub error: String,
+}
+
+impl ViewConfigReport {
+    pub fn is_empty(&self) -> bool {
+        self.unknown_columns.is_empty()
+            && self.invalid_aggregates.is_empty()
+            && self.invalid_expressions.is_empty()
+            && self.invalid_filters.is_empty()
+    }
+
+    pub(super) fn add_unknown_column(&mut self, field: &'static str, column: &str) {
+        let columns = self.unknown_columns.entry(field).or_default();
+        if !columns.iter().any(|x| x == column) {
+            columns.push(column.to_owned());
+        }
+    }
+}
@@ -29,6 +29,10 @@ pub struct ViewStats {
     pub is_filtered: bool,
     pub num_table_cells: Option<(u32, u32)>,
     pub num_view_cells: Option<(u32, u32)>,
+
+    /// The number of `Table` updates, and the net change in the `Table`'s row
+    /// count, since the `Session` was paused, or `None` if it is not paused.
+    pub num_pending_updates: Option<(u32, i64)>,
 }
 
 #[derive(Clone)]
@@ -36,7 +40,7 @@ struct ViewSubscriptionData {
     view: View,
     config: ViewConfig,
     on_stats: Callback<ViewStats>,
-    on_update: Callback<()>,
+    on_update: Callback<Option<js_sys::ArrayBuffer>>,
 }
 
 /// A subscription to `on_update()` events from a Perspective `View()`, managing
@@ -47,9 +51,15 @@ pub struct ViewSubscription {
 }
 
 impl ViewSubscriptionData {
-    /// Main handler when underlying `View()` calls `on_update()`.
-    async fn on_view_update(self) -> ApiResult<JsValue> {
-        self.on_update.emit(());
+    /// Main handler when underlying `View()` calls `on_update()`, whose
+    /// `payload` has the Arrow row `delta` of the update if this subscription
+    /// is in row delta mode.
+    async fn on_view_update(self, payload: JsValue) -> ApiResult<JsValue> {
+        let delta = js_sys::Reflect::get(&payload, js_intern::js_intern!("delta"))
+            .ok()
+            .and_then(|x| x.dyn_into::<js_sys::ArrayBuffer>().ok());
+
+        self.on_update.emit(delta);
         self.clone().update_view_stats().await?;
         Ok(JsValue::UNDEFINED)
     }
@@ -68,6 +78,7 @@ impl ViewSubscriptionData {
             is_filtered: virtual_rows != num_rows,
             is_group_by: !self.config.group_by.is_empty(),
             is_split_by: !self.config.split_by.is_empty(),
+            num_pending_updates: None,
         };
 
         self.on_stats.emit(stats);
@@ -86,11 +97,14 @@ impl ViewSubscription {
     /// * `view` - a Perspective `View()` on this `table`.
     /// * `on_stats` - a callback for metadata notifications, from Perspective's
     ///   `View.on_update()`.
+    /// * `delta_updates` - whether to subscribe in row delta mode, in which
+    ///   case `on_update` is called with each update's Arrow row delta.
     pub fn new(
         view: JsPerspectiveView,
         config: ViewConfig,
+        delta_updates: bool,
         on_stats: Callback<ViewStats>,
-        on_update: Callback<()>,
+        on_update: Callback<Option<js_sys::ArrayBuffer>>,
     ) -> Self {
         let data = ViewSubscriptionData {
             view: View::new(view),
@@ -101,11 +115,19 @@ impl ViewSubscription {
 
         let fun = {
             clone!(data);
-            move |_| js_sys::Promise::from(ApiFuture::new(data.clone().on_view_update()))
+            move |payload| {
+                js_sys::Promise::from(ApiFuture::new(data.clone().on_view_update(payload)))
+            }
         };
 
         let closure = fun.into_closure();
-        data.view.on_update(closure.as_ref().unchecked_ref());
+        if delta_updates {
+            let options = json!({"mode": "row"});
+            data.view
+                .on_update_with_options(closure.as_ref().unchecked_ref(), options);
+        } else {
+            data.view.on_update(closure.as_ref().unchecked_ref());
+        }
         ApiFuture::spawn(data.clone().update_view_stats());
         Self { data, closure }
     }
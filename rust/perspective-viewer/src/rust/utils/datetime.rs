@@ -10,7 +10,7 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
-use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use wasm_bindgen::prelude::*;
 
 use crate::utils::*;
@@ -28,6 +28,17 @@ fn get_local_tz() -> FixedOffset {
     FixedOffset::west_opt(js_sys::Date::new(&0.into()).get_timezone_offset() as i32 * 60).unwrap()
 }
 
+/// The current time in the browser's local timezone.
+pub fn get_local_now() -> ApiResult<DateTime<FixedOffset>> {
+    let now = js_sys::Date::new_0();
+    let tz = FixedOffset::west_opt(now.get_timezone_offset() as i32 * 60).into_apierror()?;
+    Ok(Utc
+        .timestamp_millis_opt(now.get_time() as i64)
+        .earliest()
+        .into_apierror()?
+        .with_timezone(&tz))
+}
+
 pub fn posix_to_utc_str(x: f64) -> ApiResult<String> {
     let tz = get_local_tz();
     if x > 0_f64 {
@@ -48,3 +59,25 @@ pub fn str_to_utc_posix(val: &str) -> Result<f64, ApiError> {
     let posix = NaiveDateTime::parse_from_str(val, input_value_format(val)?)?;
     Ok(DateTime::<Utc>::from(tz.from_local_datetime(&posix).unwrap()).timestamp_millis() as f64)
 }
+
+/// Format a `Type::Date` value, which is UTC midnight of the calendar date, for
+/// an `<input type="date">`.
+pub fn posix_to_utc_date_str(x: f64) -> ApiResult<String> {
+    if x > 0_f64 {
+        Ok(Utc
+            .timestamp_millis_opt(x as i64)
+            .earliest()
+            .into_apierror()?
+            .format("%Y-%m-%d")
+            .to_string())
+    } else {
+        Err(format!("Unknown timestamp {}", x).into())
+    }
+}
+
+/// Parse the value of an `<input type="date">` to a `Type::Date` value.
+pub fn str_to_utc_date_posix(val: &str) -> ApiResult<f64> {
+    let date = NaiveDate::parse_from_str(val, "%Y-%m-%d")?;
+    let posix = date.and_hms_opt(0, 0, 0).into_apierror()?;
+    Ok(Utc.from_utc_datetime(&posix).timestamp_millis() as f64)
+}
@@ -0,0 +1,155 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::VecDeque;
+
+/// A bounded undo/redo stack of snapshots of some state `T`.  Snapshots
+/// `record()`-ed within `coalesce_ms` of the previous one replace it rather
+/// than pushing a new undo step, so a burst of edits (e.g. typing in a filter
+/// input) undoes as a single action.
+pub struct UndoHistory<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    current: Option<T>,
+    last_recorded: f64,
+    limit: usize,
+    coalesce_ms: f64,
+}
+
+impl<T> Default for UndoHistory<T> {
+    fn default() -> Self {
+        Self::new(100, 500.0)
+    }
+}
+
+impl<T> UndoHistory<T> {
+    pub fn new(limit: usize, coalesce_ms: f64) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: vec![],
+            current: None,
+            last_recorded: f64::NEG_INFINITY,
+            limit,
+            coalesce_ms,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Replace the current snapshot without creating an undo step, e.g. when
+    /// the state change is the result of `undo()` or `redo()` itself.
+    pub fn set_current(&mut self, value: T) {
+        self.current = Some(value);
+    }
+
+    /// Forget all history, such that the next `record()`-ed snapshot is the
+    /// new baseline.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.current = None;
+    }
+}
+
+impl<T: PartialEq> UndoHistory<T> {
+    /// Record a new snapshot at time `now` (in milliseconds), returning
+    /// whether the history changed.  Recording clears the redo stack.
+    pub fn record(&mut self, value: T, now: f64) -> bool {
+        if self.current.as_ref() == Some(&value) {
+            return false;
+        }
+
+        let coalesce = now - self.last_recorded < self.coalesce_ms && !self.undo.is_empty();
+        self.last_recorded = now;
+        match self.current.replace(value) {
+            Some(prev) if !coalesce => {
+                self.undo.push_back(prev);
+                if self.undo.len() > self.limit {
+                    self.undo.pop_front();
+                }
+            },
+            _ => (),
+        }
+
+        self.redo.clear();
+        true
+    }
+
+    /// Step back one snapshot, returning the snapshot to apply.
+    pub fn undo(&mut self) -> Option<&T> {
+        let prev = self.undo.pop_back()?;
+        if let Some(current) = self.current.replace(prev) {
+            self.redo.push(current);
+        }
+
+        self.last_recorded = f64::NEG_INFINITY;
+        self.current.as_ref()
+    }
+
+    /// Step forward one snapshot, returning the snapshot to apply.
+    pub fn redo(&mut self) -> Option<&T> {
+        let next = self.redo.pop()?;
+        if let Some(current) = self.current.replace(next) {
+            self.undo.push_back(current);
+        }
+
+        self.last_recorded = f64::NEG_INFINITY;
+        self.current.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_history_undo_redo() {
+        let mut history = UndoHistory::new(10, 500.0);
+        assert!(history.record(1, 0.0));
+        assert!(!history.can_undo());
+        assert!(history.record(2, 1000.0));
+        assert!(history.record(3, 2000.0));
+        assert!(!history.record(3, 3000.0));
+        assert_eq!(history.undo(), Some(&2));
+        assert_eq!(history.undo(), Some(&1));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some(&2));
+        assert!(history.can_redo());
+        assert!(history.record(4, 4000.0));
+        assert!(!history.can_redo());
+        assert_eq!(history.undo(), Some(&2));
+    }
+
+    #[test]
+    pub fn test_history_coalesce_and_limit() {
+        let mut history = UndoHistory::new(2, 500.0);
+        history.record(1, 0.0);
+        history.record(2, 1000.0);
+        history.record(3, 1100.0);
+        history.record(4, 1200.0);
+        assert_eq!(history.undo(), Some(&1));
+        assert!(!history.can_undo());
+        history.redo();
+        history.record(5, 5000.0);
+        history.record(6, 6000.0);
+        history.record(7, 7000.0);
+        assert_eq!(history.undo(), Some(&6));
+        assert_eq!(history.undo(), Some(&5));
+        assert_eq!(history.undo(), None);
+    }
+}
@@ -24,6 +24,7 @@ mod datetime;
 mod debounce;
 mod errors;
 mod futures;
+mod history;
 mod json;
 mod pubsub;
 mod scope;
@@ -42,6 +43,7 @@ pub use custom_element::*;
 pub use datetime::*;
 pub use debounce::*;
 pub use errors::*;
+pub use history::*;
 pub use pubsub::*;
 pub use scope::*;
 pub use tee::*;
@@ -43,7 +43,11 @@ for (const key of names) {
     });
 }
 
-for (const key of ["registerPlugin", "getExprTKCommands"]) {
+for (const key of [
+    "registerPlugin",
+    "getExprTKCommands",
+    "getConfigSchema",
+]) {
     Object.defineProperty(HTMLPerspectiveViewerElement, key, {
         value: async function (...args) {
             const mod = await WASM_MODULE;
@@ -99,4 +99,22 @@ export declare class HTMLPerspectiveViewerElement
      * @returns An array of JSON descriptors for ExprTK commands
      */
     static getExprtkCommands(): Promise<Array<Record<string, string>>>;
+
+    /**
+     * Get JSON Schema for the persisted config types, e.g. for validating the
+     * output of `save()` before storing it.
+     *
+     * @category Persistence
+     * @returns An object of JSON Schema documents keyed by type name, e.g.
+     * `ViewerConfig`, `ViewConfig`, `Filter`, `Sort` and `Aggregate`.
+     * @example
+     * ```javascript
+     * const schema = await customElements
+     *     .get("perspective-viewer")
+     *     .getConfigSchema();
+     *
+     * validate(schema.ViewerConfig, await viewer.save());
+     * ```
+     */
+    static getConfigSchema(): Promise<Record<string, object>>;
 }
@@ -35,6 +35,17 @@ export { IPerspectiveViewerPlugin } from "./plugin";
 export { HTMLPerspectiveViewerPluginElement } from "./plugin";
 export { IPerspectiveViewerElement } from "./viewer";
 export { PerspectiveViewerConfig } from "./viewer";
+export {
+    ColumnStats,
+    ColumnValues,
+    ColumnValuesOptions,
+    ExpansionState,
+    LinkGroup,
+    LoadOptions,
+    TableSwapReport,
+    TopN,
+    ViewConfigReport,
+} from "./viewer";
 
 export * from "./extensions";
 export * from "./migrate";
@@ -93,6 +93,24 @@ export interface IPerspectiveViewerPlugin {
      */
     get priority(): number | undefined;
 
+    /**
+     * Whether this plugin's `update()` accepts row deltas.  If `true`, the
+     * `View` is subscribed to in row delta mode, and `update()` is passed the
+     * Arrow row deltas of the `Table` updates since the previous render, in
+     * order, as its `deltas` argument.  `deltas` may still be `undefined`,
+     * e.g. when too many updates arrive within the render throttle, in which
+     * case the plugin should re-render from the `View` as usual.
+     */
+    readonly delta_updates?: boolean;
+
+    /**
+     * Whether this plugin persists the row expansion state of its `View`.  If
+     * `true`, `restore()` is passed the viewer config's `expansion` as the
+     * `expansion` field of its token, and `save()` may report the current
+     * state in the same field.
+     */
+    readonly persist_expansion?: boolean;
+
     /**
      * Render this plugin using the provided `View`.  While there is no
      * provision to cancel a render in progress per se, calling a method on
@@ -111,7 +129,8 @@ export interface IPerspectiveViewerPlugin {
     /**
      * Draw under the assumption that the `ViewConfig` has not changed since
      * the previous call to `draw()`, but the underlying data has.  Defaults to
-     * dispatch to `draw()`.
+     * dispatch to `draw()`.  For plugins with `delta_updates`, `deltas` are the
+     * Arrow row deltas of the changed data, if known.
      *
      * @example
      * ```javascript
@@ -120,7 +139,13 @@ export interface IPerspectiveViewerPlugin {
      * }
      * ```
      */
-    update(view: perspective.View): Promise<void>;
+    update(
+        view: perspective.View,
+        column_limit?: number,
+        row_limit?: number,
+        force?: boolean,
+        deltas?: ArrayBuffer[]
+    ): Promise<void>;
 
     /**
      * Clear this plugin, though it is up to the discretion of the plugin
@@ -163,7 +188,9 @@ export interface IPerspectiveViewerPlugin {
     save(): Promise<any>;
 
     /**
-     * Restore this plugin to a state previously returned by `save()`.
+     * Restore this plugin to a state previously returned by `save()`.  The
+     * `column_aliases` field of `config` is set by `<perspective-viewer>` to
+     * the display names of columns, and is re-sent whenever they change.
      */
     restore(config: any): Promise<void>;
 
@@ -16,6 +16,121 @@ export type PerspectiveViewerConfig = perspective.ViewConfig & {
     plugin?: string;
     settings?: boolean;
     plugin_config?: any;
+
+    /**
+     * Display names for columns, keyed by column name.  Aliases only change
+     * how columns are labelled; the rest of the config (and the `View`) still
+     * refers to columns by name.
+     */
+    column_aliases?: Record<string, string>;
+
+    /**
+     * Limit the `View` to the `n` rows with the highest (`"desc"`) or lowest
+     * (`"asc"`) values of `column`, ties included.  With a `group_by`, the
+     * groups at `group_by[level]` (default `0`) are limited instead, to the
+     * top `n` within each parent group.
+     */
+    top_n?: TopN | null;
+
+    /**
+     * Which `group_by` rows are expanded: all rows up to `depth` (or all rows
+     * if omitted), except for the rows in `overrides`, which are expanded or
+     * collapsed by their row path.  Applied by plugins which support it, such
+     * as the datagrid.
+     */
+    expansion?: ExpansionState | null;
+
+    /**
+     * Link this viewer to the other viewers on the page with the same link
+     * group `name`.  Selecting a row (or a `split_by` column) in any of them
+     * filters the others, which show the "Linked" filter until it is
+     * deselected.  `columns` maps the other viewers' column names to this
+     * viewer's, where they differ.
+     */
+    link_group?: LinkGroup | null;
+};
+
+export type ExpansionState = {
+    depth?: number;
+    overrides?: { row_path: any[]; expanded: boolean }[];
+};
+
+export type LinkGroup = {
+    name: string;
+    columns?: Record<string, string>;
+};
+
+export type TopN = {
+    column: string;
+    dir: "asc" | "desc";
+    n: number;
+    level?: number;
+};
+
+/**
+ * The entries dropped from a `PerspectiveViewerConfig` by `restore()` because
+ * they are invalid for the loaded `Table`.  Also the `detail` of the
+ * `"perspective-config-error"` event.
+ */
+export type ViewConfigReport = {
+    /**
+     * Unknown column names, keyed by the config field they were removed from.
+     */
+    unknown_columns: { [field: string]: Array<string> };
+    invalid_aggregates: Array<{
+        column: string;
+        aggregate: string;
+        error: string;
+    }>;
+    invalid_expressions: Array<{
+        name: string;
+        expression: string;
+        error: { error_message: string; line: number; column: number };
+    }>;
+
+    /**
+     * Invalid filters, or empty filter groups (where `column` is `null`).
+     */
+    invalid_filters: Array<{ column: string | null; error: string }>;
+};
+
+export type LoadOptions = {
+    preserve_config?: boolean;
+    renames?: Record<string, string>;
+};
+
+/**
+ * The result of `load()` with `preserve_config`.  `renamed_columns` only
+ * includes the `renames` which apply to both `Table`s, and `dropped` lists the
+ * config entries which are invalid for the new `Table`.
+ */
+export type TableSwapReport = {
+    added_columns: Array<string>;
+    removed_columns: Array<string>;
+    renamed_columns: Record<string, string>;
+    retyped_columns: Array<string>;
+    dropped: ViewConfigReport;
+};
+
+export type ColumnValuesOptions = {
+    search?: string;
+    offset?: number;
+    limit?: number;
+};
+
+export type ColumnValues = {
+    values: Array<{ value: any; count: number }>;
+    total: number;
+};
+
+export type ColumnStats = {
+    count: number;
+    null_count: number;
+    distinct_count: number;
+    min: any;
+    max: any;
+    mean: number | null;
+    histogram: Array<{ start: number; end: number; count: number }>;
 };
 
 export type RenderStats = {
@@ -77,9 +192,13 @@ export interface IPerspectiveViewerElement {
      *
      * @category Data
      * @param data A `Promise` which resolves to the `perspective.Table`
-     * @returns {Promise<void>} A promise which resolves once the data is
-     * loaded, a `perspective.View` has been created, and the active plugin has
-     * rendered.
+     * @param options With `preserve_config`, the current config is mapped
+     * onto the new `Table` (e.g. a fresh snapshot whose schema has changed),
+     * renaming columns per `renames` (new names keyed by old name).
+     * @returns {Promise<TableSwapReport | undefined>} A promise which resolves
+     * once the data is loaded, a `perspective.View` has been created, and the
+     * active plugin has rendered;  to a `TableSwapReport` with
+     * `preserve_config`.
      * @example <caption>Load perspective.table</caption>
      *
      * ```javascript
@@ -94,8 +213,20 @@ export interface IPerspectiveViewerElement {
      * const tbl = perspective.table("x,y\n1,a\n2,b");
      * my_viewer.load(tbl);
      * ```
+     * @example <caption>Swap in a snapshot with a renamed column</caption>
+     *
+     * ```javascript
+     * const report = await my_viewer.load(new_tbl, {
+     *     preserve_config: true,
+     *     renames: { px: "price" },
+     * });
+     * console.log(report.removed_columns, report.dropped);
+     * ```
      */
-    load(table: Promise<perspective.Table> | perspective.Table): Promise<void>;
+    load(
+        table: Promise<perspective.Table> | perspective.Table,
+        options?: LoadOptions
+    ): Promise<TableSwapReport | undefined>;
 
     /**
      * Redraw this `<perspective-viewer>` and plugin when its dimensions or
@@ -157,6 +288,29 @@ export interface IPerspectiveViewerElement {
      */
     setAutoPause(autopause): void;
 
+    /**
+     * Pauses this element, deleting its `View` and ignoring render calls.
+     * Updates to the `Table` are still counted, and shown as "N updates
+     * pending" in the status bar (which resumes the element when clicked).
+     *
+     * @category Util
+     * @example
+     * ```javascript
+     * viewer.pause();
+     * await table.update(data);
+     * await viewer.resume();
+     * ```
+     */
+    pause(): void;
+
+    /**
+     * Resumes a paused element, rendering once to catch up with the updates
+     * to the `Table` which arrived while it was paused.
+     *
+     * @category Util
+     */
+    resume(): Promise<void>;
+
     /**
      * Returns the `perspective.Table()` which was supplied to `load()`
      *
@@ -222,11 +376,20 @@ export interface IPerspectiveViewerElement {
      * `load()`-ed, with the same (or a type-compatible superset) `Schema`.
      * It does not need have the same rows, or even be populated.
      *
+     * Entries which are invalid for the loaded `Table` (unknown columns,
+     * expressions which fail to validate, mistyped aggregates or filters) are
+     * dropped individually rather than rejecting the whole config, and a
+     * `"perspective-config-error"` event is dispatched describing them.
+     *
      * @category Persistence
      * @param config returned by `save()`.  This can be any format returned by
      * `save()`; the specific deserialization is chosen by `typeof config`.
+     * @param options `{strict: true}` rejects a `config` with unknown fields,
+     * e.g. a misspelled `"group-by"`, with an error naming the field's path,
+     * rather than ignoring them.
      * @returns A promise which resolves when the changes have been applied and
-     * rendered.
+     * rendered, to a `ViewConfigReport` of any dropped entries, or `undefined`
+     * if the config was applied in full.
      * @example <caption>Restore a viewer from `localStorage`</caption>
      *
      * ```javascript
@@ -236,8 +399,9 @@ export interface IPerspectiveViewerElement {
      * ```
      */
     restore(
-        config: PerspectiveViewerConfig | string | ArrayBuffer
-    ): Promise<void>;
+        config: PerspectiveViewerConfig | string | ArrayBuffer,
+        options?: { strict?: boolean }
+    ): Promise<ViewConfigReport | undefined>;
 
     /**
      * Serialize this element's attribute/interaction state, but _not_ the
@@ -249,8 +413,12 @@ export interface IPerspectiveViewerElement {
      *
      * @category Persistence
      * @param format The serialization format - `json` (JavaScript object),
-     * `arraybuffer` or `string`.  `restore()` uses the returned config's type
-     * to infer format.
+     * `arraybuffer`, `string` or `query`.  `restore()` uses the returned
+     * config's type to infer format.  `query` is a human-readable text form of
+     * the view config only, e.g.
+     * `group_by region | filter price > 10 | sort volume desc`, which is
+     * convenient to share;  clauses omitted from a `query` are reset to their
+     * defaults on `restore()`, except for `columns`.
      * @returns a serialized element in the chosen format.
      * @example <caption>Save a viewer to `localStorage`</caption>
      *
@@ -264,10 +432,40 @@ export interface IPerspectiveViewerElement {
     save(format: "json"): Promise<PerspectiveViewerConfig>;
     save(format: "arraybuffer"): Promise<ArrayBuffer>;
     save(format: "string"): Promise<string>;
+    save(format: "query"): Promise<string>;
     save(
-        format?: "json" | "arraybuffer" | "string"
+        format?: "json" | "arraybuffer" | "string" | "query"
     ): Promise<PerspectiveViewerConfig | string | ArrayBuffer>;
 
+    /**
+     * Compute the minimal difference between two configs, as a pair of
+     * partial configs which contain only the fields that changed.  `patch`
+     * can be passed to `restore()` to move a viewer from `from` to `to`, and
+     * `inverse` to move it back.  The same `patch` is the `detail` of the
+     * `"perspective-config-diff"` event dispatched after every
+     * `"perspective-config-update"`, relative to the previous update.
+     *
+     * @category Persistence
+     * @param from The original config, in any format returned by `save()`.
+     * @param to The new config, in any format returned by `save()`, or this
+     * viewer's current config if omitted.
+     * @returns The `patch` and `inverse` partial configs.
+     * @example <caption>Sync only the changes to a remote viewer</caption>
+     *
+     * ```javascript
+     * viewer.addEventListener("perspective-config-diff", (event) => {
+     *     socket.send(JSON.stringify(event.detail));
+     * });
+     * ```
+     */
+    diff(
+        from: PerspectiveViewerConfig | string | ArrayBuffer,
+        to?: PerspectiveViewerConfig | string | ArrayBuffer
+    ): Promise<{
+        patch: PerspectiveViewerConfig;
+        inverse: PerspectiveViewerConfig;
+    }>;
+
     /**
      * Flush any pending modifications to this `<perspective-viewer>`.  Since
      * `<perspective-viewer>`'s API is almost entirely `async`, it may take
@@ -305,6 +503,62 @@ export interface IPerspectiveViewerElement {
      */
     reset(all): Promise<void>;
 
+    /**
+     * Expand the `group_by` rows to `depth`, where `0` shows only the
+     * top-level groups, replacing any rows expanded or collapsed individually.
+     * Saved as the `expansion` field of the config.
+     *
+     * @category Persistence
+     * @param depth The number of `group_by` levels to expand.
+     * @example
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * await viewer.restore({group_by: ["Region", "State", "City"]});
+     * await viewer.expandToDepth(1);
+     * ```
+     */
+    expandToDepth(depth: number): Promise<void>;
+
+    /**
+     * Revert the last change to this element's config, as reported by the
+     * `"perspective-config-update"` event.  Changes made in quick succession
+     * are undone as one step.  The history is cleared when a new `Table` is
+     * loaded.  Also bound to `Ctrl+Z`.
+     *
+     * @category Persistence
+     * @returns {Promise<boolean>} Whether there was a change to undo.
+     * @example
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * await viewer.restore({group_by: ["State"]});
+     * await viewer.undo();
+     * ```
+     */
+    undo(): Promise<boolean>;
+
+    /**
+     * Re-apply the last change reverted by `undo()`.  Also bound to
+     * `Ctrl+Shift+Z`.
+     *
+     * @category Persistence
+     * @returns {Promise<boolean>} Whether there was a change to redo.
+     */
+    redo(): Promise<boolean>;
+
+    /**
+     * Whether `undo()` has a change to revert.
+     *
+     * @category Persistence
+     */
+    canUndo(): boolean;
+
+    /**
+     * Whether `redo()` has a change to re-apply.
+     *
+     * @category Persistence
+     */
+    canRedo(): boolean;
+
     /**
      * Deletes this element and clears it's internal state (but not its
      * user state).  This (or the underlying `perspective.view`'s equivalent
@@ -345,6 +599,78 @@ export interface IPerspectiveViewerElement {
      */
     copy(flat: boolean): Promise<void>;
 
+    /**
+     * Sets the table name which the SQL export (from the export and copy
+     * menus) queries.  The exported query translates this element's view
+     * config to ANSI SQL, marking any part without a SQL equivalent with an
+     * `UNTRANSLATED` comment.  Filters the engine ignores, such as a `null`
+     * term with an operator other than `is null`, are omitted and listed in a
+     * `SKIPPED` comment.
+     *
+     * @category UI Action
+     * @param name The table name, which may be schema-qualified with `.`.
+     * Defaults to this element's `title`, or `"untitled"`.
+     * @example
+     * ```javascript
+     * viewer.setSqlTableName("warehouse.superstore");
+     * ```
+     */
+    setSqlTableName(name?: string): void;
+
+    /**
+     * Gets the display names for columns set by the `column_aliases` field of
+     * the config, keyed by column name.  Plugins receive the same aliases in the
+     * `column_aliases` field of their `restore()` token.
+     *
+     * @category Plugin
+     * @returns The column aliases, which are empty if none have been set.
+     * @example
+     * ```javascript
+     * await viewer.restore({column_aliases: {px_last_usd_adj: "Price"}});
+     * viewer.getColumnAliases(); // {px_last_usd_adj: "Price"}
+     * ```
+     */
+    getColumnAliases(): Record<string, string>;
+
+    /**
+     * Gets the distinct values of a column (or expression column) of the
+     * `Table`, in ascending order, with the number of rows with each value.
+     * `search` matches values which begin with it, ignoring case, and along
+     * with `offset` and `limit` is applied by the engine for `"string"`
+     * columns, so is suitable for high-cardinality columns.
+     *
+     * @category Data
+     * @param column The column name.
+     * @param options The `search`, `offset` and `limit` to apply.
+     * @returns A page of values, and the `total` number which match `search`.
+     * @example
+     * ```javascript
+     * const { values, total } = await viewer.getColumnValues("State", {
+     *     search: "new",
+     *     limit: 10,
+     * });
+     * ```
+     */
+    getColumnValues(
+        column: string,
+        options?: ColumnValuesOptions
+    ): Promise<ColumnValues>;
+
+    /**
+     * Get summary statistics of a column of the `Table`: the row, `null` and
+     * distinct value counts, the `min` and `max` and, for numeric columns,
+     * the `mean` and a 10-bucket `histogram`.  Statistics are cached until
+     * the `Table` is updated.
+     *
+     * @category Data
+     * @param column The column name.
+     * @example
+     * ```javascript
+     * const { min, max, null_count } = await viewer.getColumnStats("Sales");
+     * ```
+     */
+    getColumnStats(column: string): Promise<ColumnStats>;
+
     /**
      * Restyles the elements and to pick up any style changes.  While most of
      * perspective styling is plain CSS and can be updated at any time, some
@@ -81,6 +81,16 @@ fn opt(outpath: &Path) {
         .execute();
 }
 
+/// Generate JSON Schema for the persisted config types.
+fn schema(outdir: &Path) {
+    Command::new("cargo")
+        .args(["run"])
+        .args(["-p", "perspective-schema"])
+        .args(["--"])
+        .arg(outdir)
+        .execute();
+}
+
 fn main() {
     let outdir = Path::new("dist/pkg");
     let artifact = "perspective";
@@ -88,6 +98,7 @@ fn main() {
     build(None);
     bindgen(outdir, artifact);
     opt(outpath);
+    schema(Path::new("dist/schema"));
 }
 
 trait SimpleCommand {
@@ -83,6 +83,10 @@ test.describe("Events", () => {
             sort: [],
             theme: "Pro Light",
             title: null,
+            diff: {
+                columns: ["Profit", "Sales"],
+                group_by: ["State"],
+            },
         });
 
         const contents = await get_contents(page);